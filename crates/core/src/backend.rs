@@ -6,20 +6,38 @@ pub mod count;
 pub mod delete;
 pub mod insert;
 pub mod load;
-mod map;
+pub(crate) mod map;
+pub mod routing;
+pub(crate) mod statement;
 pub mod update;
 
 use async_trait::async_trait;
 
 use crate::{
-    alias_format::AliasFormat, error::ToqlError, page::Page, sql::Sql, sql_arg::SqlArg,
-    sql_builder::build_result::BuildResult, table_mapper_registry::TableMapperRegistry,
+    alias_format::AliasFormat,
+    clock::Timestamps,
+    error::ToqlError,
+    field_handler::RegexSyntax,
+    field_policy::FieldPolicy,
+    metrics::Metrics,
+    page::Page,
+    query::Query,
+    query_limits::QueryLimits,
+    sql::Sql,
+    sql_arg::SqlArg,
+    sql_builder::{build_result::BuildResult, SqlBuilder},
+    table_mapper_registry::TableMapperRegistry,
 };
 use std::{
     collections::{HashMap, HashSet},
     sync::{RwLockReadGuard, RwLockWriteGuard},
 };
 
+lazy_static::lazy_static! {
+    // No restrictions, used by backends that do not provide restrictions
+    static ref NO_RESTRICTIONS: HashMap<String, Query<()>> = HashMap::new();
+}
+
 /// Backend interface that must be implemented by databases to use the default Toql functions.
 /// The Backend is implemented for a Row and Error type
 /// It contains database specific callbacks for database independend functions
@@ -38,6 +56,42 @@ where
     fn alias_format(&self) -> AliasFormat;
     /// Return the aux params. These will be used together with the query aux params to resolve aux params in SQL expressions and handlers
     fn aux_params(&self) -> &HashMap<String, SqlArg>;
+    /// Return the query limits. These are enforced by the SQL builder for every query.
+    /// By default queries are unlimited.
+    fn query_limits(&self) -> &QueryLimits {
+        &crate::query_limits::UNLIMITED
+    }
+    /// Return the field policy. It is consulted by the SQL builder in addition to the role restrictions.
+    /// By default all fields are allowed.
+    fn field_policy(&self) -> &FieldPolicy {
+        &crate::field_policy::UNRESTRICTED
+    }
+    /// Return the mandatory restrictions for each entity type. They are added by the SQL builder
+    /// to every select, count and delete statement. By default there are no restrictions.
+    fn restrictions(&self) -> &HashMap<String, Query<()>> {
+        &NO_RESTRICTIONS
+    }
+    /// Return the regex syntax of the database. It is used to build the _RE_ filter.
+    /// By default `REGEXP` is used.
+    fn regex_syntax(&self) -> &RegexSyntax {
+        &crate::field_handler::REGEXP
+    }
+    /// Return true, if selects are additionally ordered by the entity keys.
+    /// By default only the query orders.
+    fn stable_order(&self) -> bool {
+        false
+    }
+
+    /// Return the hook for statement metrics. By default there is none.
+    fn metrics(&self) -> Option<&dyn Metrics> {
+        None
+    }
+
+    /// Return the source of the time for timestamp fields. By default there is none
+    /// and the [SystemClock](crate::clock::SystemClock) is used.
+    fn timestamps(&self) -> Option<&Timestamps> {
+        None
+    }
 
     /// Execute a select statement on the database and return a vector of rows
     async fn select_sql(&mut self, sql: Sql) -> Result<Vec<R>, E>;
@@ -58,3 +112,24 @@ where
     // Execute an insert statement and return new keys
     async fn insert_sql(&mut self, sql: Sql) -> Result<Vec<SqlArg>, E>; // New ids in descending order
 }
+
+/// Create a SQL builder for the mapper `ty` with all settings of the backend.
+/// Every statement must be built with this, so that no setting is missed.
+pub(crate) fn sql_builder<'a, B, R, E>(
+    backend: &B,
+    ty: &'a str,
+    registry: &'a TableMapperRegistry,
+) -> SqlBuilder<'a>
+where
+    B: Backend<R, E> + ?Sized,
+    E: From<ToqlError>,
+{
+    SqlBuilder::new(ty, registry)
+        .with_aux_params(backend.aux_params().clone()) // todo ref
+        .with_roles(backend.roles().clone()) // todo ref
+        .with_query_limits(backend.query_limits().clone())
+        .with_field_policy(backend.field_policy().clone())
+        .with_restrictions(backend.restrictions().clone())
+        .with_regex_syntax(backend.regex_syntax().clone())
+        .with_stable_order(backend.stable_order())
+}
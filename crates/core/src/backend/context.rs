@@ -1,10 +1,23 @@
-use crate::{alias_format::AliasFormat, sql_arg::SqlArg};
+use crate::clock::Timestamps;
+use crate::metrics::Metrics;
+use crate::{
+    alias_format::AliasFormat, field_handler::RegexSyntax, field_policy::FieldPolicy, query::Query,
+    query_limits::QueryLimits, sql_arg::SqlArg,
+};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 pub struct Context {
     pub roles: HashSet<String>,
     pub aux_params: HashMap<String, SqlArg>,
     pub alias_format: AliasFormat,
+    pub query_limits: QueryLimits,
+    pub field_policy: FieldPolicy,
+    pub restrictions: HashMap<String, Query<()>>,
+    pub regex_syntax: RegexSyntax,
+    pub stable_order: bool,
+    pub metrics: Option<Arc<dyn Metrics>>,
+    pub timestamps: Option<Timestamps>,
 }
 
 impl Context {
@@ -13,6 +26,13 @@ impl Context {
             roles: HashSet::new(),
             aux_params: HashMap::new(),
             alias_format,
+            query_limits: QueryLimits::new(),
+            field_policy: FieldPolicy::new(),
+            restrictions: HashMap::new(),
+            regex_syntax: RegexSyntax::Regexp,
+            stable_order: false,
+            metrics: None,
+            timestamps: None,
         }
     }
 }
@@ -1,11 +1,24 @@
 use super::context::Context;
-use crate::{alias_format::AliasFormat, sql_arg::SqlArg};
+use crate::clock::{Clock, Timestamps};
+use crate::metrics::Metrics;
+use crate::{
+    alias_format::AliasFormat, field_handler::RegexSyntax, field_policy::FieldPolicy, query::Query,
+    query_limits::QueryLimits, sql_arg::SqlArg, table_mapper::mapped::Mapped,
+};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 pub struct ContextBuilder {
     pub roles: HashSet<String>,
     pub aux_params: HashMap<String, SqlArg>,
     pub alias_format: AliasFormat,
+    pub query_limits: QueryLimits,
+    pub field_policy: FieldPolicy,
+    pub restrictions: HashMap<String, Query<()>>,
+    pub regex_syntax: RegexSyntax,
+    pub stable_order: bool,
+    pub metrics: Option<Arc<dyn Metrics>>,
+    pub timestamps: Option<Timestamps>,
 }
 
 impl ContextBuilder {
@@ -14,6 +27,13 @@ impl ContextBuilder {
             roles: HashSet::new(),
             aux_params: HashMap::new(),
             alias_format: AliasFormat::Canonical,
+            query_limits: QueryLimits::new(),
+            field_policy: FieldPolicy::new(),
+            restrictions: HashMap::new(),
+            regex_syntax: RegexSyntax::Regexp,
+            stable_order: false,
+            metrics: None,
+            timestamps: None,
         }
     }
 
@@ -29,11 +49,64 @@ impl ContextBuilder {
         self.alias_format = alias_format;
         self
     }
+    pub fn with_query_limits(mut self, query_limits: QueryLimits) -> Self {
+        self.query_limits = query_limits;
+        self
+    }
+    pub fn with_field_policy(mut self, field_policy: FieldPolicy) -> Self {
+        self.field_policy = field_policy;
+        self
+    }
+    /// Restrict every select, count and delete of the entity `T`.
+    /// Only the filters, predicates and aux params of the restriction are used.
+    /// Its aux params are kept with the restriction and do not need to be repeated in the context.
+    /// Multiple restrictions for the same entity are combined with AND.
+    pub fn with_restriction<T: Mapped>(mut self, restriction: Query<T>) -> Self {
+        let type_name = <T as Mapped>::type_name();
+        let restriction = match self.restrictions.remove(&type_name) {
+            Some(r) => r.restrict(restriction.clone_for_type()),
+            None => restriction.clone_for_type(),
+        };
+        self.restrictions.insert(type_name, restriction);
+        self
+    }
+    pub fn with_regex_syntax(mut self, regex_syntax: RegexSyntax) -> Self {
+        self.regex_syntax = regex_syntax;
+        self
+    }
+    /// Order every select additionally by the entity keys.
+    /// This makes paging deterministic, if the ordered values tie.
+    pub fn with_stable_order(mut self, stable_order: bool) -> Self {
+        self.stable_order = stable_order;
+        self
+    }
+    /// Report the metrics of every executed statement to the hook.
+    pub fn with_metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+    /// Fill timestamp fields with the time of the clock instead of the system time.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.timestamps = Some(Timestamps::Clock(clock));
+        self
+    }
+    /// Fill timestamp fields with the clock or with a database expression, such as `NOW()`.
+    pub fn with_timestamps(mut self, timestamps: Timestamps) -> Self {
+        self.timestamps = Some(timestamps);
+        self
+    }
     pub fn build(self) -> Context {
         Context {
             roles: self.roles,
             aux_params: self.aux_params,
             alias_format: self.alias_format,
+            query_limits: self.query_limits,
+            field_policy: self.field_policy,
+            restrictions: self.restrictions,
+            regex_syntax: self.regex_syntax,
+            stable_order: self.stable_order,
+            metrics: self.metrics,
+            timestamps: self.timestamps,
         }
     }
 }
@@ -1,17 +1,22 @@
-use super::{map, Backend};
+use super::{
+    map,
+    statement::{self, Statement},
+    Backend,
+};
 use crate::toql_api::count::Count;
 use crate::{
-    alias_translator::AliasTranslator, error::ToqlError, parameter_map::ParameterMap, query::Query,
-    sql_builder::SqlBuilder, table_mapper::mapped::Mapped,
+    alias_translator::AliasTranslator, error::ToqlError, metrics::Purpose,
+    parameter_map::ParameterMap, query::Query, table_mapper::mapped::Mapped,
 };
 use std::borrow::Borrow;
 
+#[tracing::instrument(name = "toql", skip_all, fields(ty = %<T as Mapped>::type_name(), op = "count", query = %query.borrow()))]
 pub async fn count<B, Q, T, R, E>(backend: &mut B, query: Q) -> std::result::Result<u64, E>
 where
     B: Backend<R, E>,
     Q: Borrow<Query<T>> + Send + Sync,
     T: Count,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
 {
     {
         let registry = &mut *backend.registry_mut()?;
@@ -26,9 +31,7 @@ where
 
     let sql = {
         let registry = &*backend.registry()?;
-        let mut builder = SqlBuilder::new(&ty, registry)
-            .with_aux_params(backend.aux_params().clone()) // TODO ref
-            .with_roles(backend.roles().clone()); // TODO ref;
+        let mut builder = super::sql_builder(backend, &ty, registry);
         let result = builder.build_count("", query.borrow(), false)?; // All filters, not just count selections
 
         result
@@ -36,6 +39,8 @@ where
             .map_err(ToqlError::from)?
     };
 
-    let page_count = backend.select_count_sql(sql).await?;
+    let type_name = <T as Mapped>::type_name();
+    let statement = Statement::new(&type_name, "", Purpose::Count);
+    let page_count = statement::select_count(backend, statement, sql).await?;
     Ok(page_count)
 }
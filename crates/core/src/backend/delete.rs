@@ -1,30 +1,34 @@
-use super::{map, Backend};
+use super::{
+    map,
+    statement::{self, Statement},
+    Backend,
+};
 use crate::{
-    alias_translator::AliasTranslator, error::ToqlError, parameter_map::ParameterMap, query::Query,
-    sql_builder::SqlBuilder, sql_expr::SqlExpr, table_mapper::mapped::Mapped,
+    alias_translator::AliasTranslator, error::ToqlError, metrics::Purpose,
+    parameter_map::ParameterMap, query::Query, sql_expr::SqlExpr, table_mapper::mapped::Mapped,
 };
 use std::borrow::Borrow;
 
 use crate::toql_api::delete::Delete;
 
+#[tracing::instrument(name = "toql", skip_all, fields(ty = %<T as Mapped>::type_name(), op = "delete", query = %query.borrow()))]
 pub async fn delete<B, Q, T, R, E>(backend: &mut B, query: Q) -> std::result::Result<(), E>
 where
     B: Backend<R, E>,
     T: Delete,
     Q: Borrow<Query<T>>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
 {
     {
         let registry = &mut *backend.registry_mut()?;
         map::map::<T>(registry)?;
     }
 
-    let mut result = SqlBuilder::new(
+    let mut result = super::sql_builder(
+        backend,
         &<T as Mapped>::type_name(),
         &*backend.registry().map_err(ToqlError::from)?,
     )
-    .with_aux_params(backend.aux_params().clone()) // todo ref
-    .with_roles(backend.roles().clone()) // todo ref
     .build_delete(query.borrow())?;
 
     // Add alias after Verb
@@ -47,6 +51,8 @@ where
         let sql = result
             .to_sql(&p, &mut alias_translator)
             .map_err(ToqlError::from)?;
-        backend.execute_sql(sql).await
+        let type_name = <T as Mapped>::type_name();
+        let statement = Statement::new(&type_name, "", Purpose::Delete);
+        statement::execute(backend, statement, sql).await
     }
 }
@@ -1,17 +1,24 @@
 use crate::{
     alias_translator::AliasTranslator,
-    backend::{map, Backend},
+    backend::{
+        map,
+        statement::{self, Statement},
+        Backend,
+    },
+    clock::Now,
     error::ToqlError,
+    metrics::Purpose,
     parameter_map::ParameterMap,
     query::field_path::FieldPath,
     result::Result,
     sql::Sql,
+    sql_arg::SqlArg,
     sql_builder::{sql_builder_error::SqlBuilderError, SqlBuilder},
     sql_expr::resolver::Resolver,
     table_mapper::{mapped::Mapped, TableMapper},
     toql_api::{insert::Insert, paths::Paths},
     tree::{
-        tree_identity::{IdentityAction, TreeIdentity},
+        tree_identity::{FillAction, IdentityAction, TreeIdentity},
         tree_insert::TreeInsert,
     },
 };
@@ -21,6 +28,7 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
+#[tracing::instrument(name = "toql", skip_all, fields(ty = %<T as Mapped>::type_name(), op = "insert", paths = ?paths.list))]
 pub async fn insert<B, Q, T, R, E>(
     backend: &mut B,
     entities: &mut [Q],
@@ -30,13 +38,16 @@ where
     Q: BorrowMut<T>,
     T: Insert,
     B: Backend<R, E>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
 {
     {
         let registry = &mut *backend.registry_mut()?;
         map::map::<T>(registry)?;
     }
 
+    // Same time for all timestamps of this call
+    let now = Now::new(backend.timestamps());
+
     // Build up execution tree
     // Path `a_b_merge1_c_d_merge2_e` becomes
     // [j0] = [a, c, e]
@@ -68,10 +79,11 @@ where
                 entities,
                 &path,
                 &mut std::iter::repeat(&true),
+                &now,
                 "",
                 "",
             )?;
-            insert_sql(backend, path, sql, entities).await?;
+            insert_sql(backend, path, sql, &now, entities).await?;
         }
     }
 
@@ -82,10 +94,11 @@ where
         entities,
         &home_path,
         &mut std::iter::repeat(&true),
+        &now,
         "",
         "",
     )?;
-    insert_sql(backend, home_path, sql, entities).await?;
+    insert_sql(backend, home_path, sql, &now, entities).await?;
 
     // Insert merges
     for p in &merges {
@@ -96,11 +109,12 @@ where
             entities,
             &path,
             &mut std::iter::repeat(&true),
+            &now,
             "",
             "",
         )?;
 
-        insert_sql(backend, path, sql, entities).await?;
+        insert_sql(backend, path, sql, &now, entities).await?;
     }
 
     // Insert partials from top to bottom
@@ -118,11 +132,12 @@ where
                 entities,
                 &path,
                 &mut std::iter::repeat(&true),
+                &now,
                 "",
                 "",
             )?;
 
-            insert_sql(backend, path, sql, entities).await?;
+            insert_sql(backend, path, sql, &now, entities).await?;
         }
     }
     Ok(())
@@ -131,34 +146,85 @@ where
 pub(crate) async fn insert_sql<'a, Q, B, T, R, E>(
     backend: &mut B,
     path: FieldPath<'_>,
-    sql: Option<Sql>,
+    sql: Option<(Sql, u64)>,
+    now: &Now,
     entities: &mut [Q],
 ) -> std::result::Result<(), E>
 where
     B: Backend<R, E>,
     Q: BorrowMut<T>,
-    T: TreeIdentity,
-    E: From<ToqlError>,
+    T: TreeIdentity + Mapped,
+    E: From<ToqlError> + std::fmt::Display,
 {
-    if sql.is_none() {
-        return Ok(());
-    }
-    let sql = sql.unwrap();
-
+    let (sql, rows) = match sql {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    let tenant = tenant::<T, _, _, _>(backend, &path)?;
+    let type_name = <T as Mapped>::type_name();
+    let statement = Statement::new(&type_name, path.as_str(), Purpose::Insert);
     let descendents = path.children();
     if <T as TreeIdentity>::auto_id(descendents)? {
-        let ids = backend.insert_sql(sql).await?;
+        let ids = statement::insert(backend, statement, sql).await?;
         set_tree_identity(
             IdentityAction::Set(RefCell::new(ids)),
             entities,
             path.children(),
         )?;
     } else {
-        backend.execute_sql(sql).await?;
+        statement::insert_rows(backend, statement, sql, rows).await?;
+    }
+    if let Some(now) = now.arg() {
+        fill_tree(
+            FillAction::StampInsert(now.to_owned()),
+            entities,
+            path.children(),
+        )?;
+    }
+    if let Some(tenant) = tenant {
+        fill_tree(FillAction::Tenant(tenant), entities, path.children())?;
     }
     Ok(())
 }
 
+// Returns the tenant of the context, if the entities at `path` are separated by tenant.
+pub(crate) fn tenant<T, B, R, E>(backend: &B, path: &FieldPath) -> Result<Option<SqlArg>>
+where
+    T: Mapped,
+    B: Backend<R, E>,
+    E: From<ToqlError>,
+{
+    let ty = <T as Mapped>::type_name();
+    let registry = &*backend.registry()?;
+    let sql_builder = SqlBuilder::new(&ty, registry);
+    let mapper = sql_builder.mapper_for_query_path(path)?;
+    match mapper.tenant() {
+        Some((_, aux_param)) => match backend.aux_params().get(aux_param) {
+            Some(tenant) => Ok(Some(tenant.to_owned())),
+            None => Err(SqlBuilderError::QueryParamMissing(aux_param.to_string()).into()),
+        },
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn fill_tree<'a, T, Q, I>(
+    action: FillAction,
+    entities: &mut [Q],
+    descendents: I,
+) -> Result<()>
+where
+    T: TreeIdentity,
+    Q: BorrowMut<T>,
+    I: Iterator<Item = FieldPath<'a>> + Clone,
+{
+    for e in entities.iter_mut() {
+        <T as TreeIdentity>::fill(e.borrow_mut(), descendents.clone(), &action)?;
+    }
+
+    Ok(())
+}
+
 pub(crate) fn set_tree_identity<'a, T, Q, I>(
     action: IdentityAction,
     entities: &mut [Q],
@@ -182,14 +248,15 @@ pub(crate) fn build_insert_sql<'a, T, Q, B, R, E, J>(
     entities: &[Q],
     query_path: &FieldPath,
     inserts: &mut J,
+    now: &Now,
     _modifier: &str,
     _extra: &str,
-) -> Result<Option<Sql>>
+) -> Result<Option<(Sql, u64)>>
 where
     B: Backend<R, E>,
     T: Mapped + TreeInsert,
     Q: BorrowMut<T>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
     J: Iterator<Item = &'a bool>,
 {
     use crate::sql_expr::SqlExpr;
@@ -200,19 +267,24 @@ where
 
     let mut d = query_path.children();
     let columns_expr = <T as TreeInsert>::columns(&mut d)?;
+
+    // Every inserted row takes a `true` from the iterator
+    let mut rows = 0;
+    let mut counted_inserts = inserts.inspect(|i| rows += **i as u64);
     for e in entities {
-        //let mut d = query_path.children();
         <T as TreeInsert>::values(
             e.borrow(),
             query_path.children(),
             backend.roles(),
-            inserts,
+            &mut counted_inserts,
             &mut values_expr,
         )?;
     }
+    drop(counted_inserts);
     if values_expr.is_empty() {
         return Ok(None);
     }
+    let values_expr = Resolver::replace_aux_params(values_expr, &now.exprs());
 
     let mut alias_translator = AliasTranslator::new(backend.alias_format());
 
@@ -220,10 +292,11 @@ where
     let sql_builder = SqlBuilder::new(&ty, registry);
     let mapper = sql_builder.mapper_for_query_path(query_path)?;
     let canonical_table_alias = &mapper.canonical_table_alias;
-    let table_name = &mapper.table_name;
 
     let aux_params = [backend.aux_params()];
     let aux_params_map = ParameterMap::new(&aux_params);
+    let table_name =
+        Resolver::table_name(&mapper.table_name, Some(&aux_params_map)).map_err(ToqlError::from)?;
     let resolver = Resolver::new()
         .with_aux_params(&aux_params_map)
         .with_self_alias(&canonical_table_alias);
@@ -244,7 +317,7 @@ where
     insert_stmt.pop(); // Remove ', '
     insert_stmt.pop();
 
-    Ok(Some(Sql(insert_stmt, values_sql.1)))
+    Ok(Some((Sql(insert_stmt, values_sql.1), rows)))
 }
 
 pub fn plan_insert_order<T, S: AsRef<str>>(
@@ -1,15 +1,20 @@
-use super::{map, Backend};
+use super::{
+    map,
+    statement::{self, Statement},
+    Backend,
+};
 use crate::{
     alias_translator::AliasTranslator,
     error::ToqlError,
     from_row::FromRow,
     keyed::Keyed,
+    metrics::Purpose,
     page::Page,
     parameter_map::ParameterMap,
     query::{field_path::FieldPath, Query},
     sql::Sql,
-    sql_builder::SqlBuilder,
-    sql_expr::{resolver::Resolver, PredicateColumn, SqlExpr},
+    sql_builder::{build_result::BuildResult, sql_builder_error::SqlBuilderError, SqlBuilder},
+    sql_expr::{resolver::Resolver, PredicateColumn, SqlExpr, SqlExprToken},
     table_mapper::mapped::Mapped,
     tree::{tree_index::TreeIndex, tree_merge::TreeMerge, tree_predicate::TreePredicate},
 };
@@ -21,6 +26,7 @@ use std::{
 use crate::page_counts::PageCounts;
 use crate::toql_api::load::Load;
 
+#[tracing::instrument(name = "toql", skip_all, fields(ty = %<T as Mapped>::type_name(), op = "load", query = %query.borrow(), page = ?page))]
 pub async fn load<B, Q, T, R, E>(
     backend: &mut B,
     query: Q,
@@ -28,7 +34,7 @@ pub async fn load<B, Q, T, R, E>(
 ) -> std::result::Result<(Vec<T>, Option<PageCounts>), E>
 where
     B: Backend<R, E>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
     T: Load<R, E> + Send,
     Q: Borrow<Query<T>> + Sync + Send,
     <T as Keyed>::Key: FromRow<R, E>,
@@ -67,7 +73,7 @@ where
     T: Load<R, E>,
     Q: Borrow<Query<T>> + Sync,
     <T as crate::keyed::Keyed>::Key: FromRow<R, E>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
 {
     let ty = <T as Mapped>::type_name();
     let mut pending_home_paths = HashSet::new();
@@ -96,9 +102,7 @@ where
 
         let mut result = {
             let registry = backend.registry()?;
-            let mut builder = SqlBuilder::new(&ty, &*registry)
-                .with_aux_params(backend.aux_params().clone()) // todo ref
-                .with_roles(backend.roles().clone()); // todo ref// Add alias format or translator to constructor
+            let mut builder = super::sql_builder(backend, &ty, &*registry);
             builder.build_select(home_path.as_str(), query.borrow())?
         };
 
@@ -111,12 +115,12 @@ where
 
         // Build merge join
         // Get merge join and custom on predicate from mapper
-        let (mut merge_join_sql_expr, merge_join_predicate) = {
+        let (mut merge_join_sql_expr, merge_join_predicate, merge_limit) = {
             let registry = backend.registry()?;
-            let builder = SqlBuilder::new(&ty, &*registry)
-                .with_aux_params(backend.aux_params().clone()) // TODO ref
-                .with_roles(backend.roles().clone());
-            builder.merge_expr(&home_path)?
+            let builder = super::sql_builder(backend, &ty, &*registry);
+            let (merge_join, merge_predicate) = builder.merge_expr(&home_path)?;
+            let merge_limit = builder.merge_limit(home_path, query.borrow())?;
+            (merge_join, merge_predicate, merge_limit)
         };
 
         let merge_join_predicate = merge_resolver
@@ -124,13 +128,28 @@ where
             .map_err(ToqlError::from)?;
 
         // Get key columns
-        let (merge_join, key_select_expr) = {
+        let (merge_join, key_select_expr, merged_key_expr) = {
             let parent_home_path = parent_home_path.unwrap_or_default();
             let registry = backend.registry()?;
             let builder = SqlBuilder::new(&ty, &*registry); // No aux params for key
             let (key_select_expr, key_join) =
                 builder.columns_expr(parent_home_path.as_str(), &merge_base_alias)?;
 
+            // Keys of merged entities identify ranked rows for the merge limit
+            let merged_key_expr = if merge_limit.is_some() {
+                let (merged_key_expr, merged_key_join) =
+                    builder.columns_expr(home_path.as_str(), &other_alias)?;
+                if !merged_key_join.is_empty() {
+                    return Err(ToqlError::from(SqlBuilderError::MergeLimitInvalid(
+                        home_path.to_string(),
+                    ))
+                    .into());
+                }
+                merged_key_expr
+            } else {
+                SqlExpr::new()
+            };
+
             let merge_join = if key_join.is_empty() {
                 &merge_join_sql_expr
             } else {
@@ -142,10 +161,11 @@ where
                     .resolve(merge_join)
                     .map_err(ToqlError::from)?,
                 key_select_expr,
+                merged_key_expr,
             )
         };
 
-        result.set_preselect(key_select_expr); // Select key columns for indexing
+        result.set_preselect(key_select_expr.clone()); // Select key columns for indexing
         let space = merge_join.ends_with_literal(" "); // Recursions add whitespace
         if !result.join_expr.is_empty() {
             result.push_join(SqlExpr::literal(" "));
@@ -194,6 +214,21 @@ where
             result.push_join(predicate_expr);
             result.push_join(SqlExpr::literal(")"));
 
+            if let Some(limit) = merge_limit {
+                let limit_predicate =
+                    merge_limit_predicate(&result, &key_select_expr, &merged_key_expr, limit);
+                if result.where_expr.is_empty() {
+                    result.where_expr = limit_predicate;
+                } else {
+                    let mut where_expr = SqlExpr::literal("(");
+                    where_expr
+                        .extend(result.where_expr.clone())
+                        .push_literal(") AND ")
+                        .extend(limit_predicate);
+                    result.where_expr = where_expr;
+                }
+            }
+
             // Build SQL query statement
 
             let mut alias_translator = AliasTranslator::new(backend.alias_format());
@@ -204,7 +239,8 @@ where
                 .map_err(ToqlError::from)?;
 
             // Load from database
-            backend.select_sql(sql).await? // Default vector size
+            let statement = Statement::new(&ty, home_path, Purpose::Merge);
+            statement::select(backend, statement, sql).await? // Default vector size
         };
 
         // Build index
@@ -236,6 +272,64 @@ where
     Ok(pending_home_paths)
 }
 
+// Build predicate that keeps the first `limit` merged entities for every parent.
+// The rows of the merge query are ranked with a window function and
+// identified by the parent and merged keys.
+fn merge_limit_predicate(
+    result: &BuildResult,
+    parent_key_expr: &SqlExpr,
+    merged_key_expr: &SqlExpr,
+    limit: u64,
+) -> SqlExpr {
+    let mut key_columns: Vec<SqlExpr> = Vec::new();
+    for key_expr in [parent_key_expr, merged_key_expr].iter() {
+        let mut column = SqlExpr::new();
+        for token in key_expr.tokens() {
+            match token {
+                SqlExprToken::Literal(l) if l == ", " => {
+                    key_columns.push(column);
+                    column = SqlExpr::new();
+                }
+                t => {
+                    column.extend(SqlExpr::from(vec![t.to_owned()]));
+                }
+            }
+        }
+        key_columns.push(column);
+    }
+    let key_names = (0..key_columns.len())
+        .map(|i| format!("toql_k{}", i))
+        .collect::<Vec<_>>();
+
+    let mut e = SqlExpr::literal("(");
+    e.extend(parent_key_expr.clone())
+        .push_literal(", ")
+        .extend(merged_key_expr.clone())
+        .push_literal(") IN (SELECT ")
+        .push_literal(key_names.join(", "))
+        .push_literal(" FROM (SELECT ");
+    for (column, name) in key_columns.into_iter().zip(key_names.iter()) {
+        e.extend(column).push_literal(format!(" AS {}, ", name));
+    }
+    e.push_literal("ROW_NUMBER() OVER (PARTITION BY ")
+        .extend(parent_key_expr.clone())
+        .push_literal(" ORDER BY ")
+        .extend(if result.order_expr.is_empty() {
+            merged_key_expr.clone()
+        } else {
+            result.order_expr.clone()
+        })
+        .push_literal(") AS toql_rn FROM ")
+        .extend(result.from_expr.clone())
+        .push_literal(" ")
+        .extend(result.join_expr.clone());
+    if !result.where_expr.is_empty() {
+        e.push_literal(" WHERE ").extend(result.where_expr.clone());
+    }
+    e.push_literal(format!(") toql_ranked WHERE toql_rn <= {})", limit));
+    e
+}
+
 async fn load_top<B, Q, T, R, E>(
     backend: &mut B,
     query: &Q,
@@ -246,19 +340,24 @@ where
     T: Load<R, E> + Send + FromRow<R, E>,
     Q: Borrow<Query<T>> + Sync + Send,
     <T as crate::keyed::Keyed>::Key: FromRow<R, E>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
 {
     let alias_format = backend.alias_format();
 
     let ty = <T as Mapped>::type_name();
 
+    if let Some(Page::Counted(_, size)) | Some(Page::Uncounted(_, size)) = &page {
+        backend
+            .query_limits()
+            .check_page_size(*size as u64)
+            .map_err(ToqlError::from)?;
+    }
+
     let (mut result, count_result) = {
         let registry = &*backend.registry()?;
         tracing::event!(tracing::Level::INFO, query =  %query.borrow(), "Building SQL for Toql query.");
 
-        let mut builder = SqlBuilder::new(&ty, registry)
-            .with_aux_params(backend.aux_params().clone()) // todo ref
-            .with_roles(backend.roles().clone()); // todo ref;
+        let mut builder = super::sql_builder(backend, &ty, registry);
         let result = builder.build_select("", query.borrow())?;
         let count_result = if matches!(page, Some(Page::Counted(_, _))) {
             let count_result = builder.build_count("", query.borrow(), true)?;
@@ -284,7 +383,7 @@ where
     };
 
     let entities = {
-        let rows = backend.select_sql(sql).await?;
+        let rows = statement::select(backend, Statement::new(&ty, "", Purpose::Load), sql).await?;
         let mut entities = Vec::with_capacity(rows.len());
 
         for r in rows {
@@ -299,7 +398,8 @@ where
 
     let page_counts = if let Some(count_result) = count_result {
         let count_sql = Sql::new(); // TODO for postgres
-        let filtered = backend.select_max_page_size_sql(count_sql).await?;
+        let statement = Statement::new(&ty, "", Purpose::PageCount);
+        let filtered = statement::select_max_page_size(backend, statement, count_sql).await?;
 
         let total_page_size_sql = {
             let aux_params = [backend.aux_params()];
@@ -308,7 +408,8 @@ where
                 .to_sql(&aux_params, &mut alias_translator)
                 .map_err(|e| e.into())?
         };
-        let total = backend.select_count_sql(total_page_size_sql).await?;
+        let statement = Statement::new(&ty, "", Purpose::TotalCount);
+        let total = statement::select_count(backend, statement, total_page_size_sql).await?;
         Some(PageCounts { filtered, total })
     } else {
         None
@@ -0,0 +1,169 @@
+//! A backend that splits reads and writes between a primary and a replica database.
+//!
+//! Selects, counts and page sizes go to the replica, inserts, updates and deletes to the primary.
+//! After the first write, all statements go to the primary until the unit of work ends with [end_unit](RoutingBackend::end_unit),
+//! so that written data is read back consistently. Transactions run on the primary too.
+//! They are either started with [begin_transaction](RoutingBackend::begin_transaction)
+//! or detected from executed `START TRANSACTION`, `BEGIN`, `COMMIT` and `ROLLBACK` statements.
+//!
+//! The table mappers, roles, aux params and all other settings are taken from the primary.
+//! Both backends must be created with the same [Cache](crate::cache::Cache), so that entities are only mapped once.
+//!
+//! ### Example
+//! ```ignore
+//! let mut toql = RoutingBackend::new(primary, replica);
+//! let users = toql.load_many(query!(User, "*")).await?; // On replica
+//! toql.insert_one(&mut user, paths!(top)).await?;      // On primary
+//! let users = toql.load_many(query!(User, "*")).await?; // On primary
+//! toql.end_unit();
+//! ```
+
+pub mod toql_api;
+
+use super::Backend;
+use crate::{
+    error::ToqlError, page::Page, sql::Sql, sql_arg::SqlArg, sql_builder::build_result::BuildResult,
+};
+use async_trait::async_trait;
+use std::marker::PhantomData;
+
+/// Routes selects to a replica and all other statements to a primary backend.
+pub struct RoutingBackend<P, S, R, E> {
+    primary: P,
+    replica: S,
+    in_transaction: bool,
+    written: bool,
+    last_select_on_primary: bool,
+    marker: PhantomData<fn() -> (R, E)>,
+}
+
+impl<P, S, R, E> RoutingBackend<P, S, R, E>
+where
+    P: Backend<R, E>,
+    S: Backend<R, E>,
+    E: From<ToqlError>,
+{
+    /// Create a routing backend from a primary and a replica backend.
+    ///
+    /// # Panics
+    /// If the backends do not share the same [Cache](crate::cache::Cache).
+    pub fn new(primary: P, replica: S) -> Self {
+        if let (Ok(p), Ok(r)) = (primary.registry(), replica.registry()) {
+            assert!(
+                std::ptr::eq(&*p, &*r),
+                "primary and replica backend must share the same cache"
+            );
+        }
+        RoutingBackend {
+            primary,
+            replica,
+            in_transaction: false,
+            written: false,
+            last_select_on_primary: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Route all statements to the primary until [end_transaction](Self::end_transaction).
+    pub fn begin_transaction(&mut self) {
+        self.in_transaction = true;
+    }
+
+    pub fn end_transaction(&mut self) {
+        self.in_transaction = false;
+    }
+
+    /// End the unit of work. Selects go to the replica again,
+    /// unless a transaction is running.
+    pub fn end_unit(&mut self) {
+        self.written = false;
+    }
+
+    /// Return true, if selects currently go to the primary.
+    pub fn reads_primary(&self) -> bool {
+        self.in_transaction || self.written
+    }
+
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+    pub fn primary_mut(&mut self) -> &mut P {
+        &mut self.primary
+    }
+    pub fn replica(&self) -> &S {
+        &self.replica
+    }
+    pub fn replica_mut(&mut self) -> &mut S {
+        &mut self.replica
+    }
+    pub fn into_inner(self) -> (P, S) {
+        (self.primary, self.replica)
+    }
+
+    // Track transactions that are controlled with SQL statements
+    fn track_transaction(&mut self, sql: &Sql) {
+        let statement = sql.0.trim_start().to_ascii_uppercase();
+        if statement.starts_with("START TRANSACTION") || statement.starts_with("BEGIN") {
+            self.in_transaction = true;
+        } else if statement.starts_with("COMMIT") || statement.starts_with("ROLLBACK") {
+            self.in_transaction = false;
+        } else {
+            self.written = true;
+        }
+    }
+}
+
+#[async_trait]
+impl<P, S, R, E> Backend<R, E> for RoutingBackend<P, S, R, E>
+where
+    P: Backend<R, E> + Send,
+    S: Backend<R, E> + Send,
+    R: Send,
+    E: From<ToqlError> + Send,
+{
+    backend_settings!(forward: primary);
+
+    async fn select_sql(&mut self, sql: Sql) -> Result<Vec<R>, E> {
+        self.last_select_on_primary = self.reads_primary();
+        if self.last_select_on_primary {
+            self.primary.select_sql(sql).await
+        } else {
+            self.replica.select_sql(sql).await
+        }
+    }
+
+    fn prepare_page(&self, result: &mut BuildResult, page: &Page) {
+        if self.reads_primary() {
+            self.primary.prepare_page(result, page)
+        } else {
+            self.replica.prepare_page(result, page)
+        }
+    }
+
+    // The page size belongs to the connection of the last select
+    async fn select_max_page_size_sql(&mut self, sql: Sql) -> Result<u64, E> {
+        if self.last_select_on_primary {
+            self.primary.select_max_page_size_sql(sql).await
+        } else {
+            self.replica.select_max_page_size_sql(sql).await
+        }
+    }
+
+    async fn select_count_sql(&mut self, sql: Sql) -> Result<u64, E> {
+        if self.reads_primary() {
+            self.primary.select_count_sql(sql).await
+        } else {
+            self.replica.select_count_sql(sql).await
+        }
+    }
+
+    async fn execute_sql(&mut self, sql: Sql) -> Result<(), E> {
+        self.track_transaction(&sql);
+        self.primary.execute_sql(sql).await
+    }
+
+    async fn insert_sql(&mut self, sql: Sql) -> Result<Vec<SqlArg>, E> {
+        self.written = true;
+        self.primary.insert_sql(sql).await
+    }
+}
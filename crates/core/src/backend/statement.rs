@@ -0,0 +1,182 @@
+//! Executes SQL statements on the backend in a tracing span and reports their metrics.
+
+use super::Backend;
+use crate::error::ToqlError;
+use crate::metrics::{Purpose, StatementMetrics};
+use crate::sql::Sql;
+use crate::sql_arg::SqlArg;
+use std::fmt::Display;
+use std::time::Instant;
+use tracing::{field, Instrument, Span};
+
+/// Describes a statement for tracing and metrics.
+pub(crate) struct Statement<'a> {
+    pub(crate) type_name: &'a str,
+    pub(crate) path: &'a str,
+    pub(crate) purpose: Purpose,
+}
+
+impl<'a> Statement<'a> {
+    pub(crate) fn new(type_name: &'a str, path: &'a str, purpose: Purpose) -> Self {
+        Statement {
+            type_name,
+            path,
+            purpose,
+        }
+    }
+
+    fn span(&self, sql: &Sql) -> Span {
+        tracing::info_span!(
+            "sql",
+            ty = %self.type_name,
+            path = %self.path,
+            purpose = %self.purpose,
+            sql = %sql.to_unsafe_string(),
+            duration_us = field::Empty,
+            rows = field::Empty,
+            ids = field::Empty,
+            error = field::Empty,
+        )
+    }
+
+    // Record measurements in span and report them to the metrics hook
+    fn finish<B, R, E, T>(
+        &self,
+        backend: &B,
+        span: &Span,
+        start: Instant,
+        rows: Option<u64>,
+        result: &std::result::Result<T, E>,
+    ) where
+        B: Backend<R, E>,
+        E: From<ToqlError> + Display,
+    {
+        let duration = start.elapsed();
+        span.record("duration_us", duration.as_micros() as u64);
+        if let Some(rows) = rows {
+            span.record("rows", rows);
+        }
+        if let Err(e) = result {
+            span.record("error", field::display(e));
+        }
+        if let Some(metrics) = backend.metrics() {
+            metrics.record(&StatementMetrics {
+                type_name: self.type_name,
+                path: self.path,
+                purpose: self.purpose,
+                duration,
+                rows: rows.unwrap_or(0),
+                failed: result.is_err(),
+            });
+        }
+    }
+}
+
+pub(crate) async fn select<B, R, E>(
+    backend: &mut B,
+    statement: Statement<'_>,
+    sql: Sql,
+) -> std::result::Result<Vec<R>, E>
+where
+    B: Backend<R, E>,
+    E: From<ToqlError> + Display,
+{
+    let span = statement.span(&sql);
+    let start = Instant::now();
+    let result = backend.select_sql(sql).instrument(span.clone()).await;
+    let rows = result.as_ref().map_or(0, |r| r.len() as u64);
+    statement.finish(backend, &span, start, Some(rows), &result);
+    result
+}
+
+pub(crate) async fn select_count<B, R, E>(
+    backend: &mut B,
+    statement: Statement<'_>,
+    sql: Sql,
+) -> std::result::Result<u64, E>
+where
+    B: Backend<R, E>,
+    E: From<ToqlError> + Display,
+{
+    let span = statement.span(&sql);
+    let start = Instant::now();
+    let result = backend.select_count_sql(sql).instrument(span.clone()).await;
+    let rows = *result.as_ref().unwrap_or(&0);
+    statement.finish(backend, &span, start, Some(rows), &result);
+    result
+}
+
+pub(crate) async fn select_max_page_size<B, R, E>(
+    backend: &mut B,
+    statement: Statement<'_>,
+    sql: Sql,
+) -> std::result::Result<u64, E>
+where
+    B: Backend<R, E>,
+    E: From<ToqlError> + Display,
+{
+    let span = statement.span(&sql);
+    let start = Instant::now();
+    let result = backend
+        .select_max_page_size_sql(sql)
+        .instrument(span.clone())
+        .await;
+    let rows = *result.as_ref().unwrap_or(&0);
+    statement.finish(backend, &span, start, Some(rows), &result);
+    result
+}
+
+pub(crate) async fn execute<B, R, E>(
+    backend: &mut B,
+    statement: Statement<'_>,
+    sql: Sql,
+) -> std::result::Result<(), E>
+where
+    B: Backend<R, E>,
+    E: From<ToqlError> + Display,
+{
+    let span = statement.span(&sql);
+    let start = Instant::now();
+    let result = backend.execute_sql(sql).instrument(span.clone()).await;
+    statement.finish(backend, &span, start, None, &result);
+    result
+}
+
+pub(crate) async fn insert<B, R, E>(
+    backend: &mut B,
+    statement: Statement<'_>,
+    sql: Sql,
+) -> std::result::Result<Vec<SqlArg>, E>
+where
+    B: Backend<R, E>,
+    E: From<ToqlError> + Display,
+{
+    let span = statement.span(&sql);
+    let start = Instant::now();
+    let result = backend.insert_sql(sql).instrument(span.clone()).await;
+    let rows = result.as_ref().map_or(0, |ids| ids.len() as u64);
+    if let (Ok(ids), false) = (&result, span.is_disabled()) {
+        let ids = ids.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        span.record("ids", field::display(ids.join(", ")));
+    }
+    statement.finish(backend, &span, start, Some(rows), &result);
+    result
+}
+
+// Insert without generated keys, the number of rows is known from building the statement
+pub(crate) async fn insert_rows<B, R, E>(
+    backend: &mut B,
+    statement: Statement<'_>,
+    sql: Sql,
+    rows: u64,
+) -> std::result::Result<(), E>
+where
+    B: Backend<R, E>,
+    E: From<ToqlError> + Display,
+{
+    let span = statement.span(&sql);
+    let start = Instant::now();
+    let result = backend.execute_sql(sql).instrument(span.clone()).await;
+    statement.finish(backend, &span, start, Some(rows), &result);
+    result
+}
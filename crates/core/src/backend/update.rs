@@ -1,7 +1,10 @@
 use crate::{
     alias_translator::AliasTranslator,
+    clock::Now,
     error::ToqlError,
     from_row::FromRow,
+    metrics::Purpose,
+    parameter_map::ParameterMap,
     result::Result,
     sql::Sql,
     sql_arg::SqlArg,
@@ -9,15 +12,17 @@ use crate::{
     sql_expr::{resolver::Resolver, PredicateColumn, SqlExpr},
     table_mapper::{mapped::Mapped, TableMapper},
     tree::{
-        tree_identity::{IdentityAction, TreeIdentity},
+        tree_identity::{FillAction, IdentityAction, TreeIdentity},
         tree_predicate::TreePredicate,
         tree_update::TreeUpdate,
     },
 };
 
 use super::{
-    insert::{build_insert_sql, set_tree_identity},
-    map, Backend,
+    insert::{build_insert_sql, fill_tree, set_tree_identity, tenant},
+    map,
+    statement::{self, Statement},
+    Backend,
 };
 use std::{
     borrow::{Borrow, BorrowMut},
@@ -30,6 +35,7 @@ use crate::{
     toql_api::{fields::Fields, update::Update},
 };
 
+#[tracing::instrument(name = "toql", skip_all, fields(ty = %<T as Mapped>::type_name(), op = "update", fields = ?fields.list))]
 pub async fn update<B, Q, T, R, E>(
     backend: &mut B,
     entities: &mut [Q],
@@ -39,7 +45,7 @@ where
     T: Update,
     Q: BorrowMut<T> + Sync,
     B: Backend<R, E>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
     SqlArg: FromRow<R, E>,
 {
     // Ensure entity is mapped
@@ -48,6 +54,9 @@ where
         map::map::<T>(registry)?;
     }
 
+    // Same time for all timestamps of this call
+    let now = Now::new(backend.timestamps());
+
     let (field_order, merge_order, fields_map) =
         plan_update_order::<T, _>(&*backend.registry()?, fields.list.as_ref())?;
 
@@ -71,7 +80,7 @@ where
             )?;
         }
 
-        update_field_or_join(backend, entities, &query_path, fields).await?;
+        update_field_or_join(backend, entities, &query_path, fields, &now).await?;
     }
 
     // Save insert positions for merged entities
@@ -115,7 +124,7 @@ where
             let should_insert = should_insert_vec.iter();
             delete_removed_merges(backend, entities, &query_field, should_insert).await?;
             let mut should_insert = should_insert_vec.iter();
-            insert_new_merges(backend, entities, &query_field, &mut should_insert).await?;
+            insert_new_merges(backend, entities, &query_field, &mut should_insert, &now).await?;
         }
     }
     Ok(())
@@ -126,22 +135,33 @@ async fn update_field_or_join<T, B, R, E, Q>(
     entities: &mut [Q],
     query_path: &str,
     fields: &HashSet<String>,
+    now: &Now,
 ) -> std::result::Result<(), E>
 where
     T: Update,
     Q: BorrowMut<T> + Sync,
     B: Backend<R, E>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
 {
     // Ensure keys are valid
     let sqls = {
         let field_path = FieldPath::from(&query_path);
-        build_update_sql(backend, entities, &field_path, &fields, "", "")
+        build_update_sql(backend, entities, &field_path, &fields, now, "", "")
     }?;
 
     // Update joins
+    let type_name = <T as Mapped>::type_name();
+    let updated = !sqls.is_empty();
     for sql in sqls {
-        backend.execute_sql(sql).await?;
+        let statement = Statement::new(&type_name, query_path, Purpose::Update);
+        statement::execute(backend, statement, sql).await?;
+    }
+    if let (true, Some(now)) = (updated, now.arg()) {
+        fill_tree(
+            FillAction::StampUpdate(now.to_owned()),
+            entities,
+            FieldPath::from(query_path).children(),
+        )?;
     }
     Ok(())
 }
@@ -151,56 +171,82 @@ async fn insert_new_merges<'b, T, B, R, E, Q, J>(
     entities: &mut [Q],
     query_path: &str,
     should_insert: &mut J,
+    now: &Now,
 ) -> std::result::Result<(), E>
 where
     T: Update,
     Q: BorrowMut<T> + Sync,
     B: Backend<R, E>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
     SqlArg: FromRow<R, E>,
     J: Iterator<Item = &'b bool> + Clone,
 {
     use std::cell::RefCell;
     let merge_path = FieldPath::from(&query_path);
 
+    // Partial tables of merges
+    let mut partial_merge_paths = Vec::new();
+    add_partial_tables::<T>(&*backend.registry()?, &merge_path, &mut partial_merge_paths)?;
+
+    // New merges still have invalid keys, stamp them before their keys are set
+    if let Some(now) = now.arg() {
+        fill_tree(
+            FillAction::StampInsertInvalid(now.to_owned()),
+            entities,
+            merge_path.children(),
+        )?;
+        for partial_merge_path in &partial_merge_paths {
+            fill_tree(
+                FillAction::StampInsertInvalid(now.to_owned()),
+                entities,
+                FieldPath::from(partial_merge_path).children(),
+            )?;
+        }
+    }
+
     // Insert
     let sql = build_insert_sql(
         backend,
         entities,
         &merge_path,
         &mut should_insert.clone(),
+        now,
         "",
         "",
     )?;
-    if let Some(sql) = sql {
+    let type_name = <T as Mapped>::type_name();
+    if let Some((sql, rows)) = sql {
         // Insert and refresh generated id
+        let statement = Statement::new(&type_name, query_path, Purpose::Insert);
         if <T as TreeIdentity>::auto_id(merge_path.children())? {
-            let ids = backend.insert_sql(sql).await?;
+            let ids = statement::insert(backend, statement, sql).await?;
             set_tree_identity(
                 IdentityAction::SetInvalid(RefCell::new(ids)),
                 &mut entities.borrow_mut(),
                 merge_path.children(),
             )?;
         } else {
-            backend.execute_sql(sql).await?;
+            statement::insert_rows(backend, statement, sql, rows).await?;
+        }
+        if let Some(tenant) = tenant::<T, _, _, _>(backend, &merge_path)? {
+            fill_tree(FillAction::Tenant(tenant), entities, merge_path.children())?;
         }
     }
 
     // Cascade insert for partial tables
-    let mut partial_merge_paths = Vec::new();
-    add_partial_tables::<T>(&*backend.registry()?, &merge_path, &mut partial_merge_paths)?;
-
     for partial_merge_path in partial_merge_paths {
         let sql = build_insert_sql(
             backend,
             entities,
             &FieldPath::from(&partial_merge_path),
             &mut should_insert.clone(),
+            now,
             "",
             "",
         )?;
-        if let Some(sql) = sql {
-            backend.execute_sql(sql).await?;
+        if let Some((sql, rows)) = sql {
+            let statement = Statement::new(&type_name, &partial_merge_path, Purpose::Insert);
+            statement::insert_rows(backend, statement, sql, rows).await?;
         }
     }
 
@@ -217,7 +263,7 @@ where
     T: Mapped + TreePredicate,
     Q: Borrow<T> + Sync,
     B: Backend<R, E>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
     J: Iterator<Item = &'b bool>,
 {
     let parent_path = FieldPath::trim_basename(&merge_path);
@@ -265,18 +311,20 @@ where
     let sql = {
         let type_name = <T as Mapped>::type_name();
         let registry = &*backend.registry()?;
-        let mut sql_builder = SqlBuilder::new(&type_name, registry)
-            .with_aux_params(backend.aux_params().clone()) // todo ref
-            .with_roles(backend.roles().clone()); // todo ref
+        let mut sql_builder = super::sql_builder(backend, &type_name, registry);
         let delete_expr = sql_builder.build_merge_delete(&merge_path, key_predicate.to_owned())?;
 
         let mut alias_translator = AliasTranslator::new(backend.alias_format());
-        let resolver = Resolver::new();
+        let aux_params = [backend.aux_params()];
+        let aux_params = ParameterMap::new(&aux_params);
+        let resolver = Resolver::new().with_aux_params(&aux_params);
         resolver
             .to_sql(&delete_expr, &mut alias_translator)
             .map_err(ToqlError::from)?
     };
-    backend.execute_sql(sql).await?;
+    let type_name = <T as Mapped>::type_name();
+    let statement = Statement::new(&type_name, merge_path.as_str(), Purpose::Delete);
+    statement::execute(backend, statement, sql).await?;
 
     Ok(())
 }
@@ -286,6 +334,7 @@ fn build_update_sql<B, T, Q, R, E>(
     entities: &[Q],
     path: &FieldPath,
     fields: &HashSet<String>,
+    now: &Now,
     _modifier: &str,
     _extra: &str,
 ) -> Result<Vec<Sql>>
@@ -293,7 +342,7 @@ where
     B: Backend<R, E>,
     T: Mapped + TreeUpdate,
     Q: Borrow<T>,
-    E: From<ToqlError>,
+    E: From<ToqlError> + std::fmt::Display,
 {
     let mut alias_translator = AliasTranslator::new(backend.alias_format());
 
@@ -305,10 +354,14 @@ where
         TreeUpdate::update(e.borrow(), descendents, fields, backend.roles(), &mut exprs)?;
     }
 
-    // Resolve to Sql
-    let resolver = Resolver::new();
+    // Resolve to Sql, aux params are only used by table names
+    let aux_params = [backend.aux_params()];
+    let aux_params = ParameterMap::new(&aux_params);
+    let resolver = Resolver::new().with_aux_params(&aux_params);
 
+    let timestamps = now.exprs();
     for sql_expr in exprs {
+        let sql_expr = Resolver::replace_aux_params(sql_expr, &timestamps);
         let update_sql = resolver
             .to_sql(&sql_expr, &mut alias_translator)
             .map_err(ToqlError::from)?;
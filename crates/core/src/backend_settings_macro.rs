@@ -0,0 +1,125 @@
+//! Macro to implement the settings of the [Backend](crate::backend::Backend) trait.
+//!
+//! A backend takes its settings either from a [Context](crate::backend::context::Context)
+//! and a [Cache](crate::cache::Cache) or forwards them to another backend, that it wraps.
+//! The statement functions are async and must still be implemented by hand.
+//!
+//! ### Example
+//! ```rust, ignore
+//! #[async_trait]
+//! impl Backend<Row, ToqlError> for MyBackend {
+//!     toql_core::backend_settings!(context: context, cache: cache);
+//!     // async fn select_sql(...)
+//! }
+//!
+//! #[async_trait]
+//! impl Backend<Row, ToqlError> for Wrapper {
+//!     toql_core::backend_settings!(forward: inner);
+//!     // async fn select_sql(...)
+//! }
+//! ```
+#[macro_export]
+macro_rules! backend_settings {
+    (context: $context:ident, cache: $cache:ident) => {
+        fn registry(
+            &self,
+        ) -> std::result::Result<
+            std::sync::RwLockReadGuard<'_, $crate::table_mapper_registry::TableMapperRegistry>,
+            $crate::error::ToqlError,
+        > {
+            self.$cache
+                .registry
+                .read()
+                .map_err($crate::error::ToqlError::from)
+        }
+        fn registry_mut(
+            &mut self,
+        ) -> std::result::Result<
+            std::sync::RwLockWriteGuard<'_, $crate::table_mapper_registry::TableMapperRegistry>,
+            $crate::error::ToqlError,
+        > {
+            self.$cache
+                .registry
+                .write()
+                .map_err($crate::error::ToqlError::from)
+        }
+        fn roles(&self) -> &std::collections::HashSet<String> {
+            &self.$context.roles
+        }
+        fn alias_format(&self) -> $crate::alias_format::AliasFormat {
+            self.$context.alias_format.clone()
+        }
+        fn aux_params(&self) -> &std::collections::HashMap<String, $crate::sql_arg::SqlArg> {
+            &self.$context.aux_params
+        }
+        fn query_limits(&self) -> &$crate::query_limits::QueryLimits {
+            &self.$context.query_limits
+        }
+        fn field_policy(&self) -> &$crate::field_policy::FieldPolicy {
+            &self.$context.field_policy
+        }
+        fn restrictions(&self) -> &std::collections::HashMap<String, $crate::query::Query<()>> {
+            &self.$context.restrictions
+        }
+        fn regex_syntax(&self) -> &$crate::field_handler::RegexSyntax {
+            &self.$context.regex_syntax
+        }
+        fn stable_order(&self) -> bool {
+            self.$context.stable_order
+        }
+        fn metrics(&self) -> Option<&dyn $crate::metrics::Metrics> {
+            self.$context.metrics.as_deref()
+        }
+        fn timestamps(&self) -> Option<&$crate::clock::Timestamps> {
+            self.$context.timestamps.as_ref()
+        }
+    };
+    (forward: $backend:ident) => {
+        fn registry(
+            &self,
+        ) -> std::result::Result<
+            std::sync::RwLockReadGuard<'_, $crate::table_mapper_registry::TableMapperRegistry>,
+            $crate::error::ToqlError,
+        > {
+            self.$backend.registry()
+        }
+        fn registry_mut(
+            &mut self,
+        ) -> std::result::Result<
+            std::sync::RwLockWriteGuard<'_, $crate::table_mapper_registry::TableMapperRegistry>,
+            $crate::error::ToqlError,
+        > {
+            self.$backend.registry_mut()
+        }
+        fn roles(&self) -> &std::collections::HashSet<String> {
+            self.$backend.roles()
+        }
+        fn alias_format(&self) -> $crate::alias_format::AliasFormat {
+            self.$backend.alias_format()
+        }
+        fn aux_params(&self) -> &std::collections::HashMap<String, $crate::sql_arg::SqlArg> {
+            self.$backend.aux_params()
+        }
+        fn query_limits(&self) -> &$crate::query_limits::QueryLimits {
+            self.$backend.query_limits()
+        }
+        fn field_policy(&self) -> &$crate::field_policy::FieldPolicy {
+            self.$backend.field_policy()
+        }
+        fn restrictions(&self) -> &std::collections::HashMap<String, $crate::query::Query<()>> {
+            self.$backend.restrictions()
+        }
+        fn regex_syntax(&self) -> &$crate::field_handler::RegexSyntax {
+            self.$backend.regex_syntax()
+        }
+        fn stable_order(&self) -> bool {
+            self.$backend.stable_order()
+        }
+        fn metrics(&self) -> Option<&dyn $crate::metrics::Metrics> {
+            self.$backend.metrics()
+        }
+        fn timestamps(&self) -> Option<&$crate::clock::Timestamps> {
+            self.$backend.timestamps()
+        }
+    };
+}
@@ -0,0 +1,89 @@
+//! Clock for timestamp fields.
+//!
+//! Fields with `#[toql(created_at)]` or `#[toql(updated_at)]` are not taken from the entity.
+//! Inserts fill both, updates fill only `updated_at`.
+//!
+//! The [Timestamps] of the [Context](crate::backend::context::Context) decide where the time comes from:
+//! - With a [Clock] the time is sent as argument and also written back into the entities.
+//!   Without timestamps in the context the [SystemClock] is used.
+//! - With a database expression, such as `NOW()`, the database sets the time.
+//!   The entities are not updated, reload them to get the time.
+use crate::{sql_arg::SqlArg, sql_expr::SqlExpr};
+use chrono::{Timelike, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Name of the aux param, that the derived code uses as placeholder for the timestamp.
+pub const TIMESTAMP_AUX_PARAM: &str = "toql_timestamp";
+
+/// Hook that returns the current time for timestamp fields.
+///
+/// Closures returning a [SqlArg] implement this trait, for example
+/// `Arc::new(|| SqlArg::from(chrono::Utc::now().naive_utc()))`.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SqlArg;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> SqlArg + Send + Sync,
+{
+    fn now(&self) -> SqlArg {
+        self()
+    }
+}
+
+/// Clock that returns the current UTC time of the system in whole seconds.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SqlArg {
+        let now = Utc::now().naive_utc();
+        SqlArg::from(now.with_nanosecond(0).unwrap_or(now))
+    }
+}
+
+/// Source of the time for timestamp fields.
+pub enum Timestamps {
+    /// Send the time of the clock as argument and write it back into the entities.
+    Clock(Arc<dyn Clock>),
+    /// Let the database fill the timestamps with an SQL expression, such as `NOW()`.
+    /// The entities keep their timestamps, they must be reloaded to get the time.
+    Database(String),
+}
+
+/// Time of one insert or update call.
+pub(crate) enum Now {
+    Arg(SqlArg),
+    Expr(String),
+}
+
+impl Now {
+    /// Takes the time from the timestamps or from the [SystemClock].
+    pub(crate) fn new(timestamps: Option<&Timestamps>) -> Self {
+        match timestamps {
+            Some(Timestamps::Clock(c)) => Now::Arg(c.now()),
+            Some(Timestamps::Database(expr)) => Now::Expr(expr.to_owned()),
+            None => Now::Arg(SystemClock.now()),
+        }
+    }
+
+    /// Returns the time to write back into the entities, none if the database sets it.
+    pub(crate) fn arg(&self) -> Option<&SqlArg> {
+        match self {
+            Now::Arg(a) => Some(a),
+            Now::Expr(_) => None,
+        }
+    }
+
+    /// Returns the replacement for the timestamp placeholder.
+    pub(crate) fn exprs(&self) -> HashMap<String, SqlExpr> {
+        let expr = match self {
+            Now::Arg(a) => SqlExpr::arg(a.to_owned()),
+            Now::Expr(e) => SqlExpr::literal(e.to_owned()),
+        };
+        let mut exprs = HashMap::new();
+        exprs.insert(TIMESTAMP_AUX_PARAM.to_string(), expr);
+        exprs
+    }
+}
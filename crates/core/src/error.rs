@@ -3,6 +3,8 @@
 //! ToqlError represents all library errors and wraps errors from the Pest parser and the optional database crate.
 //!
 
+use crate::mock_db::mock_db_error::MockDbError;
+use crate::query_parser::query_error::QueryError;
 use crate::sql_builder::sql_builder_error::SqlBuilderError;
 use crate::{
     deserialize::error::DeserializeError, sql_arg::error::TryFromSqlArgError,
@@ -35,9 +37,9 @@ pub enum ToqlError {
     #[error("no joined value found, but expected one")]
     JoinExpected,
 
-    /// The query parser encountered a syntax error.
+    /// The query string is invalid or refers to unknown fields. Contains the position.
     #[error("{0}")]
-    QueryParserError(#[from] PestError<toql_query_parser::Rule>),
+    QueryError(#[from] QueryError),
 
     /// The sql expression parser encountered a syntax error.
     #[error("{0}")]
@@ -79,6 +81,10 @@ pub enum ToqlError {
     #[error("{0}")]
     SqlExprResolverError(#[from] ResolverError),
 
+    /// The mock database failed to execute a statement
+    #[error("mock database failed: {0}")]
+    MockDbError(#[from] MockDbError),
+
     /// Access to shared registry, typically inside cache, failed
     #[error("failed to access registry: `{0}`")]
     RegistryPoisenError(String),
@@ -24,6 +24,7 @@
 ///
 use crate::parameter_map::ParameterMap;
 use crate::query::field_filter::FieldFilter;
+use crate::sql_arg::SqlArg;
 use crate::sql_builder::sql_builder_error::SqlBuilderError;
 use crate::sql_expr::{resolver::Resolver, SqlExpr};
 
@@ -62,7 +63,7 @@ impl FieldHandler for DefaultFieldHandler {
         &self,
         mut select: SqlExpr,
         filter: &FieldFilter,
-        _aux_params: &ParameterMap,
+        aux_params: &ParameterMap,
     ) -> Result<Option<SqlExpr>, SqlBuilderError> {
         match filter {
             FieldFilter::Eq(criteria) => {
@@ -136,11 +137,86 @@ impl FieldHandler for DefaultFieldHandler {
                 select.push_literal(" LIKE ").push_arg(criteria.clone());
                 Ok(Some(select))
             }
+            FieldFilter::Ilk(criteria) => {
+                let mut expr = SqlExpr::literal("LOWER(");
+                expr.extend(select)
+                    .push_literal(") LIKE LOWER(")
+                    .push_arg(criteria.clone())
+                    .push_literal(")");
+                Ok(Some(expr))
+            }
+            FieldFilter::Nlk(criteria) => {
+                select.push_literal(" NOT LIKE ").push_arg(criteria.clone());
+                Ok(Some(select))
+            }
+            FieldFilter::Sw(criteria) => Ok(Some(like_escaped(select, "", criteria, "%"))),
+            FieldFilter::Ct(criteria) => Ok(Some(like_escaped(select, "%", criteria, "%"))),
+            FieldFilter::Ew(criteria) => Ok(Some(like_escaped(select, "%", criteria, ""))),
+            FieldFilter::Re(criteria) => match aux_params.regex_syntax() {
+                RegexSyntax::Regexp => {
+                    select.push_literal(" REGEXP ").push_arg(criteria.clone());
+                    Ok(Some(select))
+                }
+                RegexSyntax::Tilde => {
+                    select.push_literal(" ~ ").push_arg(criteria.clone());
+                    Ok(Some(select))
+                }
+                RegexSyntax::Function(name) => {
+                    let mut expr = SqlExpr::literal(format!("{}(", name));
+                    expr.push_arg(criteria.clone())
+                        .push_literal(", ")
+                        .extend(select)
+                        .push_literal(")");
+                    Ok(Some(expr))
+                }
+            },
             FieldFilter::Fn(name, _) => Err(SqlBuilderError::FilterInvalid(name.to_owned())), // Must be implemented by user
         }
     }
 }
 
+// Builds a LIKE expression, where `%` and `_` in the criteria are matched literally.
+// `!` is used as escape character, because backslashes are not portable in SQL strings.
+fn like_escaped(mut select: SqlExpr, prefix: &str, criteria: &SqlArg, suffix: &str) -> SqlExpr {
+    let text = match criteria {
+        SqlArg::Str(s) => s.to_owned(),
+        a => a.to_string(),
+    };
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push_str(prefix);
+    for c in text.chars() {
+        if matches!(c, '!' | '%' | '_') {
+            escaped.push('!');
+        }
+        escaped.push(c);
+    }
+    escaped.push_str(suffix);
+
+    select
+        .push_literal(" LIKE ")
+        .push_arg(SqlArg::Str(escaped))
+        .push_literal(" ESCAPE '!'");
+    select
+}
+
+/// Regex syntax of the database, used to build the _RE_ filter.
+/// Regular expressions are not part of standard SQL, so each backend
+/// tells the [DefaultFieldHandler] how to build them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RegexSyntax {
+    /// `column REGEXP ?`, used by MySQL and MariaDB.
+    #[default]
+    Regexp,
+    /// `column ~ ?`, used by PostgreSQL.
+    Tilde,
+    /// `name(?, column)`, a function that takes the pattern and the value.
+    /// Use this for a regex function registered on a SQLite connection.
+    Function(String),
+}
+
+/// Regex syntax for backends that do not provide one.
+pub(crate) static REGEXP: RegexSyntax = RegexSyntax::Regexp;
+
 /// Handles the standart filters as documented in the guide.
 /// Returns [FilterInvalid](../sql_builder/enum.SqlBuilderError.html) for any attempt to use FN filters.
 #[derive(Debug, Clone)]
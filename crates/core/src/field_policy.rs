@@ -0,0 +1,180 @@
+//! Runtime restrictions on query fields.
+//!
+//! Role expressions are compiled into the Toql derive. If the fields that a user may query
+//! are only known at runtime, a [FieldPolicy] can be configured on the [Context](crate::backend::context::Context).
+//! The [SqlBuilder](crate::sql_builder::SqlBuilder) consults the policy in addition to the role expressions
+//! whenever a field is selected, filtered or ordered.
+//!
+//! Fields are given by their query path, for example `address_street`.
+//! A pattern ending with `*` matches all fields with that path, `address_*` matches
+//! `address_street` and `address_country_code`, `*` matches all fields.
+//!
+//! If an operation has an allow list, only fields on that list are allowed.
+//! Denied fields are never allowed.
+//!
+//! Preselected fields and keys are always selected, because they are needed to build the struct.
+//! Fields that are selected through a wildcard are silently dropped, if the policy does not allow them.
+//!
+//! ### Example
+//! ```rust
+//! use toql_core::field_policy::{FieldPolicy, FieldOperation, OnViolation};
+//!
+//! let policy = FieldPolicy::new()
+//!     .allow("*", &[FieldOperation::Select])
+//!     .deny("salary", &[FieldOperation::Select])
+//!     .allow("name", &[FieldOperation::Filter, FieldOperation::Order])
+//!     .on_violation(OnViolation::Drop);
+//!
+//! assert!(policy.is_allowed("address_street", FieldOperation::Select));
+//! assert!(!policy.is_allowed("salary", FieldOperation::Select));
+//! assert!(!policy.is_allowed("address_street", FieldOperation::Filter));
+//! ```
+use std::fmt;
+
+/// Unrestricted field policy, used by backends that do not provide a policy.
+pub(crate) static UNRESTRICTED: FieldPolicy = FieldPolicy::new();
+
+/// The operations that a field policy can restrict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldOperation {
+    /// Field is selected, either explicitly or through a wildcard or a selection.
+    Select,
+    /// Field has a filter.
+    Filter,
+    /// Field has an ordering.
+    Order,
+}
+
+impl FieldOperation {
+    /// All operations.
+    pub const ALL: [FieldOperation; 3] = [
+        FieldOperation::Select,
+        FieldOperation::Filter,
+        FieldOperation::Order,
+    ];
+}
+
+impl fmt::Display for FieldOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldOperation::Select => write!(f, "select"),
+            FieldOperation::Filter => write!(f, "filter"),
+            FieldOperation::Order => write!(f, "order"),
+        }
+    }
+}
+
+/// Behaviour for a query field that is not allowed by the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnViolation {
+    /// Fail with [SqlBuilderError::FieldNotAllowed](crate::sql_builder::sql_builder_error::SqlBuilderError::FieldNotAllowed).
+    #[default]
+    Reject,
+    /// Ignore the selection, filter or ordering.
+    Drop,
+}
+
+/// Allow and deny lists for query fields.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldPolicy {
+    pub(crate) allow: Vec<(String, FieldOperation)>,
+    pub(crate) deny: Vec<(String, FieldOperation)>,
+    pub(crate) on_violation: OnViolation,
+}
+
+impl FieldPolicy {
+    /// Create a new policy. All fields are allowed and violations are rejected.
+    pub const fn new() -> Self {
+        FieldPolicy {
+            allow: Vec::new(),
+            deny: Vec::new(),
+            on_violation: OnViolation::Reject,
+        }
+    }
+
+    /// Allow the operations for a query field or a field pattern.
+    pub fn allow<T: Into<String>>(mut self, query_field: T, operations: &[FieldOperation]) -> Self {
+        let query_field = query_field.into();
+        for o in operations {
+            self.allow.push((query_field.clone(), *o));
+        }
+        self
+    }
+    /// Deny the operations for a query field or a field pattern.
+    pub fn deny<T: Into<String>>(mut self, query_field: T, operations: &[FieldOperation]) -> Self {
+        let query_field = query_field.into();
+        for o in operations {
+            self.deny.push((query_field.clone(), *o));
+        }
+        self
+    }
+    /// Set the behaviour for disallowed query fields.
+    pub fn on_violation(mut self, on_violation: OnViolation) -> Self {
+        self.on_violation = on_violation;
+        self
+    }
+
+    /// Returns true, if the policy neither allows nor denies anything.
+    pub fn is_unrestricted(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Returns the behaviour for disallowed query fields.
+    pub fn violation(&self) -> OnViolation {
+        self.on_violation
+    }
+
+    /// Returns true, if the operation is allowed on the query field.
+    pub fn is_allowed(&self, query_field: &str, operation: FieldOperation) -> bool {
+        let restricted = self.allow.iter().any(|(_, o)| *o == operation);
+        let allowed = !restricted || Self::matches_any(&self.allow, query_field, operation);
+
+        allowed && !Self::matches_any(&self.deny, query_field, operation)
+    }
+
+    fn matches_any(
+        rules: &[(String, FieldOperation)],
+        query_field: &str,
+        operation: FieldOperation,
+    ) -> bool {
+        rules
+            .iter()
+            .any(|(p, o)| *o == operation && Self::matches(p, query_field))
+    }
+
+    fn matches(pattern: &str, query_field: &str) -> bool {
+        match pattern.strip_suffix('*') {
+            Some(path) => query_field.starts_with(path),
+            None => pattern == query_field,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FieldOperation, FieldPolicy};
+
+    #[test]
+    fn is_allowed() {
+        let p = FieldPolicy::new();
+        assert!(p.is_unrestricted());
+        assert!(p.is_allowed("user_name", FieldOperation::Filter));
+
+        let p = FieldPolicy::new()
+            .allow("id", &FieldOperation::ALL)
+            .allow("user_*", &[FieldOperation::Select])
+            .deny("user_password", &FieldOperation::ALL);
+        assert!(!p.is_unrestricted());
+
+        assert!(p.is_allowed("id", FieldOperation::Order));
+        assert!(p.is_allowed("user_name", FieldOperation::Select));
+        assert!(p.is_allowed("user_address_street", FieldOperation::Select));
+        assert!(!p.is_allowed("user_name", FieldOperation::Filter));
+        assert!(!p.is_allowed("user_password", FieldOperation::Select));
+        assert!(!p.is_allowed("text", FieldOperation::Select));
+
+        let p = FieldPolicy::new().deny("*", &[FieldOperation::Order]);
+        assert!(p.is_allowed("text", FieldOperation::Filter));
+        assert!(!p.is_allowed("text", FieldOperation::Order));
+    }
+}
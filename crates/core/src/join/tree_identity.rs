@@ -4,7 +4,7 @@ use crate::key::Key;
 use crate::keyed::Keyed;
 use crate::query::field_path::FieldPath;
 use crate::sql_arg::SqlArg;
-use crate::tree::tree_identity::{IdentityAction, TreeIdentity};
+use crate::tree::tree_identity::{FillAction, IdentityAction, TreeIdentity};
 use std::convert::TryFrom;
 
 impl<T> TreeIdentity for Join<T>
@@ -62,4 +62,13 @@ where
             Join::Entity(e) => e.set_id(descendents, action),
         }
     }
+    fn fill<'a, 'b, I>(&mut self, descendents: I, action: &'b FillAction) -> Result<(), ToqlError>
+    where
+        I: Iterator<Item = FieldPath<'a>> + Clone,
+    {
+        match self {
+            Join::Key(_) => Ok(()),
+            Join::Entity(e) => e.fill(descendents, action),
+        }
+    }
 }
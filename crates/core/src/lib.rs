@@ -9,12 +9,14 @@ pub extern crate serde; // For generated keys and Join<T>
 #[macro_use]
 pub mod error;
 pub mod alias_format;
+pub mod clock;
 pub mod deserialize;
 pub mod identity;
 pub mod key;
 pub mod key_fields;
 pub mod keyed;
 pub mod map_key;
+pub mod metrics;
 //pub mod map_query;
 pub mod from_iterator;
 pub mod page;
@@ -32,6 +34,8 @@ pub mod join_macro;
 pub mod val_macro;
 #[macro_use]
 pub mod none_error_macro;
+#[macro_use]
+pub mod backend_settings_macro;
 
 extern crate lazy_static;
 
@@ -39,6 +43,7 @@ pub mod alias_translator;
 pub mod backend;
 pub mod cache;
 pub mod field_handler;
+pub mod field_policy;
 pub mod from_row;
 pub mod join;
 pub mod join_handler;
@@ -46,6 +51,7 @@ pub mod parameter_map;
 pub mod predicate_handler;
 pub mod query;
 pub mod query_fields;
+pub mod query_limits;
 pub mod query_parser;
 pub mod query_path;
 pub mod role_expr;
@@ -0,0 +1,67 @@
+//! Metrics for executed SQL statements.
+//!
+//! Every statement, that Toql executes, runs in a `tracing` span named `sql`.
+//! The span carries the entity type, the field path, the purpose and the SQL
+//! and records the duration, the number of rows, the generated ids and the error message.
+//!
+//! To export metrics, such as histograms, implement [Metrics] and add it to the
+//! [Context](crate::backend::context::Context) of the backend.
+use std::fmt;
+use std::time::Duration;
+
+/// The reason for a statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Purpose {
+    /// Select of the queried entities.
+    Load,
+    /// Select of merged entities.
+    Merge,
+    /// Number of rows of a page without limit.
+    PageCount,
+    /// Number of rows of a page without filters.
+    TotalCount,
+    /// Number of rows of a count query.
+    Count,
+    /// Insert of entities.
+    Insert,
+    /// Update of entities.
+    Update,
+    /// Delete of entities.
+    Delete,
+}
+
+impl fmt::Display for Purpose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let purpose = match self {
+            Purpose::Load => "load",
+            Purpose::Merge => "merge",
+            Purpose::PageCount => "page count",
+            Purpose::TotalCount => "total count",
+            Purpose::Count => "count",
+            Purpose::Insert => "insert",
+            Purpose::Update => "update",
+            Purpose::Delete => "delete",
+        };
+        write!(f, "{}", purpose)
+    }
+}
+
+/// Measurements of an executed statement.
+#[derive(Debug, Clone)]
+pub struct StatementMetrics<'a> {
+    /// Type name of the entity, that is loaded or changed.
+    pub type_name: &'a str,
+    /// Field path of the statement, empty for the entity itself.
+    pub path: &'a str,
+    pub purpose: Purpose,
+    pub duration: Duration,
+    /// Number of selected, counted or inserted rows.
+    /// Zero for updates and deletes.
+    pub rows: u64,
+    pub failed: bool,
+}
+
+/// Hook that receives the measurements of every executed statement.
+pub trait Metrics: Send + Sync {
+    fn record(&self, statement: &StatementMetrics);
+}
@@ -1,18 +1,24 @@
-use crate::alias_format::AliasFormat;
 use crate::backend::{context::Context, Backend};
 use crate::cache::Cache;
 use crate::error::ToqlError;
 use crate::result::Result;
 use crate::sql::Sql;
 use crate::sql_arg::SqlArg;
+use crate::sql_expr::{SqlExpr, SqlExprToken};
 use crate::table_mapper_registry::TableMapperRegistry;
 use crate::{page::Page, sql_builder::build_result::BuildResult};
-use std::collections::{HashMap, HashSet};
-use std::sync::{RwLockReadGuard, RwLockWriteGuard};
+use std::collections::HashMap;
 
 use crate::log_sql;
 
+use super::expectation::{Expectation, Response};
+use super::memory::{
+    statement::{self, Statement},
+    Memory, Outcome,
+};
+use super::mock_db_error::MockDbError;
 use super::row::Row;
+use super::MockDb;
 use async_trait::async_trait;
 
 pub(crate) struct MockDbBackend<'a> {
@@ -22,6 +28,164 @@ pub(crate) struct MockDbBackend<'a> {
     pub(crate) rows: HashMap<String, Vec<Row>>, // Maps select statements to multiple rows
     pub(crate) current_id: u64,
     pub(crate) start_id: u64,
+    pub(crate) memory: Option<Memory>, // In-memory tables that execute the statements
+    pub(crate) found_rows: u64,        // Number of rows of last select without limit
+    pub(crate) expectations: Vec<Expectation>,
+    pub(crate) unexpected: Vec<String>, // Statements that match no expectation
+}
+
+impl<'a> MockDbBackend<'a> {
+    // Answer statement from first matching expectation, that is not yet consumed.
+    // The statement is recorded in any case
+    fn expected(&mut self, sql: &Sql) -> Result<Option<Response>> {
+        let statement = sql.to_unsafe_string();
+        let index = self
+            .expectations
+            .iter()
+            .position(|e| !e.is_consumed() && e.matches(&statement));
+        let index = match index {
+            Some(i) => i,
+            None => {
+                self.unexpected(statement);
+                return Ok(None);
+            }
+        };
+        self.sqls.push(Sql(sql.0.clone(), sql.1.clone()));
+        let expectation = &self.expectations[index];
+        if expectation.ordered && !self.expectations[..index].iter().all(|e| e.is_consumed()) {
+            return Err(MockDbError::OutOfOrder(statement).into());
+        }
+        let expectation = &mut self.expectations[index];
+        expectation.calls += 1;
+        match &expectation.response {
+            Response::Error(e) => Err(e.to_owned().into()),
+            r => Ok(Some(r.to_owned())),
+        }
+    }
+
+    // Count over-call of a consumed expectation or record statement as unexpected.
+    // Without any expectations nothing is recorded
+    fn unexpected(&mut self, statement: String) {
+        if self.expectations.is_empty() {
+            return;
+        }
+        match self.expectations.iter_mut().find(|e| e.matches(&statement)) {
+            Some(e) => e.calls += 1,
+            None => self.unexpected.push(statement),
+        }
+    }
+
+    fn generate_ids(&mut self, number_of_rows: u64) -> Vec<SqlArg> {
+        let ids = (0..number_of_rows)
+            .map(|n| SqlArg::U64(n + self.current_id))
+            .collect::<Vec<_>>();
+        self.current_id += number_of_rows;
+        ids
+    }
+
+    // Execute statement on in-memory tables, if enabled
+    fn execute_in_memory(&mut self, sql: &Sql) -> Result<Option<Outcome>> {
+        let memory = match self.memory.as_mut() {
+            Some(m) => m,
+            None => return Ok(None),
+        };
+        let registry = self.cache.registry.read()?;
+        let auto_key = |table: &str, columns: &[String]| auto_key(&registry, table, columns);
+        let outcome = memory.execute(sql, auto_key, &mut self.current_id)?;
+        if let Outcome::Rows(_, found) = &outcome {
+            self.found_rows = *found;
+        }
+        Ok(Some(outcome))
+    }
+}
+
+// Number of rows in the values of an insert statement.
+// Statements that the in-memory tables can't parse are still recorded,
+// their rows are counted from the value tuples.
+fn inserted_rows(sql: &Sql) -> u64 {
+    match statement::parse(sql) {
+        Ok(Statement::Insert(insert)) => insert.rows.len() as u64,
+        Ok(_) => 0,
+        Err(_) => value_tuples(&sql.0),
+    }
+}
+
+// Count the parenthesized groups after `VALUES`, that are neither nested nor quoted
+fn value_tuples(sql: &str) -> u64 {
+    let values = match sql.find(" VALUES ") {
+        Some(i) => &sql[i..],
+        None => return 1,
+    };
+    let mut rows = 0;
+    let mut depth = 0;
+    let mut quote = None;
+    for c in values.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '(' => {
+                    if depth == 0 {
+                        rows += 1;
+                    }
+                    depth += 1;
+                }
+                ')' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+    rows
+}
+
+// Number from affected rows or the first value of the first row
+fn count(response: Response) -> u64 {
+    match response {
+        Response::Affected(n) => n,
+        Response::Rows(rows) => rows
+            .first()
+            .and_then(|r| r.0.first())
+            .and_then(|a| a.get_u64())
+            .unwrap_or(0),
+        Response::Error(_) => 0,
+    }
+}
+
+// Return the single key column of a mapped table, that is missing in the inserted columns
+pub(crate) fn auto_key(
+    registry: &TableMapperRegistry,
+    table: &str,
+    columns: &[String],
+) -> Option<String> {
+    let mapper = registry.mappers.values().find(|m| m.table_name == table)?;
+    let mut missing = mapper
+        .fields
+        .values()
+        .filter(|f| f.options.key)
+        .filter_map(|f| column_name(&f.expression))
+        .filter(|column| !columns.iter().any(|c| c.eq_ignore_ascii_case(column)));
+    match (missing.next(), missing.next()) {
+        (Some(column), None) => Some(column),
+        _ => None,
+    }
+}
+
+// Return the column of a field, that is mapped as `..column`
+pub(crate) fn column_name(expression: &SqlExpr) -> Option<String> {
+    match expression.tokens() {
+        [SqlExprToken::SelfAlias, rest @ ..] => {
+            let column = rest
+                .iter()
+                .map(|t| match t {
+                    SqlExprToken::Literal(l) => Some(l.as_str()),
+                    _ => None,
+                })
+                .collect::<Option<String>>()?;
+            Some(column.trim_start_matches('.').to_string())
+        }
+        _ => None,
+    }
 }
 
 // Implement template functions for updating entities
@@ -29,25 +193,48 @@ pub(crate) struct MockDbBackend<'a> {
 impl<'a> Backend<Row, ToqlError> for MockDbBackend<'a> {
     async fn execute_sql(&mut self, sql: Sql) -> Result<()> {
         log_mut_sql!(&sql);
+        if self.expected(&sql)?.is_some() {
+            return Ok(());
+        }
+        self.execute_in_memory(&sql)?;
         self.sqls.push(sql);
         Ok(())
     }
     async fn insert_sql(&mut self, sql: Sql) -> Result<Vec<SqlArg>> {
         log_mut_sql!(&sql);
-        // Count number of closing parens to guess number of rows
-        let number_of_rows: u64 = sql.0.chars().filter(|c| c == &')').count() as u64 - 1;
+        match self.expected(&sql)? {
+            Some(Response::Affected(n)) => return Ok(self.generate_ids(n)),
+            Some(Response::Rows(rows)) => {
+                return Ok(rows
+                    .into_iter()
+                    .filter_map(|r| r.0.into_iter().next())
+                    .collect())
+            }
+            _ => {}
+        }
+        if let Some(Outcome::Inserted(ids)) = self.execute_in_memory(&sql)? {
+            self.sqls.push(sql);
+            return Ok(ids);
+        }
+        let number_of_rows = inserted_rows(&sql);
 
         self.sqls.push(sql);
-        let ids = (0..number_of_rows)
-            .map(|n| SqlArg::U64((n + self.current_id).into()))
-            .collect::<Vec<_>>();
-        self.current_id += number_of_rows;
-        Ok(ids)
+        Ok(self.generate_ids(number_of_rows))
     }
 
     async fn select_sql(&mut self, sql: Sql) -> Result<Vec<Row>> {
         log_sql!(&sql);
 
+        match self.expected(&sql)? {
+            Some(Response::Rows(rows)) => return Ok(rows),
+            Some(_) => return Ok(vec![]),
+            None => {}
+        }
+        if let Some(Outcome::Rows(rows, _)) = self.execute_in_memory(&sql)? {
+            self.sqls.push(sql);
+            return Ok(rows.into_iter().map(Row).collect());
+        }
+
         let sql_string = sql.to_unsafe_string();
         self.sqls.push(sql);
 
@@ -71,36 +258,99 @@ impl<'a> Backend<Row, ToqlError> for MockDbBackend<'a> {
 
         Ok(rows)
     }
-    fn prepare_page(&self, _result: &mut BuildResult, _page: &Page) {}
+    fn prepare_page(&self, result: &mut BuildResult, page: &Page) {
+        // In-memory tables limit like MySQL
+        if self.memory.is_some() {
+            let (start, number) = match page {
+                Page::Uncounted(start, number) | Page::Counted(start, number) => (start, number),
+            };
+            result.set_extra(format!("LIMIT {}, {}", start, number));
+        }
+    }
 
     async fn select_max_page_size_sql(&mut self, sql: Sql) -> Result<u64> {
         log_sql!(&sql);
+        if let Some(response) = self.expected(&sql)? {
+            return Ok(count(response));
+        }
         self.sqls.push(sql);
-        Ok(0)
+        Ok(if self.memory.is_some() {
+            self.found_rows
+        } else {
+            0
+        })
     }
     async fn select_count_sql(&mut self, sql: Sql) -> Result<u64> {
         log_sql!(&sql);
+        if let Some(response) = self.expected(&sql)? {
+            return Ok(count(response));
+        }
+        let count = match self.execute_in_memory(&sql)? {
+            Some(Outcome::Rows(rows, _)) => rows
+                .first()
+                .and_then(|r| r.first())
+                .and_then(|a| a.get_u64())
+                .unwrap_or(0),
+            _ => 0,
+        };
         self.sqls.push(sql);
-        Ok(0)
+        Ok(count)
     }
 
-    fn registry(&self) -> std::result::Result<RwLockReadGuard<'_, TableMapperRegistry>, ToqlError> {
-        self.cache.registry.read().map_err(ToqlError::from)
-    }
+    backend_settings!(context: context, cache: cache);
+}
 
-    fn registry_mut(
-        &mut self,
-    ) -> std::result::Result<RwLockWriteGuard<'_, TableMapperRegistry>, ToqlError> {
-        self.cache.registry.write().map_err(ToqlError::from)
+// Delegate to the inner backend, so that a MockDb can be wrapped by other backends,
+// such as the [RoutingBackend](crate::backend::routing::RoutingBackend)
+#[async_trait]
+impl<'a> Backend<Row, ToqlError> for MockDb<'a> {
+    async fn execute_sql(&mut self, sql: Sql) -> Result<()> {
+        self.backend.execute_sql(sql).await
     }
-
-    fn roles(&self) -> &HashSet<String> {
-        &self.context.roles
+    async fn insert_sql(&mut self, sql: Sql) -> Result<Vec<SqlArg>> {
+        self.backend.insert_sql(sql).await
+    }
+    async fn select_sql(&mut self, sql: Sql) -> Result<Vec<Row>> {
+        self.backend.select_sql(sql).await
     }
-    fn alias_format(&self) -> AliasFormat {
-        self.context.alias_format.clone()
+    fn prepare_page(&self, result: &mut BuildResult, page: &Page) {
+        self.backend.prepare_page(result, page)
     }
-    fn aux_params(&self) -> &HashMap<String, SqlArg> {
-        &self.context.aux_params
+    async fn select_max_page_size_sql(&mut self, sql: Sql) -> Result<u64> {
+        self.backend.select_max_page_size_sql(sql).await
+    }
+    async fn select_count_sql(&mut self, sql: Sql) -> Result<u64> {
+        self.backend.select_count_sql(sql).await
+    }
+    backend_settings!(forward: backend);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{inserted_rows, value_tuples};
+    use crate::{sql::Sql, sql_arg::SqlArg};
+
+    #[test]
+    fn count_inserted_rows() {
+        let sql = Sql(
+            "INSERT INTO Book (id, title) VALUES (1, ?), (2, ?)".to_string(),
+            vec![SqlArg::from("A), (B"), SqlArg::from("C")],
+        );
+        assert_eq!(inserted_rows(&sql), 2);
+
+        // Unparsable statements count their value tuples
+        let sql = Sql(
+            "INSERT INTO Book (title) VALUES ('A), (B') ON DUPLICATE KEY UPDATE title = 'C), (D'"
+                .to_string(),
+            vec![],
+        );
+        assert_eq!(inserted_rows(&sql), 1);
+        assert_eq!(
+            value_tuples(
+                "INSERT INTO Book (title, created) VALUES (CONCAT('A', ')'), NOW()), (?, ?)"
+            ),
+            2
+        );
+        assert_eq!(value_tuples("INSERT INTO Book SELECT * FROM Draft"), 1);
     }
 }
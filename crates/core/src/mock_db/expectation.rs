@@ -0,0 +1,179 @@
+//! Expectations match the statements that the [MockDb](crate::mock_db::MockDb) receives
+//! and answer them with configured rows, affected rows or errors.
+//!
+//! ### Example
+//! ```ignore
+//! mock_db.expect(
+//!     Expectation::regex("^INSERT INTO User")
+//!         .fails(MockDbError::DuplicateKey("PRIMARY".to_string())),
+//! );
+//! ```
+use super::mock_db_error::MockDbError;
+use super::row::Row;
+use regex::Regex;
+use std::fmt;
+
+/// The answer of an expectation.
+#[derive(Debug, Clone)]
+pub(crate) enum Response {
+    Rows(Vec<Row>),
+    Affected(u64),
+    Error(MockDbError),
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Sql(String),
+    Regex(Regex),
+}
+
+/// An expected statement together with its answer.
+///
+/// By default an expectation is consumed by one call and answers with no rows.
+#[derive(Debug)]
+pub struct Expectation {
+    matcher: Matcher,
+    pub(crate) response: Response,
+    pub(crate) times: usize,
+    pub(crate) calls: usize,
+    pub(crate) ordered: bool,
+}
+
+impl Expectation {
+    fn new(matcher: Matcher) -> Self {
+        Expectation {
+            matcher,
+            response: Response::Rows(Vec::new()),
+            times: 1,
+            calls: 0,
+            ordered: false,
+        }
+    }
+
+    /// Expect a statement that equals the SQL after normalization.
+    /// Whitespace outside of string literals is collapsed and
+    /// spaces inside of parentheses and around commas are ignored.
+    pub fn sql(sql: impl AsRef<str>) -> Self {
+        Self::new(Matcher::Sql(normalize(sql.as_ref())))
+    }
+
+    /// Expect a statement that matches the regular expression.
+    ///
+    /// # Panics
+    /// If the pattern is not a valid regular expression.
+    pub fn regex(pattern: &str) -> Self {
+        let regex = Regex::new(pattern)
+            .unwrap_or_else(|e| panic!("invalid expectation pattern `{}`: {}", pattern, e));
+        Self::new(Matcher::Regex(regex))
+    }
+
+    /// Answer selects with the rows.
+    /// Inserts take the first value of each row as generated key.
+    pub fn returns(mut self, rows: Vec<Row>) -> Self {
+        self.response = Response::Rows(rows);
+        self
+    }
+
+    /// Answer with the number of affected rows.
+    /// Inserts generate as many keys, counts return the number.
+    pub fn affects(mut self, rows: u64) -> Self {
+        self.response = Response::Affected(rows);
+        self
+    }
+
+    /// Fail with the error.
+    pub fn fails(mut self, error: MockDbError) -> Self {
+        self.response = Response::Error(error);
+        self
+    }
+
+    /// Expect the statement `times` times instead of once.
+    pub fn times(mut self, times: usize) -> Self {
+        self.times = times;
+        self
+    }
+
+    /// Only match, if all expectations added before are consumed.
+    /// Otherwise the statement fails with [MockDbError::OutOfOrder].
+    pub fn in_order(mut self) -> Self {
+        self.ordered = true;
+        self
+    }
+
+    pub(crate) fn is_consumed(&self) -> bool {
+        self.calls >= self.times
+    }
+
+    pub(crate) fn matches(&self, statement: &str) -> bool {
+        match &self.matcher {
+            Matcher::Sql(sql) => *sql == normalize(statement),
+            Matcher::Regex(regex) => regex.is_match(statement),
+        }
+    }
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.matcher {
+            Matcher::Sql(sql) => write!(f, "{}", sql),
+            Matcher::Regex(regex) => write!(f, "/{}/", regex.as_str()),
+        }
+    }
+}
+
+// Collapse whitespace outside of string literals
+// and remove it inside of parens and around commas
+fn normalize(sql: &str) -> String {
+    let mut normalized = String::with_capacity(sql.len());
+    let mut quoted = false;
+    let mut space = false;
+    for c in sql.trim().chars() {
+        if quoted {
+            normalized.push(c);
+            quoted = c != '\'';
+            continue;
+        }
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space && !matches!(c, ')' | ',') && !normalized.ends_with(&['(', ','][..]) {
+            normalized.push(' ');
+        }
+        space = false;
+        normalized.push(c);
+        quoted = c == '\'';
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod test {
+    use super::{normalize, Expectation};
+
+    #[test]
+    fn normalize_sql() {
+        assert_eq!(
+            normalize("  SELECT  a.id ,a.name\n FROM User a WHERE a.id IN ( 1 , 2 ) "),
+            "SELECT a.id,a.name FROM User a WHERE a.id IN (1,2)"
+        );
+        assert_eq!(
+            normalize("SELECT a.id, a.name FROM User a"),
+            normalize("SELECT a.id,a.name  FROM User a")
+        );
+        assert_eq!(normalize("SELECT 'a  ( b' ,  1"), "SELECT 'a  ( b',1");
+        assert_eq!(normalize("SELECT 'it''s  ok'  "), "SELECT 'it''s  ok'");
+    }
+
+    #[test]
+    fn match_statements() {
+        let e = Expectation::sql("SELECT a.id FROM User a");
+        assert!(e.matches("SELECT  a.id\nFROM User a"));
+        assert!(!e.matches("SELECT a.id FROM User a WHERE a.id = 1"));
+
+        let e = Expectation::regex("^DELETE .* WHERE");
+        assert!(e.matches("DELETE a FROM User a WHERE a.id = 1"));
+        assert!(!e.matches("SELECT a.id FROM User a WHERE a.id = 1"));
+        assert_eq!(e.to_string(), "/^DELETE .* WHERE/");
+    }
+}
@@ -0,0 +1,280 @@
+//! Seed the in-memory tables of the [MockDb](crate::mock_db::MockDb) from fixtures.
+//!
+//! A fixture maps entity types to their entities. Entities describe their fields by name.
+//! Joined entities are nested objects or keys, merged entities are nested arrays.
+//!
+//! ### Example
+//! ```yaml
+//! Book:
+//!   - id: 1
+//!     title: Rust
+//!     author: { id: 7, name: Ann }
+//!     chapters:
+//!       - { id: 1, title: Ownership }
+//!   - id: 2
+//!     title: Go
+//!     author: 7
+//! ```
+//! The foreign keys `author_id` and `book_id` are taken from the join and merge mapping.
+use super::backend::{auto_key, column_name};
+use super::memory::Memory;
+use super::mock_db_error::MockDbError;
+use crate::result::Result;
+use crate::sql_arg::SqlArg;
+use crate::sql_expr::{SqlExpr, SqlExprToken};
+use crate::table_mapper::TableMapper;
+use crate::table_mapper_registry::TableMapperRegistry;
+use heck::MixedCase;
+use serde_json::Value;
+
+fn error(message: impl Into<String>) -> MockDbError {
+    MockDbError::Fixture(message.into())
+}
+
+pub(crate) struct Seeder<'a> {
+    pub(crate) registry: &'a TableMapperRegistry,
+    pub(crate) memory: &'a mut Memory,
+    pub(crate) next_id: &'a mut u64,
+}
+
+impl<'a> Seeder<'a> {
+    /// Insert all entities of the fixture.
+    pub(crate) fn seed(&mut self, fixture: &Value) -> Result<()> {
+        let types = fixture
+            .as_object()
+            .ok_or_else(|| error("fixture must map entity types to entities"))?;
+        for (type_name, entities) in types {
+            if !self.registry.mappers.contains_key(type_name) {
+                return Err(error(format!("type `{}` is not mapped", type_name)).into());
+            }
+            match entities {
+                Value::Array(entities) => {
+                    for e in entities {
+                        self.entity(type_name, e, Vec::new())?;
+                    }
+                }
+                e => {
+                    self.entity(type_name, e, Vec::new())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Insert entity with the columns from its parent and return its row
+    fn entity(
+        &mut self,
+        type_name: &str,
+        entity: &Value,
+        mut row: Vec<(String, SqlArg)>,
+    ) -> Result<Vec<(String, SqlArg)>> {
+        let registry = self.registry;
+        let mapper = registry
+            .mappers
+            .get(type_name)
+            .ok_or_else(|| error(format!("type `{}` is not mapped", type_name)))?;
+        let fields = entity
+            .as_object()
+            .ok_or_else(|| error(format!("entity of `{}` must be an object", type_name)))?;
+
+        let mut merges = Vec::new();
+        for (name, value) in fields {
+            let toql_name = name.to_mixed_case();
+            if let Some(field) = mapper.fields.get(&toql_name) {
+                let column = column_name(&field.expression).ok_or_else(|| {
+                    error(format!(
+                        "field `{}` of `{}` is not mapped to a column",
+                        name, type_name
+                    ))
+                })?;
+                row.push((column, arg(value, name)?));
+            } else if let Some(join) = mapper.joins.get(&toql_name) {
+                let columns = column_pairs(&join.on_expression).ok_or_else(|| {
+                    error(format!(
+                        "join `{}` of `{}` has no join columns",
+                        name, type_name
+                    ))
+                })?;
+                match value {
+                    Value::Object(_) => {
+                        let joined = self.entity(&join.joined_mapper, value, Vec::new())?;
+                        for (column, other_column) in columns {
+                            row.push((column, lookup(&joined, &other_column)));
+                        }
+                    }
+                    Value::Null => {
+                        for (column, _) in columns {
+                            row.push((column, SqlArg::Null));
+                        }
+                    }
+                    key if columns.len() == 1 => {
+                        row.push((columns[0].0.to_owned(), arg(key, name)?));
+                    }
+                    _ => {
+                        return Err(error(format!(
+                            "join `{}` of `{}` has a composite key and must be an object",
+                            name, type_name
+                        ))
+                        .into())
+                    }
+                }
+            } else if let Some(merge) = mapper.merges.get(&toql_name) {
+                merges.push((name, merge, value));
+            } else {
+                return Err(error(format!("`{}` has no field `{}`", type_name, name)).into());
+            }
+        }
+
+        self.insert(mapper, &mut row);
+
+        for (name, merge, value) in merges {
+            let columns = column_pairs(&merge.merge_predicate).ok_or_else(|| {
+                error(format!(
+                    "merge `{}` of `{}` has no merge columns",
+                    name, type_name
+                ))
+            })?;
+            let entities = value.as_array().ok_or_else(|| {
+                error(format!(
+                    "merge `{}` of `{}` must be an array",
+                    name, type_name
+                ))
+            })?;
+            for e in entities {
+                let parent = columns
+                    .iter()
+                    .map(|(column, other_column)| (other_column.to_owned(), lookup(&row, column)))
+                    .collect();
+                self.entity(&merge.merged_mapper, e, parent)?;
+            }
+        }
+        Ok(row)
+    }
+
+    // Insert row, unless an entity with the same key is already seeded.
+    // The row gets a generated key, if it misses an auto key
+    fn insert(&mut self, mapper: &TableMapper, row: &mut Vec<(String, SqlArg)>) {
+        let columns = row.iter().map(|(c, _)| c.to_owned()).collect::<Vec<_>>();
+        if let Some(column) = auto_key(self.registry, &mapper.table_name, &columns) {
+            row.push((column, SqlArg::U64(*self.next_id)));
+            *self.next_id += 1;
+        }
+        let key = mapper
+            .fields
+            .values()
+            .filter(|f| f.options.key)
+            .filter_map(|f| column_name(&f.expression))
+            .map(|c| {
+                let value = lookup(row, &c);
+                (c, value)
+            })
+            .collect::<Vec<_>>();
+
+        let table = self
+            .memory
+            .tables
+            .entry(mapper.table_name.to_owned())
+            .or_default();
+        let seeded = !key.is_empty()
+            && table.rows.iter().any(|r| {
+                key.iter()
+                    .all(|(c, v)| table.column_index(c).map_or(v.is_null(), |i| r[i] == *v))
+            });
+        if seeded {
+            return;
+        }
+        let indexes = row
+            .iter()
+            .map(|(c, _)| table.ensure_column(c))
+            .collect::<Vec<_>>();
+        let mut values = vec![SqlArg::Null; table.columns.len()];
+        for (i, (_, v)) in indexes.into_iter().zip(row.iter()) {
+            values[i] = v.to_owned();
+        }
+        table.rows.push(values);
+    }
+}
+
+fn lookup(row: &[(String, SqlArg)], column: &str) -> SqlArg {
+    row.iter()
+        .rev()
+        .find(|(c, _)| c.eq_ignore_ascii_case(column))
+        .map(|(_, v)| v.to_owned())
+        .unwrap_or(SqlArg::Null)
+}
+
+fn arg(value: &Value, name: &str) -> Result<SqlArg> {
+    Ok(match value {
+        Value::Null => SqlArg::Null,
+        Value::Bool(b) => SqlArg::Bool(*b),
+        Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+            (Some(u), _, _) => SqlArg::U64(u),
+            (_, Some(i), _) => SqlArg::I64(i),
+            (_, _, Some(f)) => SqlArg::F64(f),
+            _ => return Err(error(format!("number of `{}` is out of range", name)).into()),
+        },
+        Value::String(s) => SqlArg::Str(s.to_owned()),
+        _ => return Err(error(format!("`{}` must be a value", name)).into()),
+    })
+}
+
+// Return the column pairs of a join or merge condition like `..a_id = ...id AND ..b = ...b`.
+// The first column belongs to `..`, the second to `...`
+fn column_pairs(expression: &SqlExpr) -> Option<Vec<(String, String)>> {
+    let mut condition = String::new();
+    for t in expression.tokens() {
+        match t {
+            SqlExprToken::SelfAlias => condition.push_str(".."),
+            SqlExprToken::OtherAlias => condition.push_str("..."),
+            SqlExprToken::Literal(l) => condition.push_str(l),
+            _ => return None,
+        }
+    }
+    let column = |c: &str| -> Option<(bool, String)> {
+        let c = c.trim();
+        if let Some(c) = c.strip_prefix("....") {
+            Some((false, c.to_string()))
+        } else {
+            c.strip_prefix("...").map(|c| (true, c.to_string()))
+        }
+    };
+    let mut pairs = Vec::new();
+    for equality in condition.split(" AND ") {
+        let (left, right) = equality.split_once('=')?;
+        match (column(left)?, column(right)?) {
+            ((true, s), (false, o)) | ((false, o), (true, s)) => pairs.push((s, o)),
+            _ => return None,
+        }
+    }
+    Some(pairs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::column_pairs;
+    use crate::sql_expr::SqlExpr;
+
+    #[test]
+    fn parse_column_pairs() {
+        let mut e = SqlExpr::self_alias();
+        e.push_literal(".author_id = ")
+            .push_other_alias()
+            .push_literal(".id")
+            .push_literal(" AND ")
+            .push_other_alias()
+            .push_literal(".kind = ")
+            .push_self_alias()
+            .push_literal(".author_kind");
+        assert_eq!(
+            column_pairs(&e),
+            Some(vec![
+                ("author_id".to_string(), "id".to_string()),
+                ("author_kind".to_string(), "kind".to_string())
+            ])
+        );
+
+        let mut e = SqlExpr::self_alias();
+        e.push_literal(".id = 5");
+        assert_eq!(column_pairs(&e), None);
+    }
+}
@@ -0,0 +1,951 @@
+//! In-memory tables for the [MockDb](super::MockDb).
+//!
+//! The tables execute the subset of SQL that Toql generates:
+//! selects with joins, filters, order and limit, inserts, updates and deletes.
+//! Tables are created on the first insert and grow a column for every new inserted column.
+//! Columns that a table does not know yet are read as `NULL`.
+pub(crate) mod statement;
+
+use crate::result::Result;
+use crate::sql::Sql;
+use crate::sql_arg::SqlArg;
+use statement::{
+    error, BinaryOp, Delete, Expr, InList, Insert, OrderItem, Select, Source, Statement, Update,
+};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// An in-memory table.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Table {
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<Vec<SqlArg>>,
+}
+
+impl Table {
+    pub(crate) fn column_index(&self, column: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case(column))
+    }
+
+    /// Return the index of the column and add the column, if it is missing.
+    pub(crate) fn ensure_column(&mut self, column: &str) -> usize {
+        match self.column_index(column) {
+            Some(i) => i,
+            None => {
+                self.columns.push(column.to_string());
+                for r in &mut self.rows {
+                    r.push(SqlArg::Null);
+                }
+                self.columns.len() - 1
+            }
+        }
+    }
+}
+
+/// The result of an executed statement.
+pub(crate) enum Outcome {
+    /// Selected rows and the number of rows without limit.
+    Rows(Vec<Vec<SqlArg>>, u64),
+    /// Generated keys of inserted rows.
+    Inserted(Vec<SqlArg>),
+    /// Updated or deleted rows.
+    Changed,
+}
+
+/// The tables of the in-memory database.
+#[derive(Debug, Default)]
+pub(crate) struct Memory {
+    pub(crate) tables: HashMap<String, Table>,
+}
+
+impl Memory {
+    /// Execute a SQL statement.
+    ///
+    /// `auto_key` returns the column, that gets a generated key, if an insert omits it.
+    pub(crate) fn execute<F>(
+        &mut self,
+        sql: &Sql,
+        auto_key: F,
+        next_id: &mut u64,
+    ) -> Result<Outcome>
+    where
+        F: Fn(&str, &[String]) -> Option<String>,
+    {
+        match statement::parse(sql)? {
+            Statement::Select(select) => {
+                let (_, rows, found) = Executor { memory: self }.select(&select, None)?;
+                Ok(Outcome::Rows(rows, found))
+            }
+            Statement::Insert(insert) => self.insert(insert, auto_key, next_id),
+            Statement::Update(update) => self.update(update),
+            Statement::Delete(delete) => self.delete(delete),
+        }
+    }
+
+    fn insert<F>(&mut self, insert: Insert, auto_key: F, next_id: &mut u64) -> Result<Outcome>
+    where
+        F: Fn(&str, &[String]) -> Option<String>,
+    {
+        let key_column = auto_key(&insert.table, &insert.columns);
+        let executor = Executor { memory: self };
+        let scope = Scope::new(&[], None);
+        let mut values = Vec::with_capacity(insert.rows.len());
+        for row in &insert.rows {
+            let mut v = Vec::with_capacity(row.len());
+            for expr in row {
+                v.push(match expr {
+                    Some(e) => executor.eval(e, &scope)?,
+                    None => SqlArg::Null,
+                });
+            }
+            values.push(v);
+        }
+
+        let table = self.tables.entry(insert.table).or_default();
+        let indexes = insert
+            .columns
+            .iter()
+            .map(|c| table.ensure_column(c))
+            .collect::<Vec<_>>();
+        let key_index = key_column.map(|c| table.ensure_column(&c));
+
+        let mut keys = Vec::new();
+        for v in values {
+            let mut row = vec![SqlArg::Null; table.columns.len()];
+            for (i, a) in indexes.iter().zip(v) {
+                row[*i] = a;
+            }
+            if let Some(k) = key_index {
+                let key = SqlArg::U64(*next_id);
+                *next_id += 1;
+                row[k] = key.clone();
+                keys.push(key);
+            }
+            table.rows.push(row);
+        }
+        // Backends return new keys in descending order
+        keys.reverse();
+        Ok(Outcome::Inserted(keys))
+    }
+
+    fn update(&mut self, update: Update) -> Result<Outcome> {
+        let source = Source::Table {
+            name: update.table.clone(),
+            alias: update.alias.clone(),
+        };
+        let executor = Executor { memory: self };
+        let mut changes = Vec::new();
+        for bindings in executor.filtered(&source, update.filter.as_ref(), None)? {
+            let scope = Scope::new(&bindings, None);
+            let mut values = Vec::with_capacity(update.assignments.len());
+            for (_, expr) in &update.assignments {
+                values.push(executor.eval(expr, &scope)?);
+            }
+            if let Some(index) = bindings[0].index {
+                changes.push((index, values));
+            }
+        }
+
+        if let Some(table) = self.tables.get_mut(&update.table) {
+            let indexes = update
+                .assignments
+                .iter()
+                .map(|(c, _)| table.ensure_column(c))
+                .collect::<Vec<_>>();
+            for (row, values) in changes {
+                for (i, v) in indexes.iter().zip(values) {
+                    table.rows[row][*i] = v;
+                }
+            }
+        }
+        Ok(Outcome::Changed)
+    }
+
+    fn delete(&mut self, delete: Delete) -> Result<Outcome> {
+        let table_name = table_of(&delete.from, &delete.target)
+            .ok_or_else(|| error(format!("unknown table alias `{}`", delete.target)))?
+            .to_string();
+        let executor = Executor { memory: self };
+        let mut indexes = HashSet::new();
+        for bindings in executor.filtered(&delete.from, delete.filter.as_ref(), None)? {
+            if let Some(index) = bindings
+                .iter()
+                .find(|b| *b.alias == *delete.target)
+                .and_then(|b| b.index)
+            {
+                indexes.insert(index);
+            }
+        }
+
+        if let Some(table) = self.tables.get_mut(&table_name) {
+            let mut i = 0;
+            table.rows.retain(|_| {
+                i += 1;
+                !indexes.contains(&(i - 1))
+            });
+        }
+        Ok(Outcome::Changed)
+    }
+}
+
+fn table_of<'a>(source: &'a Source, alias: &str) -> Option<&'a str> {
+    match source {
+        Source::Table { name, alias: a } if a == alias => Some(name),
+        Source::Join { left, right, .. } => {
+            table_of(left, alias).or_else(|| table_of(right, alias))
+        }
+        _ => None,
+    }
+}
+
+// A row of a table or derived table, that is visible under an alias.
+#[derive(Debug, Clone)]
+struct Binding {
+    alias: Rc<str>,
+    columns: Rc<[String]>,
+    values: Option<Rc<[SqlArg]>>, // None for the missing side of a left join
+    index: Option<usize>,         // Row index in stored table
+}
+
+impl Binding {
+    fn value(&self, column: &str) -> Option<SqlArg> {
+        let i = self
+            .columns
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case(column));
+        match (&self.values, i) {
+            (Some(values), Some(i)) => Some(values[i].to_owned()),
+            _ => None,
+        }
+    }
+    fn has_column(&self, column: &str) -> bool {
+        self.columns.iter().any(|c| c.eq_ignore_ascii_case(column))
+    }
+}
+
+// The bindings of a row, the outer scope is used by correlated subqueries.
+struct Scope<'a> {
+    bindings: &'a [Binding],
+    outer: Option<&'a Scope<'a>>,
+}
+
+impl<'a> Scope<'a> {
+    fn new(bindings: &'a [Binding], outer: Option<&'a Scope<'a>>) -> Self {
+        Scope { bindings, outer }
+    }
+
+    fn column(&self, alias: Option<&str>, column: &str) -> Result<SqlArg> {
+        let mut scope = Some(self);
+        while let Some(s) = scope {
+            let found = match alias {
+                Some(a) => s.bindings.iter().find(|b| &*b.alias == a),
+                None => s.bindings.iter().find(|b| b.has_column(column)),
+            };
+            if let Some(b) = found {
+                return Ok(b.value(column).unwrap_or(SqlArg::Null));
+            }
+            scope = s.outer;
+        }
+        match alias {
+            Some(a) => Err(error(format!("unknown table alias `{}`", a))),
+            // Unknown columns of a single table are not inserted yet
+            None if self.bindings.len() == 1 => Ok(SqlArg::Null),
+            None => Err(error(format!("unknown column `{}`", column))),
+        }
+    }
+}
+
+struct Executor<'m> {
+    memory: &'m Memory,
+}
+
+impl<'m> Executor<'m> {
+    // Return the columns, the rows and the number of rows without limit
+    fn select(
+        &self,
+        select: &Select,
+        outer: Option<&Scope>,
+    ) -> Result<(Vec<String>, Vec<Vec<SqlArg>>, u64)> {
+        let columns = select
+            .items
+            .iter()
+            .map(|i| i.name.to_owned())
+            .collect::<Vec<_>>();
+        let contexts = match &select.from {
+            Some(source) => self.filtered(source, select.filter.as_ref(), outer)?,
+            None => vec![Vec::new()],
+        };
+
+        // Aggregated select returns a single row
+        if select
+            .items
+            .iter()
+            .any(|i| matches!(i.expr, Expr::Count(_)))
+        {
+            let mut row = Vec::with_capacity(select.items.len());
+            for item in &select.items {
+                row.push(match &item.expr {
+                    Expr::Count(None) => SqlArg::U64(contexts.len() as u64),
+                    Expr::Count(Some(e)) => {
+                        let mut n = 0;
+                        for c in &contexts {
+                            if !self.eval(e, &Scope::new(c, outer))?.is_null() {
+                                n += 1;
+                            }
+                        }
+                        SqlArg::U64(n)
+                    }
+                    e => match contexts.first() {
+                        Some(c) => self.eval(e, &Scope::new(c, outer))?,
+                        None => SqlArg::Null,
+                    },
+                });
+            }
+            return Ok((columns, vec![row], 1));
+        }
+
+        // Window functions see all filtered rows
+        let mut windows = HashMap::new();
+        for (i, item) in select.items.iter().enumerate() {
+            if let Expr::RowNumber(partition, order) = &item.expr {
+                windows.insert(i, self.row_numbers(&contexts, partition, order, outer)?);
+            }
+        }
+
+        let mut rows = Vec::with_capacity(contexts.len());
+        for (n, c) in contexts.iter().enumerate() {
+            let scope = Scope::new(c, outer);
+            let mut row = Vec::with_capacity(select.items.len());
+            for (i, item) in select.items.iter().enumerate() {
+                row.push(match windows.get(&i) {
+                    Some(w) => w[n].to_owned(),
+                    None => self.eval(&item.expr, &scope)?,
+                });
+            }
+            let keys = self.order_keys(&select.order, &scope)?;
+            rows.push((keys, row));
+        }
+        if !select.order.is_empty() {
+            rows.sort_by(|(a, _), (b, _)| compare_keys(a, b, &select.order));
+        }
+
+        let mut rows = rows.into_iter().map(|(_, r)| r).collect::<Vec<_>>();
+        if select.distinct {
+            let mut seen = HashSet::new();
+            rows.retain(|r| seen.insert(format!("{:?}", r)));
+        }
+        let found = rows.len() as u64;
+        if let Some((offset, count)) = select.limit {
+            rows = rows
+                .into_iter()
+                .skip(offset as usize)
+                .take(count as usize)
+                .collect();
+        }
+        Ok((columns, rows, found))
+    }
+
+    fn order_keys(&self, order: &[OrderItem], scope: &Scope) -> Result<Vec<SqlArg>> {
+        let mut keys = Vec::with_capacity(order.len());
+        for o in order {
+            keys.push(self.eval(&o.expr, scope)?);
+        }
+        Ok(keys)
+    }
+
+    fn row_numbers(
+        &self,
+        contexts: &[Vec<Binding>],
+        partition: &[Expr],
+        order: &[OrderItem],
+        outer: Option<&Scope>,
+    ) -> Result<Vec<SqlArg>> {
+        let mut partitions: HashMap<String, Vec<(Vec<SqlArg>, usize)>> = HashMap::new();
+        for (n, c) in contexts.iter().enumerate() {
+            let scope = Scope::new(c, outer);
+            let mut key = Vec::with_capacity(partition.len());
+            for p in partition {
+                key.push(self.eval(p, &scope)?);
+            }
+            let keys = self.order_keys(order, &scope)?;
+            partitions
+                .entry(format!("{:?}", key))
+                .or_default()
+                .push((keys, n));
+        }
+        let mut numbers = vec![SqlArg::Null; contexts.len()];
+        for (_, mut rows) in partitions {
+            rows.sort_by(|(a, _), (b, _)| compare_keys(a, b, order));
+            for (i, (_, n)) in rows.into_iter().enumerate() {
+                numbers[n] = SqlArg::U64(i as u64 + 1);
+            }
+        }
+        Ok(numbers)
+    }
+
+    // Return the bindings of all rows of the source, that match the filter
+    fn filtered(
+        &self,
+        source: &Source,
+        filter: Option<&Expr>,
+        outer: Option<&Scope>,
+    ) -> Result<Vec<Vec<Binding>>> {
+        let (_, contexts) = self.source(source, outer)?;
+        match filter {
+            None => Ok(contexts),
+            Some(f) => {
+                let mut filtered = Vec::with_capacity(contexts.len());
+                for c in contexts {
+                    if is_true(&self.eval(f, &Scope::new(&c, outer))?) {
+                        filtered.push(c);
+                    }
+                }
+                Ok(filtered)
+            }
+        }
+    }
+
+    // Return the null bindings of the source and the bindings of all rows
+    fn source(
+        &self,
+        source: &Source,
+        outer: Option<&Scope>,
+    ) -> Result<(Vec<Binding>, Vec<Vec<Binding>>)> {
+        match source {
+            Source::Table { name, alias } => {
+                let alias: Rc<str> = Rc::from(alias.as_str());
+                let (columns, rows): (Rc<[String]>, &[Vec<SqlArg>]) =
+                    match self.memory.tables.get(name) {
+                        Some(t) => (Rc::from(t.columns.as_slice()), &t.rows),
+                        None => (Rc::from(Vec::new()), &[]),
+                    };
+                let empty = Binding {
+                    alias,
+                    columns,
+                    values: None,
+                    index: None,
+                };
+                let rows = rows
+                    .iter()
+                    .enumerate()
+                    .map(|(i, r)| {
+                        vec![Binding {
+                            values: Some(Rc::from(r.as_slice())),
+                            index: Some(i),
+                            ..empty.clone()
+                        }]
+                    })
+                    .collect();
+                Ok((vec![empty], rows))
+            }
+            Source::Derived { select, alias } => {
+                let (columns, rows, _) = self.select(select, outer)?;
+                let empty = Binding {
+                    alias: Rc::from(alias.as_str()),
+                    columns: Rc::from(columns),
+                    values: None,
+                    index: None,
+                };
+                let rows = rows
+                    .into_iter()
+                    .map(|r| {
+                        vec![Binding {
+                            values: Some(Rc::from(r)),
+                            ..empty.clone()
+                        }]
+                    })
+                    .collect();
+                Ok((vec![empty], rows))
+            }
+            Source::Join {
+                left,
+                right,
+                outer: left_join,
+                on,
+            } => {
+                let (mut empty, left_rows) = self.source(left, outer)?;
+                let (right_empty, right_rows) = self.source(right, outer)?;
+                let mut rows = Vec::new();
+                for l in left_rows {
+                    let mut matched = false;
+                    for r in &right_rows {
+                        let mut bindings = l.clone();
+                        bindings.extend(r.iter().cloned());
+                        if is_true(&self.eval(on, &Scope::new(&bindings, outer))?) {
+                            matched = true;
+                            rows.push(bindings);
+                        }
+                    }
+                    if *left_join && !matched {
+                        let mut bindings = l;
+                        bindings.extend(right_empty.iter().cloned());
+                        rows.push(bindings);
+                    }
+                }
+                empty.extend(right_empty);
+                Ok((empty, rows))
+            }
+        }
+    }
+
+    fn eval(&self, expr: &Expr, scope: &Scope) -> Result<SqlArg> {
+        Ok(match expr {
+            Expr::Value(v) => v.to_owned(),
+            Expr::Column(alias, column) => scope.column(alias.as_deref(), column)?,
+            Expr::Tuple(_) => return Err(error("row value is only supported with IN")),
+            Expr::Not(e) => match self.eval(e, scope)? {
+                SqlArg::Null => SqlArg::Null,
+                v => SqlArg::Bool(!is_true(&v)),
+            },
+            Expr::Neg(e) => match self.eval(e, scope)? {
+                SqlArg::U64(n) if n <= i64::MAX as u64 => SqlArg::I64(-(n as i64)),
+                SqlArg::I64(n) if n < 0 => SqlArg::U64(n.unsigned_abs()),
+                SqlArg::I64(n) => SqlArg::I64(-n),
+                SqlArg::Null => SqlArg::Null,
+                v => SqlArg::F64(-number(&v)?),
+            },
+            Expr::Binary(left, op, right) => self.binary(left, *op, right, scope)?,
+            Expr::IsNull(e, negated) => SqlArg::Bool(self.eval(e, scope)?.is_null() != *negated),
+            Expr::In(e, list, negated) => {
+                let values = self.values(e, scope)?;
+                let candidates = match list {
+                    InList::Exprs(exprs) => {
+                        let mut candidates = Vec::with_capacity(exprs.len());
+                        for e in exprs {
+                            candidates.push(self.values(e, scope)?);
+                        }
+                        candidates
+                    }
+                    InList::Select(select) => self.select(select, Some(scope))?.1,
+                };
+                let mut result = SqlArg::Bool(false);
+                for c in candidates {
+                    match tuple_equals(&values, &c) {
+                        Some(true) => {
+                            result = SqlArg::Bool(true);
+                            break;
+                        }
+                        Some(false) => {}
+                        None => result = SqlArg::Null,
+                    }
+                }
+                negate(result, *negated)
+            }
+            Expr::Between(e, low, high, negated) => {
+                let v = self.eval(e, scope)?;
+                let low = compare(&v, &self.eval(low, scope)?);
+                let high = compare(&v, &self.eval(high, scope)?);
+                let result = match (low, high) {
+                    (Some(l), Some(h)) => {
+                        SqlArg::Bool(l != Ordering::Less && h != Ordering::Greater)
+                    }
+                    _ => SqlArg::Null,
+                };
+                negate(result, *negated)
+            }
+            Expr::Like(e, pattern, escape, negated) => {
+                let v = self.eval(e, scope)?;
+                let pattern = self.eval(pattern, scope)?;
+                let escape = match escape {
+                    Some(e) => self.eval(e, scope)?.to_string().chars().next(),
+                    None => Some('\\'),
+                };
+                let result = match (&v, &pattern) {
+                    (SqlArg::Null, _) | (_, SqlArg::Null) => SqlArg::Null,
+                    _ => SqlArg::Bool(like(&v.to_string(), &pattern.to_string(), escape)),
+                };
+                negate(result, *negated)
+            }
+            Expr::Regexp(e, pattern, negated) => {
+                let v = self.eval(e, scope)?;
+                let pattern = self.eval(pattern, scope)?;
+                let result = match (&v, &pattern) {
+                    (SqlArg::Null, _) | (_, SqlArg::Null) => SqlArg::Null,
+                    _ => {
+                        let regex = regex::Regex::new(&pattern.to_string())
+                            .map_err(|e| error(format!("invalid regular expression: {}", e)))?;
+                        SqlArg::Bool(regex.is_match(&v.to_string()))
+                    }
+                };
+                negate(result, *negated)
+            }
+            Expr::Exists(select) => SqlArg::Bool(!self.select(select, Some(scope))?.1.is_empty()),
+            Expr::Subquery(select) => self
+                .select(select, Some(scope))?
+                .1
+                .into_iter()
+                .next()
+                .and_then(|r| r.into_iter().next())
+                .unwrap_or(SqlArg::Null),
+            Expr::Case(whens, otherwise) => {
+                for (condition, value) in whens {
+                    if is_true(&self.eval(condition, scope)?) {
+                        return self.eval(value, scope);
+                    }
+                }
+                match otherwise {
+                    Some(e) => self.eval(e, scope)?,
+                    None => SqlArg::Null,
+                }
+            }
+            Expr::Function(name, args) => {
+                let mut values = Vec::with_capacity(args.len());
+                for a in args {
+                    values.push(self.eval(a, scope)?);
+                }
+                function(name, values)?
+            }
+            Expr::Count(_) => return Err(error("COUNT is only supported as select column")),
+            Expr::RowNumber(..) => {
+                return Err(error("ROW_NUMBER is only supported as select column"))
+            }
+        })
+    }
+
+    // Evaluate an expression or a row value
+    fn values(&self, expr: &Expr, scope: &Scope) -> Result<Vec<SqlArg>> {
+        match expr {
+            Expr::Tuple(exprs) => {
+                let mut values = Vec::with_capacity(exprs.len());
+                for e in exprs {
+                    values.push(self.eval(e, scope)?);
+                }
+                Ok(values)
+            }
+            e => Ok(vec![self.eval(e, scope)?]),
+        }
+    }
+
+    fn binary(&self, left: &Expr, op: BinaryOp, right: &Expr, scope: &Scope) -> Result<SqlArg> {
+        let l = self.eval(left, scope)?;
+        // Short circuit logic
+        match (op, truth(&l)) {
+            (BinaryOp::And, Some(false)) => return Ok(SqlArg::Bool(false)),
+            (BinaryOp::Or, Some(true)) => return Ok(SqlArg::Bool(true)),
+            _ => {}
+        }
+        let r = self.eval(right, scope)?;
+        Ok(match op {
+            BinaryOp::And => match (truth(&l), truth(&r)) {
+                (_, Some(false)) => SqlArg::Bool(false),
+                (Some(true), Some(true)) => SqlArg::Bool(true),
+                _ => SqlArg::Null,
+            },
+            BinaryOp::Or => match (truth(&l), truth(&r)) {
+                (_, Some(true)) => SqlArg::Bool(true),
+                (Some(false), Some(false)) => SqlArg::Bool(false),
+                _ => SqlArg::Null,
+            },
+            BinaryOp::Eq
+            | BinaryOp::Ne
+            | BinaryOp::Lt
+            | BinaryOp::Le
+            | BinaryOp::Gt
+            | BinaryOp::Ge => match compare(&l, &r) {
+                None => SqlArg::Null,
+                Some(o) => SqlArg::Bool(match op {
+                    BinaryOp::Eq => o == Ordering::Equal,
+                    BinaryOp::Ne => o != Ordering::Equal,
+                    BinaryOp::Lt => o == Ordering::Less,
+                    BinaryOp::Le => o != Ordering::Greater,
+                    BinaryOp::Gt => o == Ordering::Greater,
+                    _ => o != Ordering::Less,
+                }),
+            },
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div => {
+                if l.is_null() || r.is_null() {
+                    return Ok(SqlArg::Null);
+                }
+                match (integer(&l), integer(&r), op) {
+                    (Some(a), Some(b), BinaryOp::Add) => from_integer(a + b),
+                    (Some(a), Some(b), BinaryOp::Sub) => from_integer(a - b),
+                    (Some(a), Some(b), BinaryOp::Mul) => from_integer(a * b),
+                    _ => {
+                        let (a, b) = (number(&l)?, number(&r)?);
+                        match op {
+                            BinaryOp::Add => SqlArg::F64(a + b),
+                            BinaryOp::Sub => SqlArg::F64(a - b),
+                            BinaryOp::Mul => SqlArg::F64(a * b),
+                            _ if b == 0.0 => SqlArg::Null,
+                            _ => SqlArg::F64(a / b),
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn function(name: &str, values: Vec<SqlArg>) -> Result<SqlArg> {
+    let text = |v: &SqlArg, f: fn(&str) -> String| match v {
+        SqlArg::Null => SqlArg::Null,
+        v => SqlArg::Str(f(&v.to_string())),
+    };
+    Ok(match (name, values.as_slice()) {
+        ("LOWER", [v]) => text(v, str::to_lowercase),
+        ("UPPER", [v]) => text(v, str::to_uppercase),
+        ("COALESCE", _) | ("IFNULL", _) => values
+            .into_iter()
+            .find(|v| !v.is_null())
+            .unwrap_or(SqlArg::Null),
+        ("CONCAT", _) => {
+            if values.iter().any(|v| v.is_null()) {
+                SqlArg::Null
+            } else {
+                SqlArg::Str(values.iter().map(|v| v.to_string()).collect())
+            }
+        }
+        ("LENGTH", [v]) => match v {
+            SqlArg::Null => SqlArg::Null,
+            v => SqlArg::U64(v.to_string().len() as u64),
+        },
+        _ => return Err(error(format!("unsupported function `{}`", name))),
+    })
+}
+
+fn is_true(value: &SqlArg) -> bool {
+    truth(value).unwrap_or(false)
+}
+
+fn truth(value: &SqlArg) -> Option<bool> {
+    match value {
+        SqlArg::Null => None,
+        SqlArg::Bool(b) => Some(*b),
+        SqlArg::U64(n) => Some(*n != 0),
+        SqlArg::I64(n) => Some(*n != 0),
+        SqlArg::F64(n) => Some(*n != 0.0),
+        SqlArg::Str(s) => Some(s.parse::<f64>().map(|n| n != 0.0).unwrap_or(false)),
+    }
+}
+
+fn negate(value: SqlArg, negated: bool) -> SqlArg {
+    match value {
+        SqlArg::Bool(b) => SqlArg::Bool(b != negated),
+        v => v,
+    }
+}
+
+fn integer(value: &SqlArg) -> Option<i128> {
+    match value {
+        SqlArg::U64(n) => Some(*n as i128),
+        SqlArg::I64(n) => Some(*n as i128),
+        SqlArg::Bool(b) => Some(*b as i128),
+        _ => None,
+    }
+}
+
+fn from_integer(n: i128) -> SqlArg {
+    if n >= 0 && n <= u64::MAX as i128 {
+        SqlArg::U64(n as u64)
+    } else if n >= i64::MIN as i128 && n < 0 {
+        SqlArg::I64(n as i64)
+    } else {
+        SqlArg::F64(n as f64)
+    }
+}
+
+fn number(value: &SqlArg) -> Result<f64> {
+    match value {
+        SqlArg::U64(n) => Ok(*n as f64),
+        SqlArg::I64(n) => Ok(*n as f64),
+        SqlArg::F64(n) => Ok(*n),
+        SqlArg::Bool(b) => Ok(*b as u8 as f64),
+        SqlArg::Str(s) => s
+            .parse()
+            .map_err(|_| error(format!("`{}` is not a number", s))),
+        SqlArg::Null => Err(error("NULL is not a number")),
+    }
+}
+
+// Compare two values, numbers and booleans compare by value, NULL is not comparable
+fn compare(a: &SqlArg, b: &SqlArg) -> Option<Ordering> {
+    match (a, b) {
+        (SqlArg::Null, _) | (_, SqlArg::Null) => None,
+        (SqlArg::Str(a), SqlArg::Str(b)) => Some(a.cmp(b)),
+        (SqlArg::Str(s), n) => s.parse::<f64>().ok()?.partial_cmp(&number(n).ok()?),
+        (n, SqlArg::Str(s)) => number(n).ok()?.partial_cmp(&s.parse::<f64>().ok()?),
+        _ => match (integer(a), integer(b)) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => number(a).ok()?.partial_cmp(&number(b).ok()?),
+        },
+    }
+}
+
+fn tuple_equals(a: &[SqlArg], b: &[SqlArg]) -> Option<bool> {
+    if a.len() != b.len() {
+        return Some(false);
+    }
+    let mut result = Some(true);
+    for (a, b) in a.iter().zip(b) {
+        match compare(a, b) {
+            Some(Ordering::Equal) => {}
+            Some(_) => return Some(false),
+            None => result = None,
+        }
+    }
+    result
+}
+
+// Order like MySQL, NULL is smaller than any value
+fn compare_keys(a: &[SqlArg], b: &[SqlArg], order: &[OrderItem]) -> Ordering {
+    for ((a, b), o) in a.iter().zip(b).zip(order) {
+        let ordering = match (a.is_null(), b.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) | (false, true) => {
+                let nulls_first = o.nulls_first.unwrap_or(!o.desc);
+                match (a.is_null(), nulls_first) {
+                    (true, true) | (false, false) => Ordering::Less,
+                    _ => Ordering::Greater,
+                }
+            }
+            _ => {
+                let ordering = compare(a, b).unwrap_or(Ordering::Equal);
+                if o.desc {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+// SQL LIKE with `%` and `_` wildcards
+fn like(text: &str, pattern: &str, escape: Option<char>) -> bool {
+    enum Part {
+        Char(char),
+        One,
+        Any,
+    }
+    let mut parts = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        parts.push(match c {
+            c if Some(c) == escape => Part::Char(chars.next().unwrap_or(c)),
+            '%' => Part::Any,
+            '_' => Part::One,
+            c => Part::Char(c),
+        });
+    }
+    let text = text.chars().collect::<Vec<_>>();
+
+    // Dynamic programming over pattern parts and text positions
+    let mut matches = vec![false; text.len() + 1];
+    matches[0] = true;
+    for part in &parts {
+        let mut next = vec![false; text.len() + 1];
+        for i in 0..=text.len() {
+            next[i] = match part {
+                Part::Any => matches[i] || (i > 0 && next[i - 1]),
+                Part::One => i > 0 && matches[i - 1],
+                Part::Char(c) => i > 0 && matches[i - 1] && text[i - 1] == *c,
+            };
+        }
+        matches = next;
+    }
+    matches[text.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{like, Memory, Outcome};
+    use crate::sql::Sql;
+    use crate::sql_arg::SqlArg;
+
+    fn execute(memory: &mut Memory, sql: &str) -> Outcome {
+        let mut next_id = 100;
+        memory
+            .execute(
+                &Sql(sql.to_string(), Vec::new()),
+                |_, columns| {
+                    if columns.iter().any(|c| c == "id") {
+                        None
+                    } else {
+                        Some("id".to_string())
+                    }
+                },
+                &mut next_id,
+            )
+            .unwrap()
+    }
+
+    fn rows(memory: &mut Memory, sql: &str) -> String {
+        match execute(memory, sql) {
+            Outcome::Rows(rows, _) => rows
+                .iter()
+                .map(|r| {
+                    r.iter()
+                        .map(|a| a.to_sql_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => panic!("expected rows"),
+        }
+    }
+
+    #[test]
+    fn like_patterns() {
+        assert!(like("abc", "a%", None));
+        assert!(like("abc", "%b%", None));
+        assert!(like("abc", "a_c", None));
+        assert!(!like("abc", "a_", None));
+        assert!(like("50%", "50!%", Some('!')));
+        assert!(!like("500", "50!%", Some('!')));
+    }
+
+    #[test]
+    fn execute_statements() {
+        let mut memory = Memory::default();
+        assert!(matches!(
+            execute(&mut memory, "INSERT INTO Book (id, title) VALUES (1, 'B'), (2, 'A')"),
+            Outcome::Inserted(k) if k.is_empty()
+        ));
+        assert!(matches!(
+            execute(&mut memory, "INSERT INTO Book (title) VALUES (NULL)"),
+            Outcome::Inserted(k) if k == [SqlArg::U64(100)]
+        ));
+        execute(
+            &mut memory,
+            "INSERT INTO Author (id, book_id, name) VALUES (7, 1, 'X')",
+        );
+
+        assert_eq!(
+            rows(
+                &mut memory,
+                "SELECT b.id, b.title FROM Book b ORDER BY b.title ASC"
+            ),
+            "100 NULL, 2 'A', 1 'B'"
+        );
+        assert_eq!(
+            rows(
+                &mut memory,
+                "SELECT b.id, a.name FROM Book b LEFT JOIN (Author a) ON (a.book_id = b.id) \
+                WHERE b.title IS NOT NULL ORDER BY b.id DESC LIMIT 1, 5"
+            ),
+            "1 'X'"
+        );
+        assert_eq!(
+            rows(
+                &mut memory,
+                "SELECT COUNT(*) FROM Book b WHERE b.id IN (1, 2)"
+            ),
+            "2"
+        );
+
+        execute(&mut memory, "UPDATE Book SET title = 'C' WHERE id = 2");
+        execute(&mut memory, "DELETE b FROM Book b WHERE b.id = 100");
+        assert_eq!(
+            rows(&mut memory, "SELECT b.title FROM Book b ORDER BY b.id ASC"),
+            "'B', 'C'"
+        );
+    }
+}
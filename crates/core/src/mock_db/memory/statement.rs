@@ -0,0 +1,944 @@
+//! Parser for the SQL subset that Toql generates.
+//!
+//! The statements are parsed from the raw SQL with its arguments,
+//! so that arguments keep their type and need no unquoting.
+use crate::error::ToqlError;
+use crate::mock_db::mock_db_error::MockDbError;
+use crate::result::Result;
+use crate::sql::Sql;
+use crate::sql_arg::SqlArg;
+
+#[derive(Debug)]
+pub(crate) enum Statement {
+    Select(Select),
+    Insert(Insert),
+    Update(Update),
+    Delete(Delete),
+}
+
+#[derive(Debug)]
+pub(crate) struct Select {
+    pub(crate) distinct: bool,
+    pub(crate) items: Vec<SelectItem>,
+    pub(crate) from: Option<Source>,
+    pub(crate) filter: Option<Expr>,
+    pub(crate) order: Vec<OrderItem>,
+    pub(crate) limit: Option<(u64, u64)>, // Offset, count
+}
+
+#[derive(Debug)]
+pub(crate) struct SelectItem {
+    pub(crate) expr: Expr,
+    pub(crate) name: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct OrderItem {
+    pub(crate) expr: Expr,
+    pub(crate) desc: bool,
+    pub(crate) nulls_first: Option<bool>,
+}
+
+#[derive(Debug)]
+pub(crate) enum Source {
+    Table {
+        name: String,
+        alias: String,
+    },
+    Derived {
+        select: Box<Select>,
+        alias: String,
+    },
+    Join {
+        left: Box<Source>,
+        right: Box<Source>,
+        outer: bool,
+        on: Expr,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct Insert {
+    pub(crate) table: String,
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<Vec<Option<Expr>>>, // None for DEFAULT
+}
+
+#[derive(Debug)]
+pub(crate) struct Update {
+    pub(crate) table: String,
+    pub(crate) alias: String,
+    pub(crate) assignments: Vec<(String, Expr)>,
+    pub(crate) filter: Option<Expr>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Delete {
+    pub(crate) target: String, // Alias of the table to delete from
+    pub(crate) from: Source,
+    pub(crate) filter: Option<Expr>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BinaryOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    And,
+    Or,
+}
+
+#[derive(Debug)]
+pub(crate) enum InList {
+    Exprs(Vec<Expr>),
+    Select(Box<Select>),
+}
+
+#[derive(Debug)]
+pub(crate) enum Expr {
+    Value(SqlArg),
+    Column(Option<String>, String), // Optional alias, column
+    Tuple(Vec<Expr>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    IsNull(Box<Expr>, bool), // Negated
+    In(Box<Expr>, InList, bool),
+    Between(Box<Expr>, Box<Expr>, Box<Expr>, bool),
+    Like(Box<Expr>, Box<Expr>, Option<Box<Expr>>, bool), // Pattern, escape
+    Regexp(Box<Expr>, Box<Expr>, bool),
+    Exists(Box<Select>),
+    Subquery(Box<Select>),
+    Case(Vec<(Expr, Expr)>, Option<Box<Expr>>),
+    Function(String, Vec<Expr>),
+    Count(Option<Box<Expr>>), // None for COUNT(*)
+    RowNumber(Vec<Expr>, Vec<OrderItem>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    QuotedWord(String),
+    Str(String),
+    Number(String),
+    Arg(SqlArg),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: &[&str] = &[
+    "<=", ">=", "<>", "!=", "=", "<", ">", "(", ")", ",", ".", "*", "+", "-", "/", ";",
+];
+
+// Words that end a table reference or an expression
+const RESERVED: &[&str] = &[
+    "SELECT", "FROM", "WHERE", "JOIN", "LEFT", "RIGHT", "INNER", "OUTER", "CROSS", "ON", "ORDER",
+    "GROUP", "BY", "LIMIT", "OFFSET", "AND", "OR", "NOT", "AS", "SET", "VALUES", "UNION", "ASC",
+    "DESC", "NULLS", "THEN", "WHEN", "ELSE", "END", "ESCAPE", "IS", "IN", "LIKE", "BETWEEN",
+    "REGEXP",
+];
+
+pub(crate) fn error(message: impl Into<String>) -> ToqlError {
+    ToqlError::MockDbError(MockDbError::Sql(message.into()))
+}
+
+fn tokenize(sql: &Sql) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut args = sql.1.iter();
+    let mut chars = sql.0.char_indices().peekable();
+    let text = &sql.0;
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '\'' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '\'')) if matches!(chars.peek(), Some((_, '\''))) => {
+                            chars.next();
+                            s.push('\'');
+                        }
+                        Some((_, '\'')) => break,
+                        Some((_, c)) => s.push(c),
+                        None => return Err(error("unterminated string")),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            '`' | '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, q)) if q == c => break,
+                        Some((_, c)) => s.push(c),
+                        None => return Err(error("unterminated identifier")),
+                    }
+                }
+                tokens.push(Token::QuotedWord(s));
+            }
+            '?' => {
+                let arg = args
+                    .next()
+                    .ok_or_else(|| error("missing argument for `?`"))?;
+                tokens.push(Token::Arg(arg.to_owned()));
+            }
+            '$' => {
+                let mut end = start + 1;
+                while let Some((i, d)) = chars.peek() {
+                    if !d.is_ascii_digit() {
+                        break;
+                    }
+                    end = i + 1;
+                    chars.next();
+                }
+                let position: usize = text[start + 1..end]
+                    .parse()
+                    .map_err(|_| error("invalid positional argument"))?;
+                let arg = position
+                    .checked_sub(1)
+                    .and_then(|p| sql.1.get(p))
+                    .ok_or_else(|| error(format!("missing argument for `${}`", position)))?;
+                tokens.push(Token::Arg(arg.to_owned()));
+            }
+            c if c.is_ascii_digit() => {
+                let mut end = start + 1;
+                let mut exponent = false;
+                while let Some((i, d)) = chars.peek().cloned() {
+                    let accepted = d.is_ascii_digit()
+                        || d == '.'
+                        || (!exponent && (d == 'e' || d == 'E'))
+                        || ((d == '+' || d == '-') && text[..i].ends_with(['e', 'E']));
+                    if !accepted {
+                        break;
+                    }
+                    exponent |= d == 'e' || d == 'E';
+                    end = i + 1;
+                    chars.next();
+                }
+                tokens.push(Token::Number(text[start..end].to_string()));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, d)) = chars.peek().cloned() {
+                    if !(d.is_alphanumeric() || d == '_') {
+                        break;
+                    }
+                    end = i + d.len_utf8();
+                    chars.next();
+                }
+                tokens.push(Token::Word(text[start..end].to_string()));
+            }
+            _ => {
+                let symbol = SYMBOLS
+                    .iter()
+                    .find(|s| text[start..].starts_with(*s))
+                    .ok_or_else(|| error(format!("unexpected character `{}`", c)))?;
+                for _ in 1..symbol.len() {
+                    chars.next();
+                }
+                tokens.push(Token::Symbol(symbol));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+pub(crate) fn parse(sql: &Sql) -> Result<Statement> {
+    let mut parser = Parser {
+        tokens: tokenize(sql)?,
+        pos: 0,
+    };
+    let statement = parser.statement()?;
+    parser.symbol(";");
+    if let Some(t) = parser.tokens.get(parser.pos) {
+        return Err(error(format!("unexpected `{:?}` after statement", t)));
+    }
+    Ok(statement)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+    fn peek_keyword(&self, offset: usize, keyword: &str) -> bool {
+        matches!(self.tokens.get(self.pos + offset), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+    fn keyword(&mut self, keyword: &str) -> bool {
+        if self.peek_keyword(0, keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(keyword))
+        }
+    }
+    fn symbol(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+    fn expect_symbol(&mut self, symbol: &str) -> Result<()> {
+        if self.symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected(symbol))
+        }
+    }
+    fn unexpected(&self, expected: &str) -> ToqlError {
+        match self.peek() {
+            Some(t) => error(format!("expected `{}`, found `{:?}`", expected, t)),
+            None => error(format!("expected `{}`, found end of statement", expected)),
+        }
+    }
+    fn identifier(&mut self) -> Result<String> {
+        match self.peek().cloned() {
+            Some(Token::Word(w)) if !is_reserved(&w) => {
+                self.pos += 1;
+                Ok(w)
+            }
+            Some(Token::QuotedWord(w)) => {
+                self.pos += 1;
+                Ok(w)
+            }
+            _ => Err(self.unexpected("identifier")),
+        }
+    }
+    // Optional alias after a table or select column
+    fn alias(&mut self) -> Result<Option<String>> {
+        if self.keyword("AS") {
+            return self.identifier().map(Some);
+        }
+        match self.peek() {
+            Some(Token::Word(w)) if !is_reserved(w) => self.identifier().map(Some),
+            Some(Token::QuotedWord(_)) => self.identifier().map(Some),
+            _ => Ok(None),
+        }
+    }
+    fn number(&mut self) -> Result<u64> {
+        match self.peek().cloned() {
+            Some(Token::Number(n)) => {
+                self.pos += 1;
+                n.parse()
+                    .map_err(|_| error(format!("invalid number `{}`", n)))
+            }
+            Some(Token::Arg(a)) => {
+                self.pos += 1;
+                a.to_string()
+                    .parse()
+                    .map_err(|_| error(format!("invalid number `{}`", a)))
+            }
+            _ => Err(self.unexpected("number")),
+        }
+    }
+
+    fn statement(&mut self) -> Result<Statement> {
+        if self.keyword("SELECT") {
+            self.select_body().map(Statement::Select)
+        } else if self.keyword("INSERT") {
+            self.insert().map(Statement::Insert)
+        } else if self.keyword("UPDATE") {
+            self.update().map(Statement::Update)
+        } else if self.keyword("DELETE") {
+            self.delete().map(Statement::Delete)
+        } else {
+            Err(self.unexpected("SELECT, INSERT, UPDATE or DELETE"))
+        }
+    }
+
+    fn select_body(&mut self) -> Result<Select> {
+        let distinct = self.keyword("DISTINCT");
+        let mut items = Vec::new();
+        loop {
+            let expr = self.expr()?;
+            let name = match self.alias()? {
+                Some(alias) => alias,
+                None => match &expr {
+                    Expr::Column(_, column) => column.to_owned(),
+                    _ => String::new(),
+                },
+            };
+            items.push(SelectItem { expr, name });
+            if !self.symbol(",") {
+                break;
+            }
+        }
+        let from = if self.keyword("FROM") {
+            Some(self.source()?)
+        } else {
+            None
+        };
+        let filter = self.filter()?;
+        let order = if self.keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            self.order_items()?
+        } else {
+            Vec::new()
+        };
+        let limit = if self.keyword("LIMIT") {
+            let first = self.number()?;
+            if self.symbol(",") {
+                Some((first, self.number()?))
+            } else if self.keyword("OFFSET") {
+                Some((self.number()?, first))
+            } else {
+                Some((0, first))
+            }
+        } else {
+            None
+        };
+        Ok(Select {
+            distinct,
+            items,
+            from,
+            filter,
+            order,
+            limit,
+        })
+    }
+
+    fn filter(&mut self) -> Result<Option<Expr>> {
+        if self.keyword("WHERE") {
+            self.expr().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn order_items(&mut self) -> Result<Vec<OrderItem>> {
+        let mut items = Vec::new();
+        loop {
+            let expr = self.expr()?;
+            let desc = if self.keyword("DESC") {
+                true
+            } else {
+                self.keyword("ASC");
+                false
+            };
+            let nulls_first = if self.keyword("NULLS") {
+                if self.keyword("FIRST") {
+                    Some(true)
+                } else {
+                    self.expect_keyword("LAST")?;
+                    Some(false)
+                }
+            } else {
+                None
+            };
+            items.push(OrderItem {
+                expr,
+                desc,
+                nulls_first,
+            });
+            if !self.symbol(",") {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn source(&mut self) -> Result<Source> {
+        let mut source = self.primary_source()?;
+        loop {
+            let outer = if self.keyword("LEFT") {
+                self.keyword("OUTER");
+                true
+            } else {
+                self.keyword("INNER");
+                false
+            };
+            if !self.keyword("JOIN") {
+                if outer {
+                    return Err(self.unexpected("JOIN"));
+                }
+                break;
+            }
+            let right = self.primary_source()?;
+            self.expect_keyword("ON")?;
+            let on = self.expr()?;
+            source = Source::Join {
+                left: Box::new(source),
+                right: Box::new(right),
+                outer,
+                on,
+            };
+        }
+        Ok(source)
+    }
+
+    fn primary_source(&mut self) -> Result<Source> {
+        if self.symbol("(") {
+            if self.keyword("SELECT") {
+                let select = self.select_body()?;
+                self.expect_symbol(")")?;
+                let alias = self
+                    .alias()?
+                    .ok_or_else(|| error("derived table needs an alias"))?;
+                return Ok(Source::Derived {
+                    select: Box::new(select),
+                    alias,
+                });
+            }
+            let source = self.source()?;
+            self.expect_symbol(")")?;
+            return Ok(source);
+        }
+        let mut name = self.identifier()?;
+        if self.symbol(".") {
+            // Schema qualified table, the schema is ignored
+            name = self.identifier()?;
+        }
+        let alias = self.alias()?.unwrap_or_else(|| name.clone());
+        Ok(Source::Table { name, alias })
+    }
+
+    fn insert(&mut self) -> Result<Insert> {
+        self.expect_keyword("INTO")?;
+        let table = self.identifier()?;
+        self.expect_symbol("(")?;
+        let mut columns = Vec::new();
+        loop {
+            columns.push(self.identifier()?);
+            if !self.symbol(",") {
+                break;
+            }
+        }
+        self.expect_symbol(")")?;
+        self.expect_keyword("VALUES")?;
+        let mut rows = Vec::new();
+        loop {
+            self.expect_symbol("(")?;
+            let mut values = Vec::new();
+            loop {
+                if self.keyword("DEFAULT") {
+                    values.push(None);
+                } else {
+                    values.push(Some(self.expr()?));
+                }
+                if !self.symbol(",") {
+                    break;
+                }
+            }
+            self.expect_symbol(")")?;
+            if values.len() != columns.len() {
+                return Err(error("number of values does not match number of columns"));
+            }
+            rows.push(values);
+            if !self.symbol(",") {
+                break;
+            }
+        }
+        Ok(Insert {
+            table,
+            columns,
+            rows,
+        })
+    }
+
+    fn update(&mut self) -> Result<Update> {
+        let table = self.identifier()?;
+        let alias = self.alias()?.unwrap_or_else(|| table.clone());
+        self.expect_keyword("SET")?;
+        let mut assignments = Vec::new();
+        loop {
+            let mut column = self.identifier()?;
+            if self.symbol(".") {
+                column = self.identifier()?;
+            }
+            self.expect_symbol("=")?;
+            assignments.push((column, self.expr()?));
+            if !self.symbol(",") {
+                break;
+            }
+        }
+        let filter = self.filter()?;
+        Ok(Update {
+            table,
+            alias,
+            assignments,
+            filter,
+        })
+    }
+
+    fn delete(&mut self) -> Result<Delete> {
+        let target = if self.peek_keyword(0, "FROM") {
+            None
+        } else {
+            Some(self.identifier()?)
+        };
+        self.expect_keyword("FROM")?;
+        let from = self.source()?;
+        let target = match (target, &from) {
+            (Some(t), _) => t,
+            (None, Source::Table { alias, .. }) => alias.to_owned(),
+            (None, _) => return Err(error("DELETE with joins needs a target alias")),
+        };
+        let filter = self.filter()?;
+        Ok(Delete {
+            target,
+            from,
+            filter,
+        })
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let mut left = self.and_expr()?;
+        while self.keyword("OR") {
+            let right = self.and_expr()?;
+            left = Expr::Binary(Box::new(left), BinaryOp::Or, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr> {
+        let mut left = self.not_expr()?;
+        while self.keyword("AND") {
+            let right = self.not_expr()?;
+            left = Expr::Binary(Box::new(left), BinaryOp::And, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn not_expr(&mut self) -> Result<Expr> {
+        if self.keyword("NOT") {
+            Ok(Expr::Not(Box::new(self.not_expr()?)))
+        } else {
+            self.comparison()
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let left = self.additive()?;
+        let op = match self.peek() {
+            Some(Token::Symbol("=")) => Some(BinaryOp::Eq),
+            Some(Token::Symbol("<>")) | Some(Token::Symbol("!=")) => Some(BinaryOp::Ne),
+            Some(Token::Symbol("<")) => Some(BinaryOp::Lt),
+            Some(Token::Symbol("<=")) => Some(BinaryOp::Le),
+            Some(Token::Symbol(">")) => Some(BinaryOp::Gt),
+            Some(Token::Symbol(">=")) => Some(BinaryOp::Ge),
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.pos += 1;
+            let right = self.additive()?;
+            return Ok(Expr::Binary(Box::new(left), op, Box::new(right)));
+        }
+        if self.keyword("IS") {
+            let negated = self.keyword("NOT");
+            self.expect_keyword("NULL")?;
+            return Ok(Expr::IsNull(Box::new(left), negated));
+        }
+        let negated = ["IN", "BETWEEN", "LIKE", "REGEXP"]
+            .iter()
+            .any(|k| self.peek_keyword(0, "NOT") && self.peek_keyword(1, k));
+        if negated {
+            self.pos += 1;
+        }
+        let left = Box::new(left);
+        if self.keyword("IN") {
+            self.expect_symbol("(")?;
+            let list = if self.keyword("SELECT") {
+                InList::Select(Box::new(self.select_body()?))
+            } else {
+                InList::Exprs(self.expr_list()?)
+            };
+            self.expect_symbol(")")?;
+            Ok(Expr::In(left, list, negated))
+        } else if self.keyword("BETWEEN") {
+            let low = self.additive()?;
+            self.expect_keyword("AND")?;
+            let high = self.additive()?;
+            Ok(Expr::Between(left, Box::new(low), Box::new(high), negated))
+        } else if self.keyword("LIKE") {
+            let pattern = self.additive()?;
+            let escape = if self.keyword("ESCAPE") {
+                Some(Box::new(self.additive()?))
+            } else {
+                None
+            };
+            Ok(Expr::Like(left, Box::new(pattern), escape, negated))
+        } else if self.keyword("REGEXP") {
+            let pattern = self.additive()?;
+            Ok(Expr::Regexp(left, Box::new(pattern), negated))
+        } else {
+            Ok(*left)
+        }
+    }
+
+    fn expr_list(&mut self) -> Result<Vec<Expr>> {
+        let mut exprs = Vec::new();
+        loop {
+            exprs.push(self.expr()?);
+            if !self.symbol(",") {
+                break;
+            }
+        }
+        Ok(exprs)
+    }
+
+    fn additive(&mut self) -> Result<Expr> {
+        let mut left = self.multiplicative()?;
+        loop {
+            let op = if self.symbol("+") {
+                BinaryOp::Add
+            } else if self.symbol("-") {
+                BinaryOp::Sub
+            } else {
+                break;
+            };
+            let right = self.multiplicative()?;
+            left = Expr::Binary(Box::new(left), op, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.symbol("*") {
+                BinaryOp::Mul
+            } else if self.symbol("/") {
+                BinaryOp::Div
+            } else {
+                break;
+            };
+            let right = self.unary()?;
+            left = Expr::Binary(Box::new(left), op, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.symbol("-") {
+            Ok(Expr::Neg(Box::new(self.unary()?)))
+        } else if self.symbol("+") {
+            self.unary()
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or_else(|| self.unexpected("expression"))?;
+        self.pos += 1;
+        match token {
+            Token::Arg(a) => Ok(Expr::Value(a)),
+            Token::Str(s) => Ok(Expr::Value(SqlArg::Str(s))),
+            Token::Number(n) => parse_number(&n).map(Expr::Value),
+            Token::Symbol("(") => {
+                if self.keyword("SELECT") {
+                    let select = self.select_body()?;
+                    self.expect_symbol(")")?;
+                    return Ok(Expr::Subquery(Box::new(select)));
+                }
+                let mut exprs = self.expr_list()?;
+                self.expect_symbol(")")?;
+                if exprs.len() == 1 {
+                    Ok(exprs.pop().unwrap())
+                } else {
+                    Ok(Expr::Tuple(exprs))
+                }
+            }
+            Token::QuotedWord(w) => self.column(w),
+            Token::Word(w) => {
+                let upper = w.to_ascii_uppercase();
+                match upper.as_str() {
+                    "NULL" => return Ok(Expr::Value(SqlArg::Null)),
+                    "TRUE" => return Ok(Expr::Value(SqlArg::Bool(true))),
+                    "FALSE" => return Ok(Expr::Value(SqlArg::Bool(false))),
+                    "EXISTS" => {
+                        self.expect_symbol("(")?;
+                        self.expect_keyword("SELECT")?;
+                        let select = self.select_body()?;
+                        self.expect_symbol(")")?;
+                        return Ok(Expr::Exists(Box::new(select)));
+                    }
+                    "CASE" => return self.case(),
+                    _ => {}
+                }
+                if !self.symbol("(") {
+                    if is_reserved(&w) {
+                        self.pos -= 1;
+                        return Err(self.unexpected("expression"));
+                    }
+                    return self.column(w);
+                }
+                match upper.as_str() {
+                    "COUNT" => {
+                        let arg = if self.symbol("*") {
+                            None
+                        } else {
+                            Some(Box::new(self.expr()?))
+                        };
+                        self.expect_symbol(")")?;
+                        Ok(Expr::Count(arg))
+                    }
+                    "ROW_NUMBER" => {
+                        self.expect_symbol(")")?;
+                        self.expect_keyword("OVER")?;
+                        self.expect_symbol("(")?;
+                        let partition = if self.keyword("PARTITION") {
+                            self.expect_keyword("BY")?;
+                            self.expr_list()?
+                        } else {
+                            Vec::new()
+                        };
+                        let order = if self.keyword("ORDER") {
+                            self.expect_keyword("BY")?;
+                            self.order_items()?
+                        } else {
+                            Vec::new()
+                        };
+                        self.expect_symbol(")")?;
+                        Ok(Expr::RowNumber(partition, order))
+                    }
+                    _ => {
+                        let args = if self.symbol(")") {
+                            Vec::new()
+                        } else {
+                            let args = self.expr_list()?;
+                            self.expect_symbol(")")?;
+                            args
+                        };
+                        Ok(Expr::Function(upper, args))
+                    }
+                }
+            }
+            t => {
+                self.pos -= 1;
+                Err(error(format!("unexpected `{:?}` in expression", t)))
+            }
+        }
+    }
+
+    fn column(&mut self, first: String) -> Result<Expr> {
+        if self.symbol(".") {
+            let column = self.identifier()?;
+            Ok(Expr::Column(Some(first), column))
+        } else {
+            Ok(Expr::Column(None, first))
+        }
+    }
+
+    fn case(&mut self) -> Result<Expr> {
+        let mut whens = Vec::new();
+        while self.keyword("WHEN") {
+            let condition = self.expr()?;
+            self.expect_keyword("THEN")?;
+            whens.push((condition, self.expr()?));
+        }
+        if whens.is_empty() {
+            return Err(self.unexpected("WHEN"));
+        }
+        let otherwise = if self.keyword("ELSE") {
+            Some(Box::new(self.expr()?))
+        } else {
+            None
+        };
+        self.expect_keyword("END")?;
+        Ok(Expr::Case(whens, otherwise))
+    }
+}
+
+fn is_reserved(word: &str) -> bool {
+    RESERVED.iter().any(|r| r.eq_ignore_ascii_case(word))
+}
+
+fn parse_number(n: &str) -> Result<SqlArg> {
+    let invalid = || error(format!("invalid number `{}`", n));
+    if n.contains(['.', 'e', 'E']) {
+        n.parse::<f64>().map(SqlArg::F64).map_err(|_| invalid())
+    } else {
+        n.parse::<u64>().map(SqlArg::U64).map_err(|_| invalid())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, Expr, Source, Statement};
+    use crate::sql::Sql;
+    use crate::sql_arg::SqlArg;
+
+    fn sql(s: &str, args: Vec<SqlArg>) -> Sql {
+        Sql(s.to_string(), args)
+    }
+
+    #[test]
+    fn parse_select() {
+        let statement = parse(&sql(
+            "SELECT DISTINCT l1.id, (SELECT 'a') AS x FROM Level1 l1 \
+            LEFT JOIN (Level2 l2 JOIN (Level3 l3) ON (l2.l3_id = l3.id)) ON (l1.l2_id = l2.id) \
+            WHERE l1.id = ? AND l1.text NOT LIKE 'a!%' ESCAPE '!' ORDER BY l1.id DESC LIMIT 5, 10",
+            vec![SqlArg::U64(1)],
+        ))
+        .unwrap();
+        let select = match statement {
+            Statement::Select(s) => s,
+            s => panic!("expected select, got {:?}", s),
+        };
+        assert!(select.distinct);
+        assert_eq!(select.items[0].name, "id");
+        assert_eq!(select.items[1].name, "x");
+        assert!(matches!(
+            select.from,
+            Some(Source::Join { outer: true, .. })
+        ));
+        assert_eq!(select.limit, Some((5, 10)));
+        assert!(select.order[0].desc);
+    }
+
+    #[test]
+    fn parse_mutations() {
+        let statement = parse(&sql(
+            "INSERT INTO Level1 (id, text) VALUES (1, 'it''s'), (2, DEFAULT)",
+            vec![],
+        ))
+        .unwrap();
+        match statement {
+            Statement::Insert(i) => {
+                assert_eq!(i.columns, ["id", "text"]);
+                assert!(matches!(&i.rows[0][1], Some(Expr::Value(SqlArg::Str(s))) if s == "it's"));
+                assert!(i.rows[1][1].is_none());
+            }
+            s => panic!("expected insert, got {:?}", s),
+        }
+
+        assert!(matches!(
+            parse(&sql("UPDATE Level1 SET text = 'a' WHERE id = 1", vec![])).unwrap(),
+            Statement::Update(_)
+        ));
+        assert!(matches!(
+            parse(&sql(
+                "DELETE l1 FROM Level1 l1 WHERE l1.id = $1",
+                vec![SqlArg::U64(1)]
+            ))
+            .unwrap(),
+            Statement::Delete(_)
+        ));
+        assert!(parse(&sql("DROP TABLE Level1", vec![])).is_err());
+        assert!(parse(&sql("SELECT l1.id FROM Level1 l1 WHERE", vec![])).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! Errors from the [MockDb](crate::mock_db::MockDb)
+use thiserror::Error;
+
+/// Represents all errors from the Mock Db, including errors that expectations inject.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MockDbError {
+    /// A unique key is violated. Contains the key description.
+    #[error("duplicate entry for key `{0}`")]
+    DuplicateKey(String),
+
+    /// The transaction was aborted to resolve a deadlock.
+    #[error("deadlock found when trying to get lock")]
+    Deadlock,
+
+    /// The connection to the database was lost.
+    #[error("lost connection to database")]
+    ConnectionLost,
+
+    /// Any other database error. Contains the error message.
+    #[error("{0}")]
+    Other(String),
+
+    /// The in-memory tables cannot execute the statement. Contains the reason.
+    #[error("{0}")]
+    Sql(String),
+
+    /// A fixture cannot be parsed or does not fit the mapped entities. Contains the reason.
+    #[error("invalid fixture: {0}")]
+    Fixture(String),
+
+    /// The statement matches an expectation, whose preceding expectations are not consumed.
+    /// Contains the statement.
+    #[error("statement `{0}` is out of order")]
+    OutOfOrder(String),
+
+    /// An expectation was called less often than expected.
+    /// Contains the expectation, the expected and the actual number of calls.
+    #[error("expectation `{0}` was called {2} of {1} times")]
+    Unconsumed(String, usize, usize),
+
+    /// An expectation was called more often than expected.
+    /// Contains the expectation, the expected and the actual number of calls.
+    #[error("expectation `{0}` was called {2} times instead of {1}")]
+    Overcalled(String, usize, usize),
+
+    /// A statement matches no expectation. Contains the statement.
+    #[error("statement `{0}` was not expected")]
+    Unexpected(String),
+}
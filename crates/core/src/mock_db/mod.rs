@@ -1,20 +1,39 @@
 //! The Toql Mock Db provides a dummy database that can be used for testing or documentation examples.
+//!
+//! By default the Mock Db only records the SQL statements and returns mocked rows.
+//! Created with [MockDb::in_memory] it keeps in-memory tables instead and executes the statements,
+//! so that inserted entities can be loaded again.
+//!
+//! [Expectations](expectation::Expectation) answer matching statements with configured rows or errors
+//! and can be verified to have been called.
 
 use crate::backend::context::Context;
 use crate::cache::Cache;
+use crate::result::Result;
+use crate::table_mapper::mapped::Mapped;
+use crate::tree::tree_map::TreeMap;
 use std::collections::HashMap;
 
 pub mod backend;
+pub mod expectation;
+#[cfg(feature = "fixtures")]
+pub mod fixture;
+mod memory;
+pub mod mock_db_error;
+pub mod snapshot;
 #[macro_use]
 pub mod row;
 
 pub mod toql_api;
 
 use backend::MockDbBackend;
+use expectation::Expectation;
+use memory::Memory;
+use mock_db_error::MockDbError;
 
 ///
 /// The implementation collects all SQL statements that can be asserted.
-/// For loading it return default values.
+/// For loading it return default values or the rows of its in-memory tables.
 ///
 pub struct MockDb<'a> {
     backend: MockDbBackend<'a>,
@@ -27,9 +46,15 @@ impl<'a> MockDb<'a> {
     pub fn reset_id(&mut self) {
         self.backend.current_id = self.backend.start_id;
     }
+    // Generated keys of in-memory tables must stay unique
+    fn reset_id_if_recording(&mut self) {
+        if self.backend.memory.is_none() {
+            self.reset_id();
+        }
+    }
     pub fn take_unsafe_sqls(&mut self) -> Vec<String> {
         self.clear_rows();
-        self.reset_id();
+        self.reset_id_if_recording();
 
         self.backend
             .sqls
@@ -39,7 +64,7 @@ impl<'a> MockDb<'a> {
     }
     pub fn take_unsafe_sql(&mut self) -> String {
         self.clear_rows();
-        self.reset_id();
+        self.reset_id_if_recording();
         let len = self.backend.sqls.len();
         if len == 0 {
             "<<No SQL statement>>".to_string()
@@ -56,6 +81,90 @@ impl<'a> MockDb<'a> {
     pub fn mock_rows(&mut self, select: impl Into<String>, rows: Vec<row::Row>) {
         self.backend.rows.insert(select.into(), rows);
     }
+    /// Return the rows of an in-memory table in insertion order.
+    /// The values follow the order of [table_columns](MockDb::table_columns).
+    pub fn table_rows(&self, table: &str) -> Vec<row::Row> {
+        self.table(table)
+            .map(|t| t.rows.iter().cloned().map(row::Row).collect())
+            .unwrap_or_default()
+    }
+    /// Return the columns of an in-memory table in order of their first insert.
+    pub fn table_columns(&self, table: &str) -> Vec<String> {
+        self.table(table)
+            .map(|t| t.columns.clone())
+            .unwrap_or_default()
+    }
+    fn table(&self, table: &str) -> Option<&memory::Table> {
+        self.backend.memory.as_ref()?.tables.get(table)
+    }
+
+    /// Add an expectation.
+    /// A statement is answered by the first matching expectation that is not yet consumed.
+    /// Statements without such an expectation behave as usual,
+    /// but are recorded as over-call or unexpected statement.
+    pub fn expect(&mut self, expectation: Expectation) -> &mut Self {
+        self.backend.expectations.push(expectation);
+        self
+    }
+    /// Verify that all expectations were called exactly as often as expected
+    /// and that no unexpected statement was executed.
+    pub fn verify(&self) -> std::result::Result<(), MockDbError> {
+        for e in &self.backend.expectations {
+            if e.calls < e.times {
+                return Err(MockDbError::Unconsumed(e.to_string(), e.times, e.calls));
+            }
+            if e.calls > e.times {
+                return Err(MockDbError::Overcalled(e.to_string(), e.times, e.calls));
+            }
+        }
+        match self.backend.unexpected.first() {
+            Some(statement) => Err(MockDbError::Unexpected(statement.to_owned())),
+            None => Ok(()),
+        }
+    }
+    pub fn clear_expectations(&mut self) {
+        self.backend.expectations.clear();
+        self.backend.unexpected.clear();
+    }
+
+    /// Map an entity together with its joined and merged entities.
+    /// Fixtures can only seed mapped entities.
+    pub fn map<T: Mapped + TreeMap>(&mut self) -> Result<&mut Self> {
+        let mut registry = self.backend.cache.registry.write()?;
+        crate::backend::map::map::<T>(&mut registry)?;
+        drop(registry);
+        Ok(self)
+    }
+
+    /// Seed the in-memory tables from a JSON [fixture](fixture).
+    #[cfg(feature = "fixtures")]
+    pub fn seed_json(&mut self, json: &str) -> Result<()> {
+        let fixture =
+            serde_json::from_str(json).map_err(|e| MockDbError::Fixture(e.to_string()))?;
+        self.seed(&fixture)
+    }
+
+    /// Seed the in-memory tables from a YAML [fixture](fixture).
+    #[cfg(feature = "fixtures")]
+    pub fn seed_yaml(&mut self, yaml: &str) -> Result<()> {
+        let fixture =
+            serde_yaml::from_str(yaml).map_err(|e| MockDbError::Fixture(e.to_string()))?;
+        self.seed(&fixture)
+    }
+
+    #[cfg(feature = "fixtures")]
+    fn seed(&mut self, fixture: &serde_json::Value) -> Result<()> {
+        let registry = self.backend.cache.registry.read()?;
+        let memory = self.backend.memory.as_mut().ok_or_else(|| {
+            MockDbError::Fixture("fixtures need a Mock Db with in-memory tables".to_string())
+        })?;
+        fixture::Seeder {
+            registry: &registry,
+            memory,
+            next_id: &mut self.backend.current_id,
+        }
+        .seed(fixture)
+    }
 }
 
 impl<'a> MockDb<'a> {
@@ -72,7 +181,27 @@ impl<'a> MockDb<'a> {
                 rows: HashMap::new(),
                 current_id: 100,
                 start_id: 100, // Start at index 100
+                memory: None,
+                found_rows: 0,
+                expectations: Vec::new(),
+                unexpected: Vec::new(),
             },
         }
     }
+
+    /// Create a Mock Db with empty in-memory tables.
+    ///
+    /// The statements are still recorded, but selects return the rows of the tables
+    /// instead of mocked rows. Tables are created on the first insert,
+    /// keys that an insert omits are generated from 100 on.
+    pub fn in_memory(cache: &'a Cache) -> MockDb<'a> {
+        Self::in_memory_with_context(cache, Context::default())
+    }
+
+    /// Create a Mock Db with empty in-memory tables and a context.
+    pub fn in_memory_with_context(cache: &'a Cache, context: Context) -> MockDb<'a> {
+        let mut mock_db = Self::with_context(cache, context);
+        mock_db.backend.memory = Some(Memory::default());
+        mock_db
+    }
 }
@@ -0,0 +1,219 @@
+//! Snapshot tests for the generated SQL.
+//!
+//! A [Snapshot] renders the SQL statements of an entity for some queries in every [AliasFormat]
+//! and compares them with a stored snapshot file. Mismatches panic with a line diff.
+//! Set the environment variable `TOQL_UPDATE_SNAPSHOTS=1` to write the current SQL instead.
+//!
+//! ### Example
+//! ```ignore
+//! let cache = Cache::new();
+//! let snapshot = Snapshot::new(&cache, "tests/snapshots");
+//! let user = User::default();
+//! snapshot.assert(&user, &[query!(User, "*, id eq 5")]).await;
+//! ```
+use super::row::Row;
+use super::MockDb;
+use crate::alias_format::AliasFormat;
+use crate::backend::context_builder::ContextBuilder;
+use crate::cache::Cache;
+use crate::error::ToqlError;
+use crate::from_row::FromRow;
+use crate::keyed::Keyed;
+use crate::query::Query;
+use crate::table_mapper::mapped::Mapped;
+use crate::toql_api::{
+    count::Count, delete::Delete, fields::Fields, insert::Insert, load::Load, paths::Paths,
+    update::Update, ToqlApi,
+};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Environment variable to update snapshot files instead of comparing them.
+pub const UPDATE_SNAPSHOTS: &str = "TOQL_UPDATE_SNAPSHOTS";
+
+const ALIAS_FORMATS: [AliasFormat; 4] = [
+    AliasFormat::Canonical,
+    AliasFormat::MediumIndex,
+    AliasFormat::ShortIndex,
+    AliasFormat::TinyIndex,
+];
+
+/// Compares the generated SQL of entities with snapshot files in a directory.
+/// Every entity type has its own file `<type name>.sql`.
+pub struct Snapshot<'a> {
+    cache: &'a Cache,
+    dir: PathBuf,
+    update: bool,
+}
+
+impl<'a> Snapshot<'a> {
+    /// Create snapshots in the directory.
+    /// Updates the files, if the environment variable `TOQL_UPDATE_SNAPSHOTS` is set.
+    pub fn new(cache: &'a Cache, dir: impl Into<PathBuf>) -> Self {
+        Snapshot {
+            cache,
+            dir: dir.into(),
+            update: matches!(std::env::var_os(UPDATE_SNAPSHOTS), Some(v) if v != "0"),
+        }
+    }
+
+    /// Update the snapshot files instead of comparing them.
+    pub fn update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Render load, count, insert, update and delete statements of the entity in every alias format.
+    /// Loads, counts and deletes are rendered for every query, insert and update for the entity.
+    /// Failing statements are rendered with their error.
+    /// Every alias format inserts and updates its own clone of the entity.
+    pub async fn render<T>(&self, entity: &T, queries: &[Query<T>]) -> String
+    where
+        T: Load<Row, ToqlError> + Count + Insert + Update + Delete + Keyed + Clone + Sync,
+        <T as Keyed>::Key: FromRow<Row, ToqlError>,
+    {
+        let mut snapshot = String::new();
+        for alias_format in ALIAS_FORMATS.iter() {
+            let context = ContextBuilder::new()
+                .with_alias_format(alias_format.to_owned())
+                .build();
+            let mut toql = MockDb::with_context(self.cache, context);
+            writeln!(snapshot, "-- {:?}", alias_format).unwrap();
+
+            for query in queries {
+                let result = toql.load_many(query).await.map(|_| ());
+                section(
+                    &mut snapshot,
+                    &mut toql,
+                    format!("load `{}`", query),
+                    result,
+                );
+                let result = toql.count(query).await.map(|_| ());
+                section(
+                    &mut snapshot,
+                    &mut toql,
+                    format!("count `{}`", query),
+                    result,
+                );
+            }
+            let mut entity = entity.clone();
+            let result = toql.insert_one(&mut entity, Paths::top()).await;
+            section(&mut snapshot, &mut toql, "insert".to_string(), result);
+            let result = toql.update_one(&mut entity, Fields::top()).await;
+            section(&mut snapshot, &mut toql, "update".to_string(), result);
+            for query in queries {
+                let result = toql.delete_many(query).await;
+                section(
+                    &mut snapshot,
+                    &mut toql,
+                    format!("delete `{}`", query),
+                    result,
+                );
+            }
+            snapshot.push('\n');
+        }
+        snapshot
+    }
+
+    /// Render the statements of the entity and compare them with its snapshot file.
+    ///
+    /// # Panics
+    /// If the snapshot file is missing or differs.
+    pub async fn assert<T>(&self, entity: &T, queries: &[Query<T>])
+    where
+        T: Load<Row, ToqlError> + Count + Insert + Update + Delete + Keyed + Clone + Sync,
+        <T as Keyed>::Key: FromRow<Row, ToqlError>,
+    {
+        let actual = self.render(entity, queries).await;
+        let path = self.dir.join(format!("{}.sql", <T as Mapped>::type_name()));
+
+        if self.update {
+            std::fs::create_dir_all(&self.dir)
+                .and_then(|_| std::fs::write(&path, &actual))
+                .unwrap_or_else(|e| panic!("cannot write snapshot `{}`: {}", path.display(), e));
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "cannot read snapshot `{}`: {}, set {}=1 to create it",
+                path.display(),
+                e,
+                UPDATE_SNAPSHOTS
+            )
+        });
+        if expected != actual {
+            panic!(
+                "snapshot `{}` does not match, set {}=1 to update it:\n{}",
+                path.display(),
+                UPDATE_SNAPSHOTS,
+                diff(&expected, &actual)
+            );
+        }
+    }
+}
+
+fn section(
+    snapshot: &mut String,
+    toql: &mut MockDb,
+    title: String,
+    result: std::result::Result<(), ToqlError>,
+) {
+    writeln!(snapshot, "-- {}", title).unwrap();
+    for sql in toql.take_unsafe_sqls() {
+        writeln!(snapshot, "{}", sql).unwrap();
+    }
+    if let Err(e) = result {
+        writeln!(snapshot, "-- error: {}", e).unwrap();
+    }
+}
+
+// Line diff from the longest common subsequence.
+// Removed lines start with `-`, added lines with `+`
+fn diff(expected: &str, actual: &str) -> String {
+    let old = expected.lines().collect::<Vec<_>>();
+    let new = actual.lines().collect::<Vec<_>>();
+
+    // Length of common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            writeln!(diff, "  {}", old[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            writeln!(diff, "- {}", old[i]).unwrap();
+            i += 1;
+        } else {
+            writeln!(diff, "+ {}", new[j]).unwrap();
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod test {
+    use super::diff;
+
+    #[test]
+    fn line_diff() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nx\nc\nd\n"),
+            "  a\n- b\n+ x\n  c\n+ d\n"
+        );
+        assert_eq!(diff("", "a"), "+ a\n");
+        assert_eq!(diff("a", ""), "- a\n");
+    }
+}
@@ -29,7 +29,6 @@ impl<'a> ToqlApi for MockDb<'a> {
     type Row = Row;
     type Error = ToqlError;
 
-    #[tracing::instrument(skip(self, entity, paths), fields(ty = %<T as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn insert_one<T>(&mut self, entity: &mut T, paths: Paths) -> Result<(), Self::Error>
     where
         T: Insert,
@@ -41,7 +40,6 @@ impl<'a> ToqlApi for MockDb<'a> {
     ///
     /// Skip fields in struct that are auto generated with `#[toql(skip_inup)]`.
     /// Returns the last generated id.
-    #[tracing::instrument(skip(self, entities, paths), fields(ty = %<T as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn insert_many<T, Q>(
         &mut self,
         entities: &mut [Q],
@@ -54,14 +52,12 @@ impl<'a> ToqlApi for MockDb<'a> {
         insert(&mut self.backend, entities, paths).await
     }
 
-    #[tracing::instrument(skip(self, entity, fields), fields(ty = %<T as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn update_one<T>(&mut self, entity: &mut T, fields: Fields) -> Result<(), Self::Error>
     where
         T: Update + Keyed,
     {
         update::<_, _, T, _, _>(&mut self.backend, &mut [entity], fields).await
     }
-    #[tracing::instrument(skip(self, entities, fields), fields(ty = %<T as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn update_many<T, Q>(
         &mut self,
         entities: &mut [Q],
@@ -77,7 +73,6 @@ impl<'a> ToqlApi for MockDb<'a> {
     /// Load a struct with dependencies for a given Toql query.
     ///
     /// Returns a struct or a [ToqlMySqlAsyncError](../toql/error/enum.ToqlMySqlAsyncError.html) if no struct was found _NotFound_ or more than one _NotUnique_.
-    #[tracing::instrument(skip(self, query), fields(ty = %<T as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn load_one<T, B>(&mut self, query: B) -> Result<T, Self::Error>
     where
         T: Load<Self::Row, Self::Error>,
@@ -97,7 +92,6 @@ impl<'a> ToqlApi for MockDb<'a> {
     /// Returns a tuple with the structs and an optional tuple of count values.
     /// If `count` argument is `false`, no count queries are run and the resulting `Option<(u32,u32)>` will be `None`
     /// otherwise the count queries are run and it will be `Some((total count, filtered count))`.
-    #[tracing::instrument(skip(self, query), fields(ty = %<T as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn load_many<T, B>(&mut self, query: B) -> Result<Vec<T>, Self::Error>
     where
         T: Load<Self::Row, Self::Error>,
@@ -113,7 +107,6 @@ impl<'a> ToqlApi for MockDb<'a> {
     /// Returns a tuple with the structs and an optional tuple of count values.
     /// If `count` argument is `false`, no count queries are run and the resulting `Option<(u32,u32)>` will be `None`
     /// otherwise the count queries are run and it will be `Some((unpaged count, unfiltered count))`.
-    #[tracing::instrument(skip(self, query), fields(ty = %<T as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn load_page<T, B>(
         &mut self,
         query: B,
@@ -132,7 +125,6 @@ impl<'a> ToqlApi for MockDb<'a> {
     /// Counts the number of rows that match the query predicate.
     ///
     /// Returns a struct or a [ToqlMySqlAsyncError](../toql/error/enum.ToqlMySqlAsyncError.html) if no struct was found _NotFound_ or more than one _NotUnique_.
-    #[tracing::instrument(skip(self, query), fields(ty = %<T as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn count<T, B>(&mut self, query: B) -> Result<u64, Self::Error>
     where
         T: Count,
@@ -141,7 +133,6 @@ impl<'a> ToqlApi for MockDb<'a> {
         count(&mut self.backend, query).await
     }
 
-    #[tracing::instrument(skip(self, key), fields(ty = %<<K as Key>::Entity as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn delete_one<K>(&mut self, key: K) -> Result<(), Self::Error>
     where
         K: Key + Into<Query<<K as Key>::Entity>> + Send,
@@ -152,7 +143,6 @@ impl<'a> ToqlApi for MockDb<'a> {
         Ok(())
     }
 
-    #[tracing::instrument(skip(self, query), fields(ty = %<T as crate::table_mapper::mapped::Mapped>::type_name()))]
     async fn delete_many<T, B>(&mut self, query: B) -> Result<(), Self::Error>
     where
         T: Delete,
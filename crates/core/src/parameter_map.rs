@@ -2,25 +2,41 @@
 /// It is used to combine multiple aux parameters, that may come
 /// from the [Context](backend/context/struct.Context),
 /// from a [Query](query/struct.Query) or a predicate or field mapping.
-use crate::sql_arg::SqlArg;
+/// The map also carries the [RegexSyntax] of the database for field handlers.
+use crate::{field_handler::RegexSyntax, sql_arg::SqlArg};
 use std::collections::HashMap;
 
 #[derive(Debug)]
 ///
-pub struct ParameterMap<'a>(&'a [&'a HashMap<String, SqlArg>]);
+pub struct ParameterMap<'a> {
+    params: &'a [&'a HashMap<String, SqlArg>],
+    regex_syntax: &'a RegexSyntax,
+}
 
 impl<'a> ParameterMap<'a> {
     pub fn new(params: &'a [&'a HashMap<String, SqlArg>]) -> Self {
-        ParameterMap(params)
+        ParameterMap {
+            params,
+            regex_syntax: &crate::field_handler::REGEXP,
+        }
+    }
+    /// Set the regex syntax of the database.
+    pub fn with_regex_syntax(mut self, regex_syntax: &'a RegexSyntax) -> Self {
+        self.regex_syntax = regex_syntax;
+        self
+    }
+    /// Returns the regex syntax of the database.
+    pub fn regex_syntax(&self) -> &RegexSyntax {
+        self.regex_syntax
     }
     /// Returns true, if the map contains a parameter with the given name.
     pub fn contains(&self, name: &str) -> bool {
-        self.0.iter().any(|m| m.contains_key(name))
+        self.params.iter().any(|m| m.contains_key(name))
     }
 
     // Searches the map for a given parameter and returns its value or None.
     pub fn get(&self, name: &str) -> Option<&'a SqlArg> {
-        for p in self.0 {
+        for p in self.params {
             if let Some(p) = p.get(name) {
                 return Some(p);
             }
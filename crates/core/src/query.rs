@@ -23,14 +23,30 @@
 //! ```
 //! The query macro produces a [Query] type, so the result can
 //! modified with builder functions.
+//!
+//! ## Serde
+//! With the `serde` feature a [Query] can be serialized and deserialized.
+//! Unlike the string form this keeps the aux params, bound placeholder values, where predicates, join statements, merge limits and the distinct flag.
+//! Tokens, filters, orders, concatenations and arguments are externally tagged in snake case,
+//! so the query `+1.id EQ 5` becomes
+//! ```json
+//! {"tokens":[{"field":{"concatenation":"and","name":"id","hidden":true,
+//!     "order":{"asc":1},"filter":{"eq":{"u64":5}}}}],
+//!  "distinct":false,"aux_params":{},"where_predicates":[],"where_predicate_params":[],
+//!  "select_columns":[],"join_stmts":[],"join_stmt_params":[],"merge_limits":{}}
+//! ```
+//! Missing query fields take their default value.
 pub mod concatenation;
 pub mod field;
 pub mod field_filter;
 pub mod field_order;
 pub mod field_path;
+pub mod field_quantifier;
 pub mod from_key_fields;
 pub mod predicate;
+pub mod query_folder;
 pub mod query_token;
+pub mod query_visitor;
 pub mod query_with;
 pub mod selection;
 pub mod wildcard;
@@ -43,7 +59,9 @@ use std::fmt;
 use concatenation::Concatenation;
 use field::Field;
 use predicate::Predicate;
+use query_folder::QueryFolder;
 use query_token::QueryToken;
+use query_visitor::QueryVisitor;
 use query_with::QueryWith;
 use wildcard::Wildcard;
 
@@ -78,6 +96,18 @@ use wildcard::Wildcard;
 /// assert_eq!("a eq 1; (b eq 3, c eq 2)", q2.to_string())
 /// ```
 ///
+/// Better use [restrict()](struct.Query.html#method.restrict), it parenthesizes both queries.
+///
+/// ``` ignore
+/// let q = user_query.restrict(Field::from("permission").ne(""));
+///
+/// assert_eq!("((*, id nen); id),(permission NE '')", q.to_string())
+/// ```
+///
+/// Restrictions that must apply to every query of an entity can be added to the
+/// [Context](crate::backend::context::Context), the [SqlBuilder](crate::sql_builder::SqlBuilder)
+/// then adds them to every select, count and delete statement.
+///
 /// For every fields of a struct the Toql derive generates fields.
 /// For a Toql derived struct it's possible to write
 ///
@@ -91,6 +121,11 @@ use wildcard::Wildcard;
 ///
 use crate::sql_arg::SqlArg;
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_feature", serde(bound = "", default))]
 pub struct Query<M> {
     pub(crate) tokens: Vec<QueryToken>,
     /// Select DISTINCT
@@ -114,7 +149,18 @@ pub struct Query<M> {
     // Join params for additional sql restriction
     pub join_stmt_params: Vec<SqlArg>,
 
+    /// Maximum number of merged entities per parent, keyed by merge path
+    pub merge_limits: HashMap<String, u64>,
+
+    /// Values for named placeholders, keyed by placeholder name
+    #[cfg_attr(
+        feature = "serde_feature",
+        serde(skip_serializing_if = "HashMap::is_empty")
+    )]
+    pub bind_params: HashMap<String, SqlArg>,
+
     /// Type marker
+    #[cfg_attr(feature = "serde_feature", serde(skip))]
     pub type_marker: std::marker::PhantomData<M>,
 }
 
@@ -130,6 +176,8 @@ impl<M> Query<M> {
             select_columns: Vec::new(),
             join_stmts: Vec::new(),
             join_stmt_params: Vec::new(),
+            merge_limits: HashMap::new(),
+            bind_params: HashMap::new(),
             type_marker: std::marker::PhantomData, //  wildcard_scope: None
         }
     }
@@ -153,6 +201,8 @@ impl<M> Query<M> {
             select_columns: self.select_columns.clone(),
             join_stmts: self.join_stmts.clone(),
             join_stmt_params: self.join_stmt_params.clone(),
+            merge_limits: self.merge_limits.clone(),
+            bind_params: self.bind_params.clone(),
             type_marker: std::marker::PhantomData,
         }
     }
@@ -201,6 +251,8 @@ impl<M> Query<M> {
             select_columns: self.select_columns.clone(),
             join_stmts: self.join_stmts.clone(),
             join_stmt_params: self.join_stmt_params.clone(),
+            merge_limits: self.merge_limits.clone(),
+            bind_params: self.bind_params.clone(),
             type_marker: std::marker::PhantomData,
         }
     }
@@ -217,6 +269,8 @@ impl<M> Query<M> {
             select_columns: Vec::new(),
             join_stmts: Vec::new(),
             join_stmt_params: Vec::new(),
+            merge_limits: HashMap::new(),
+            bind_params: HashMap::new(),
             type_marker: std::marker::PhantomData, //  wildcard_scope: None
         }
     }
@@ -231,6 +285,8 @@ impl<M> Query<M> {
             select_columns: Vec::new(),
             join_stmts: Vec::new(),
             join_stmt_params: Vec::new(),
+            merge_limits: HashMap::new(),
+            bind_params: HashMap::new(),
             type_marker: std::marker::PhantomData, //  wildcard_scope: None
         }
     }
@@ -275,11 +331,11 @@ impl<M> Query<M> {
         let mut query = query.into();
         if !self.tokens.is_empty() {
             match query.tokens.get_mut(0) {
-                Some(QueryToken::LeftBracket(c)) => *c = Concatenation::Or,
+                Some(QueryToken::LeftBracket(c)) => *c = c.to_or(),
                 Some(QueryToken::RightBracket) => {}
-                Some(QueryToken::Field(f)) => f.concatenation = Concatenation::Or,
+                Some(QueryToken::Field(f)) => f.concatenation = f.concatenation.to_or(),
                 Some(QueryToken::Wildcard(w)) => w.concatenation = Concatenation::Or,
-                Some(QueryToken::Predicate(p)) => p.concatenation = Concatenation::Or,
+                Some(QueryToken::Predicate(p)) => p.concatenation = p.concatenation.to_or(),
                 Some(QueryToken::Selection(p)) => p.concatenation = Concatenation::Or,
                 None => {}
             }
@@ -300,11 +356,76 @@ impl<M> Query<M> {
         self
     }
 
+    /// Negate the query.
+    /// A single field or predicate is negated directly,
+    /// any other query is wrapped with negated parentheses.
+    /// Negating twice restores the original query.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(mut self) -> Self {
+        let parenthesized = self.is_parenthesized();
+        match self.tokens.as_mut_slice() {
+            [] => {}
+            [QueryToken::Field(f)] => f.concatenation = f.concatenation.negate(),
+            [QueryToken::Predicate(p)] => p.concatenation = p.concatenation.negate(),
+            [QueryToken::LeftBracket(c), ..] if parenthesized => *c = c.negate(),
+            _ => {
+                self.tokens
+                    .insert(0, QueryToken::LeftBracket(Concatenation::AndNot));
+                self.tokens.push(QueryToken::RightBracket);
+            }
+        }
+        self
+    }
+
+    // Returns true, if the first left bracket is closed by the last token.
+    fn is_parenthesized(&self) -> bool {
+        let mut depth = 0;
+        for (i, t) in self.tokens.iter().enumerate() {
+            match t {
+                QueryToken::LeftBracket(_) => depth += 1,
+                QueryToken::RightBracket => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return i + 1 == self.tokens.len();
+                    }
+                }
+                _ if depth == 0 => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
     /// Modifiy the query with an additional stuct.
     pub fn with(self, query_with: impl QueryWith<M>) -> Self {
         query_with.with(self)
     }
 
+    /// Restrict the query with a permission query.
+    /// Both queries are parenthesized, so a leading OR in this query cannot bypass the restriction.
+    /// The aux params and bound placeholder values of the restriction are added to this query.
+    pub fn restrict<T>(self, restriction: T) -> Self
+    where
+        T: Into<Query<M>>,
+    {
+        let mut restriction = restriction.into();
+        let mut query = self.parenthesize();
+        query.aux_params.extend(restriction.aux_params.drain());
+        query.bind_params.extend(restriction.bind_params.drain());
+        if restriction.tokens.is_empty() {
+            query
+        } else {
+            query.and_parentized(restriction)
+        }
+    }
+    /// Restrict the query with an additional struct.
+    /// The struct modifies an empty query, that is then used as restriction.
+    /// See [restrict()](struct.Query.html#method.restrict).
+    pub fn restrict_with(self, query_with: impl QueryWith<M>) -> Self {
+        let restriction = query_with.with(Query::new());
+        self.restrict(restriction)
+    }
+
     /// Convenence method to add aux params
     pub fn aux_param<S, A>(mut self, name: S, value: A) -> Self
     where
@@ -315,6 +436,36 @@ impl<M> Query<M> {
         self
     }
 
+    /// Bind a value to the named placeholder `:name` in filters and predicate arguments.
+    /// Placeholders are only bound with these values and never with aux params,
+    /// so a query from a client cannot read aux params of the server.
+    /// Unbound placeholders raise an error, when the query is built.
+    pub fn bind<S, A>(mut self, name: S, value: A) -> Self
+    where
+        A: Into<SqlArg>,
+        S: Into<String>,
+    {
+        self.bind_params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Select only distinct rows.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Load at most `limit` merged entities per parent for the merge path,
+    /// e.g. the latest 5 comments of every post with `limit_merge("comments", 5)`.
+    /// This overrides the limit from the mapping. Order the merge to choose the entities.
+    pub fn limit_merge<S>(mut self, merge_path: S, limit: u64) -> Self
+    where
+        S: Into<String>,
+    {
+        self.merge_limits.insert(merge_path.into(), limit);
+        self
+    }
+
     /// Check if query contains path
     /// Example: Path is 'user_address'
     /// Valid query paths are 'user_*', 'user_address_*', 'user_address_country_*,'user_address_id'
@@ -345,6 +496,54 @@ impl<M> Query<M> {
             }
         })
     }
+
+    /// Walk through all query tokens with a [QueryVisitor].
+    pub fn accept<V: QueryVisitor>(&self, visitor: &mut V) {
+        for token in &self.tokens {
+            match token {
+                QueryToken::LeftBracket(concatenation) => visitor.enter_parens(concatenation),
+                QueryToken::RightBracket => visitor.leave_parens(),
+                QueryToken::Field(field) => visitor.visit_field(field),
+                QueryToken::Predicate(predicate) => visitor.visit_predicate(predicate),
+                QueryToken::Wildcard(wildcard) => visitor.visit_wildcard(wildcard),
+                QueryToken::Selection(selection) => visitor.visit_selection(selection),
+            }
+        }
+    }
+
+    /// Rewrite all query tokens with a [QueryFolder].
+    /// Parentheses that become empty are removed.
+    pub fn fold<F: QueryFolder>(mut self, folder: &mut F) -> Self {
+        let mut tokens = Vec::with_capacity(self.tokens.len());
+        for token in self.tokens.drain(..) {
+            let token = match token {
+                QueryToken::Field(field) => folder.fold_field(field).map(QueryToken::Field),
+                QueryToken::Predicate(predicate) => {
+                    folder.fold_predicate(predicate).map(QueryToken::Predicate)
+                }
+                QueryToken::Wildcard(wildcard) => {
+                    folder.fold_wildcard(wildcard).map(QueryToken::Wildcard)
+                }
+                QueryToken::Selection(selection) => {
+                    folder.fold_selection(selection).map(QueryToken::Selection)
+                }
+                QueryToken::RightBracket => {
+                    if let Some(QueryToken::LeftBracket(_)) = tokens.last() {
+                        tokens.pop();
+                        None
+                    } else {
+                        Some(QueryToken::RightBracket)
+                    }
+                }
+                t => Some(t),
+            };
+            if let Some(t) = token {
+                tokens.push(t);
+            }
+        }
+        self.tokens = tokens;
+        self
+    }
 }
 
 /// Asserts that the provided roles contains all required roles.
@@ -366,15 +565,20 @@ pub fn assert_roles(
 impl<M> fmt::Display for Query<M> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fn get_concatenation(c: &Concatenation) -> char {
-            match c {
-                Concatenation::And => ',',
-                Concatenation::Or => ';',
+            if c.is_and() {
+                ','
+            } else {
+                ';'
             }
         }
 
         let mut s = String::new();
         let mut concatenation_needed = false;
 
+        if self.distinct {
+            f.write_str("DISTINCT ")?;
+        }
+
         for token in &self.tokens {
             if concatenation_needed {
                 match &token {
@@ -462,9 +666,18 @@ impl<M> Default for Query<M> {
     }
 }
 
+impl<M> Clone for Query<M> {
+    fn clone(&self) -> Self {
+        self.clone_for_type()
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{query_with::QueryWith, Field, Predicate, Query, Selection, Wildcard};
+    use super::{
+        concatenation::Concatenation, query_folder::QueryFolder, query_visitor::QueryVisitor,
+        query_with::QueryWith, Field, Predicate, Query, Selection, Wildcard,
+    };
     use crate::sql_arg::SqlArg;
 
     struct User;
@@ -533,4 +746,126 @@ mod test {
         assert_eq!(q.to_string(), "prop,item EQ 1");
         assert_eq!(q.aux_params.get("thing"), Some(&SqlArg::from("item")));
     }
+
+    #[test]
+    fn restrict() {
+        let q = Query::<User>::from(Wildcard::new())
+            .and(Field::from("id").nen())
+            .or(Field::from("id"));
+        assert_eq!(
+            q.restrict(Field::from("permission").ne("")).to_string(),
+            "(*,id NEN;id),(permission NE '')"
+        );
+
+        let q = Query::<User>::new().restrict(Field::from("permission").ne(""));
+        assert_eq!(q.to_string(), "(permission NE '')");
+
+        let q = Query::<User>::from(Field::from("prop")).restrict(Query::new());
+        assert_eq!(q.to_string(), "(prop)");
+
+        let q = Query::<User>::from(Field::from("prop"))
+            .or(Field::from("text"))
+            .restrict_with(Item);
+        assert_eq!(q.to_string(), "(prop;text),(item EQ 1)");
+        assert_eq!(q.aux_params.get("thing"), Some(&SqlArg::from("item")));
+    }
+
+    #[test]
+    fn not() {
+        let q = Query::<User>::from(Field::from("prop").eq(1)).not();
+        assert_eq!(q.to_string(), "!prop EQ 1");
+        assert_eq!(q.not().to_string(), "prop EQ 1");
+
+        let q = Query::<User>::from(Field::from("prop").eq(1))
+            .or(Predicate::from("pred"))
+            .not();
+        assert_eq!(q.to_string(), "!(prop EQ 1;@pred)");
+        assert_eq!(q.not().to_string(), "(prop EQ 1;@pred)");
+
+        let q = Query::<User>::from(Field::from("id"))
+            .or(Query::from(Field::from("prop").eq(1)).not())
+            .or(Query::from(Field::from("a"))
+                .and(Field::from("b"))
+                .parenthesize()
+                .not());
+        assert_eq!(q.to_string(), "id;!prop EQ 1;!(a,b)");
+
+        let q = Query::<User>::from(Field::from("a"))
+            .parenthesize()
+            .and(Field::from("b"))
+            .not();
+        assert_eq!(q.to_string(), "!((a),b)");
+    }
+
+    #[derive(Default)]
+    struct Tokens(Vec<String>);
+
+    impl QueryVisitor for Tokens {
+        fn visit_field(&mut self, field: &Field) {
+            self.0.push(field.name().to_string());
+        }
+        fn visit_predicate(&mut self, predicate: &Predicate) {
+            self.0.push(format!("@{}", predicate.name()));
+        }
+        fn visit_wildcard(&mut self, wildcard: &Wildcard) {
+            self.0.push(format!("{}*", wildcard.path()));
+        }
+        fn visit_selection(&mut self, selection: &Selection) {
+            self.0.push(format!("${}", selection.name()));
+        }
+        fn enter_parens(&mut self, concatenation: &Concatenation) {
+            self.0.push(format!("{:?}(", concatenation));
+        }
+        fn leave_parens(&mut self) {
+            self.0.push(")".to_string());
+        }
+    }
+
+    struct Strip(&'static str);
+
+    impl QueryFolder for Strip {
+        fn fold_field(&mut self, field: Field) -> Option<Field> {
+            if field.name().starts_with(self.0) {
+                None
+            } else {
+                Some(field.unordered().unfiltered())
+            }
+        }
+        fn fold_predicate(&mut self, predicate: Predicate) -> Option<Predicate> {
+            Some(predicate.rename("other"))
+        }
+    }
+
+    #[test]
+    fn visit_and_fold() {
+        let q = Query::<User>::from(Wildcard::from("level1"))
+            .or_parentized(
+                Query::from(Field::from("level2_prop").eq(1))
+                    .and(Predicate::from("pred").is(2))
+                    .or(Selection::from("std")),
+            )
+            .and_parentized(Field::from("level3_prop"))
+            .and(Field::from("prop").asc(1).eq(5));
+
+        let mut tokens = Tokens::default();
+        q.accept(&mut tokens);
+        assert_eq!(
+            tokens.0,
+            vec![
+                "level1_*",
+                "Or(",
+                "level2_prop",
+                "@pred",
+                "$std",
+                ")",
+                "And(",
+                "level3_prop",
+                ")",
+                "prop"
+            ]
+        );
+
+        let q = q.fold(&mut Strip("level"));
+        assert_eq!(q.to_string(), "level1_*;(@other 2;$std),prop");
+    }
 }
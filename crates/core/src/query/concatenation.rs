@@ -1,5 +1,42 @@
+/// The concatenation of a query token with the previous one.
+/// The negated variants put a `NOT` in front of the token.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_feature", serde(rename_all = "snake_case"))]
 pub enum Concatenation {
     And,
     Or,
+    AndNot,
+    OrNot,
+}
+
+impl Concatenation {
+    /// Returns true for `And` and `AndNot`.
+    pub fn is_and(&self) -> bool {
+        matches!(self, Concatenation::And | Concatenation::AndNot)
+    }
+    /// Returns true for `AndNot` and `OrNot`.
+    pub fn is_negated(&self) -> bool {
+        matches!(self, Concatenation::AndNot | Concatenation::OrNot)
+    }
+    /// Returns the concatenation with the opposite negation.
+    pub fn negate(&self) -> Self {
+        match self {
+            Concatenation::And => Concatenation::AndNot,
+            Concatenation::Or => Concatenation::OrNot,
+            Concatenation::AndNot => Concatenation::And,
+            Concatenation::OrNot => Concatenation::Or,
+        }
+    }
+    /// Returns the OR concatenation with the same negation.
+    pub fn to_or(&self) -> Self {
+        if self.is_negated() {
+            Concatenation::OrNot
+        } else {
+            Concatenation::Or
+        }
+    }
 }
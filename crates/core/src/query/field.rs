@@ -11,18 +11,44 @@
 /// ```
 use super::concatenation::Concatenation;
 use super::field_filter::FieldFilter;
-use super::field_order::FieldOrder;
+use super::field_order::{FieldOrder, NullsOrder};
+use super::field_quantifier::FieldQuantifier;
 use crate::sql_arg::SqlArg;
+use std::collections::BTreeMap;
 //use heck::MixedCase;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct Field {
     pub(crate) concatenation: Concatenation,
     pub(crate) name: String,
     pub(crate) hidden: bool,
     pub(crate) order: Option<FieldOrder>,
+    #[cfg_attr(
+        feature = "serde_feature",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub(crate) nulls: Option<NullsOrder>, // Placement of null values, only with order
     pub(crate) filter: Option<FieldFilter>,
-    //  pub(crate) aggregation: bool,
+    #[cfg_attr(
+        feature = "serde_feature",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub(crate) quantifier: Option<FieldQuantifier>,
+    #[cfg_attr(
+        feature = "serde_feature",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub(crate) placeholders: BTreeMap<usize, String>, // Filter argument position, parameter name
+    #[cfg_attr(
+        feature = "serde_feature",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub(crate) field_refs: BTreeMap<usize, String>, // Filter argument position, other field name
+                                                    //  pub(crate) aggregation: bool,
 }
 
 impl Field {
@@ -48,7 +74,11 @@ impl Field {
             name,
             hidden: false,
             order: None,
+            nulls: None,
             filter: None,
+            quantifier: None,
+            placeholders: BTreeMap::new(),
+            field_refs: BTreeMap::new(),
         }
     }
     /// Hide field. Useful if a field should not be selected, but be used for filtering.
@@ -66,6 +96,18 @@ impl Field {
         self.order = Some(FieldOrder::Desc(order));
         self
     }
+    /// Place null values first, when records are ordered by this field.
+    /// Must be called after [asc](Field::asc) or [desc](Field::desc).
+    pub fn nulls_first(mut self) -> Self {
+        self.nulls = self.order.as_ref().map(|_| NullsOrder::First);
+        self
+    }
+    /// Place null values last, when records are ordered by this field.
+    /// Must be called after [asc](Field::asc) or [desc](Field::desc).
+    pub fn nulls_last(mut self) -> Self {
+        self.nulls = self.order.as_ref().map(|_| NullsOrder::Last);
+        self
+    }
     /// Filter records with _equal_ predicate.
     pub fn eq(mut self, criteria: impl Into<SqlArg>) -> Self {
         self.filter = Some(FieldFilter::Eq(criteria.into()));
@@ -116,6 +158,37 @@ impl Field {
         self.filter = Some(FieldFilter::Lk(criteria.into()));
         self
     }
+    /// Filter records with _regular expression_ predicate.
+    /// The SQL depends on the [RegexSyntax](crate::field_handler::RegexSyntax) of the database.
+    pub fn re(mut self, criteria: impl Into<SqlArg>) -> Self {
+        self.filter = Some(FieldFilter::Re(criteria.into()));
+        self
+    }
+    /// Filter records with _case insensitive like_ predicate.
+    pub fn ilk(mut self, criteria: impl Into<SqlArg>) -> Self {
+        self.filter = Some(FieldFilter::Ilk(criteria.into()));
+        self
+    }
+    /// Filter records with _not like_ predicate.
+    pub fn nlk(mut self, criteria: impl Into<SqlArg>) -> Self {
+        self.filter = Some(FieldFilter::Nlk(criteria.into()));
+        self
+    }
+    /// Filter records that _start with_ the text. `%` and `_` in the text are matched literally.
+    pub fn sw(mut self, criteria: impl Into<SqlArg>) -> Self {
+        self.filter = Some(FieldFilter::Sw(criteria.into()));
+        self
+    }
+    /// Filter records that _contain_ the text. `%` and `_` in the text are matched literally.
+    pub fn ct(mut self, criteria: impl Into<SqlArg>) -> Self {
+        self.filter = Some(FieldFilter::Ct(criteria.into()));
+        self
+    }
+    /// Filter records that _end with_ the text. `%` and `_` in the text are matched literally.
+    pub fn ew(mut self, criteria: impl Into<SqlArg>) -> Self {
+        self.filter = Some(FieldFilter::Ew(criteria.into()));
+        self
+    }
     /// Filter records with _inside_ predicate.
     pub fn ins<T, I>(mut self, criteria: I) -> Self
     where
@@ -154,6 +227,23 @@ impl Field {
         self
     }
 
+    /// Filter parent records, if at least one merged record matches the filter.
+    /// The field must be a field of a merged entity, e.g. `addresses_city`.
+    pub fn any(mut self) -> Self {
+        self.quantifier = Some(FieldQuantifier::Any);
+        self
+    }
+    /// Filter parent records, if all merged records match the filter.
+    pub fn all(mut self) -> Self {
+        self.quantifier = Some(FieldQuantifier::All);
+        self
+    }
+    /// Filter parent records, if no merged record matches the filter.
+    pub fn none(mut self) -> Self {
+        self.quantifier = Some(FieldQuantifier::None);
+        self
+    }
+
     pub fn concatenate(mut self, concatenation: Concatenation) -> Self {
         self.concatenation = concatenation;
         self
@@ -162,26 +252,97 @@ impl Field {
     pub fn into_name(self) -> String {
         self.name
     }
+
+    /// Rename the field. The name must include the path, e.g. `user_address_street`.
+    pub fn rename<T>(mut self, name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.name = name.into();
+        self
+    }
+    /// Remove the ordering.
+    pub fn unordered(mut self) -> Self {
+        self.order = None;
+        self.nulls = None;
+        self
+    }
+    /// Remove the filter.
+    pub fn unfiltered(mut self) -> Self {
+        self.filter = None;
+        self
+    }
+
+    /// Return the field name, including its path.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Return the concatenation to the preceding token.
+    pub fn concatenation(&self) -> &Concatenation {
+        &self.concatenation
+    }
+    /// Return true, if the field is hidden.
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+    /// Return the ordering, if any.
+    pub fn order(&self) -> Option<&FieldOrder> {
+        self.order.as_ref()
+    }
+    /// Return the placement of null values, if any.
+    pub fn nulls(&self) -> Option<&NullsOrder> {
+        self.nulls.as_ref()
+    }
+    /// Return the filter, if any.
+    pub fn filter(&self) -> Option<&FieldFilter> {
+        self.filter.as_ref()
+    }
+    /// Return the quantifier, if any.
+    pub fn quantifier(&self) -> Option<&FieldQuantifier> {
+        self.quantifier.as_ref()
+    }
+    /// Mark the filter argument at `index` as named placeholder, e.g. `:status`.
+    /// The argument is replaced with the value from [Query::bind](crate::query::Query::bind), when the query is built.
+    pub fn placeholder(mut self, index: usize, name: impl Into<String>) -> Self {
+        self.placeholders.insert(index, name.into());
+        self
+    }
+    /// Return the named placeholders, keyed by the position of the filter argument.
+    pub fn placeholders(&self) -> &BTreeMap<usize, String> {
+        &self.placeholders
+    }
+    /// Compare with another field: the filter argument at `index` is replaced with the
+    /// column or expression of the field `name`, e.g. `done_at gt $due_at`.
+    /// The name must include the path, e.g. `project_deadline`.
+    pub fn field_ref(mut self, index: usize, name: impl Into<String>) -> Self {
+        self.field_refs.insert(index, name.into());
+        self
+    }
+    /// Return the referenced fields, keyed by the position of the filter argument.
+    pub fn field_refs(&self) -> &BTreeMap<usize, String> {
+        &self.field_refs
+    }
 }
 
 impl ToString for Field {
     fn to_string(&self) -> String {
         let mut s = String::new();
-        match self.order {
-            Some(FieldOrder::Asc(o)) => {
-                s.push('+');
-                s.push_str(&o.to_string());
-            }
-            Some(FieldOrder::Desc(o)) => {
-                s.push('-');
-                s.push_str(&o.to_string());
-            }
-            None => {}
-        };
+        if let Some(ref quantifier) = self.quantifier {
+            s.push_str(&quantifier.to_string());
+            s.push(' ');
+        }
+        if let Some(ref order) = self.order {
+            s.push(if order.is_desc() { '-' } else { '+' });
+            s.push_str(&order.ordinal().to_string());
+        }
         if self.hidden {
             s.push('.');
         }
         s.push_str(&self.name);
+        if let Some(nulls) = &self.nulls {
+            s.push(' ');
+            s.push_str(&nulls.to_string());
+        }
 
         if self.filter.is_some() {
             s.push(' ');
@@ -189,7 +350,15 @@ impl ToString for Field {
         match self.filter {
             None => {}
             Some(ref filter) => {
-                s.push_str(filter.to_string().as_str());
+                s.push_str(&filter.to_query_string_with(|i, a| {
+                    if let Some(name) = self.placeholders.get(&i) {
+                        format!(":{}", name)
+                    } else if let Some(name) = self.field_refs.get(&i) {
+                        format!("${}", name)
+                    } else {
+                        a.to_query_string()
+                    }
+                }));
             }
         }
         s
@@ -199,13 +368,28 @@ impl ToString for Field {
 #[cfg(test)]
 mod test {
     use super::Field;
+    use crate::sql_arg::SqlArg;
 
     #[test]
     fn build() {
-        assert_eq!(Field::from("prop").eq(true).to_string(), "prop EQ 1");
+        assert_eq!(Field::from("prop").eq(true).to_string(), "prop EQ true");
         assert_eq!(Field::from("prop").eqn().to_string(), "prop EQN");
         assert_eq!(Field::from("prop").ne(1).to_string(), "prop NE 1");
         assert_eq!(Field::from("prop").nen().to_string(), "prop NEN");
+        assert_eq!(
+            Field::from("prop")
+                .bw(SqlArg::Null, 10)
+                .placeholder(0, "low")
+                .to_string(),
+            "prop BW :low 10"
+        );
+        assert_eq!(
+            Field::from("prop")
+                .gt(SqlArg::Null)
+                .field_ref(0, "level2_prop")
+                .to_string(),
+            "prop GT $level2_prop"
+        );
         assert_eq!(Field::from("prop").gt(1).to_string(), "prop GT 1");
         assert_eq!(Field::from("prop").ge(1.5).to_string(), "prop GE 1.5");
         assert_eq!(Field::from("prop").lt(1.5).to_string(), "prop LT 1.5");
@@ -214,6 +398,12 @@ mod test {
             Field::from("prop").lk("%ABC%").to_string(),
             "prop LK '%ABC%'"
         );
+        assert_eq!(Field::from("prop").re("^A").to_string(), "prop RE '^A'");
+        assert_eq!(Field::from("prop").ilk("a%").to_string(), "prop ILK 'a%'");
+        assert_eq!(Field::from("prop").nlk("a%").to_string(), "prop NLK 'a%'");
+        assert_eq!(Field::from("prop").sw("a").to_string(), "prop SW 'a'");
+        assert_eq!(Field::from("prop").ct("a").to_string(), "prop CT 'a'");
+        assert_eq!(Field::from("prop").ew("a").to_string(), "prop EW 'a'");
         assert_eq!(Field::from("prop").bw(1, 10).to_string(), "prop BW 1 10");
         assert_eq!(
             Field::from("prop").ins(vec![1, 10]).to_string(),
@@ -223,6 +413,18 @@ mod test {
             Field::from("prop").out(vec![1, 10]).to_string(),
             "prop OUT 1 10"
         );
+        assert_eq!(
+            Field::from("items_prop").eq(1).any().to_string(),
+            "ANY items_prop EQ 1"
+        );
+        assert_eq!(
+            Field::from("items_prop").eq(1).hide().all().to_string(),
+            "ALL .items_prop EQ 1"
+        );
+        assert_eq!(
+            Field::from("items_prop").eq(1).none().to_string(),
+            "NONE items_prop EQ 1"
+        );
         assert_eq!(
             Field::from("prop").fnc("SC", vec![1, 10]).to_string(),
             "prop FN SC 1 10"
@@ -231,6 +433,15 @@ mod test {
         assert_eq!(Field::from("prop").asc(1).to_string(), "+1prop");
         assert_eq!(Field::from("prop").desc(3).to_string(), "-3prop");
         assert_eq!(Field::from("prop").hide().to_string(), ".prop");
+        assert_eq!(
+            Field::from("prop").asc(1).nulls_last().to_string(),
+            "+1prop NULLS LAST"
+        );
+        assert_eq!(
+            Field::from("prop").desc(2).nulls_first().eq(1).to_string(),
+            "-2prop NULLS FIRST EQ 1"
+        );
+        assert_eq!(Field::from("prop").nulls_first().to_string(), "prop");
 
         // Combination
         assert_eq!(
@@ -3,6 +3,11 @@
 use crate::sql_arg::SqlArg;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_feature", serde(rename_all = "snake_case"))]
 pub enum FieldFilter {
     Eq(SqlArg),
     Eqn,
@@ -13,90 +18,113 @@ pub enum FieldFilter {
     Lt(SqlArg),
     Le(SqlArg),
     Lk(SqlArg),
+    Re(SqlArg),
+    Ilk(SqlArg),
+    Nlk(SqlArg),
+    Sw(SqlArg),
+    Ct(SqlArg),
+    Ew(SqlArg),
     Bw(SqlArg, SqlArg), // Lower, upper limit
     In(Vec<SqlArg>),
     Out(Vec<SqlArg>),
     Fn(String, Vec<SqlArg>), // Function name, args
 }
 
-impl ToString for FieldFilter {
-    fn to_string(&self) -> String {
-        let mut s = String::new();
+impl FieldFilter {
+    // Returns the filter keyword and its arguments
+    fn parts(&self) -> (String, Vec<&SqlArg>) {
         match self {
-            FieldFilter::Eq(ref arg) => {
-                s.push_str("EQ ");
-                s.push_str(&arg.to_query_string());
-            }
-            FieldFilter::Eqn => {
-                s.push_str("EQN");
-            }
-            FieldFilter::Ne(ref arg) => {
-                s.push_str("NE ");
-                s.push_str(&arg.to_query_string());
-            }
-            FieldFilter::Nen => {
-                s.push_str("NEN");
-            }
-            FieldFilter::Gt(ref arg) => {
-                s.push_str("GT ");
-                s.push_str(&arg.to_query_string());
-            }
-            FieldFilter::Ge(ref arg) => {
-                s.push_str("GE ");
-                s.push_str(&arg.to_query_string());
-            }
-            FieldFilter::Lt(ref arg) => {
-                s.push_str("LT ");
-                s.push_str(&arg.to_query_string());
-            }
-            FieldFilter::Le(ref arg) => {
-                s.push_str("LE ");
-                s.push_str(&arg.to_query_string());
-            }
-            FieldFilter::Lk(ref arg) => {
-                s.push_str("LK ");
-                s.push_str(&arg.to_query_string());
-            }
-            FieldFilter::Bw(ref lower, ref upper) => {
-                s.push_str("BW ");
-                s.push_str(&lower.to_query_string());
-                s.push(' ');
-                s.push_str(&upper.to_query_string());
-            }
-            FieldFilter::In(ref args) => {
-                s.push_str("IN ");
-                s.push_str(
-                    &args
-                        .iter()
-                        .map(|a| a.to_query_string())
-                        .collect::<Vec<_>>()
-                        .join(" "),
-                )
-            }
-            FieldFilter::Out(ref args) => {
-                s.push_str("OUT ");
-                s.push_str(
-                    &args
-                        .iter()
-                        .map(|a| a.to_query_string())
-                        .collect::<Vec<_>>()
-                        .join(" "),
-                )
+            FieldFilter::Eq(arg) => ("EQ".to_string(), vec![arg]),
+            FieldFilter::Eqn => ("EQN".to_string(), vec![]),
+            FieldFilter::Ne(arg) => ("NE".to_string(), vec![arg]),
+            FieldFilter::Nen => ("NEN".to_string(), vec![]),
+            FieldFilter::Gt(arg) => ("GT".to_string(), vec![arg]),
+            FieldFilter::Ge(arg) => ("GE".to_string(), vec![arg]),
+            FieldFilter::Lt(arg) => ("LT".to_string(), vec![arg]),
+            FieldFilter::Le(arg) => ("LE".to_string(), vec![arg]),
+            FieldFilter::Lk(arg) => ("LK".to_string(), vec![arg]),
+            FieldFilter::Re(arg) => ("RE".to_string(), vec![arg]),
+            FieldFilter::Ilk(arg) => ("ILK".to_string(), vec![arg]),
+            FieldFilter::Nlk(arg) => ("NLK".to_string(), vec![arg]),
+            FieldFilter::Sw(arg) => ("SW".to_string(), vec![arg]),
+            FieldFilter::Ct(arg) => ("CT".to_string(), vec![arg]),
+            FieldFilter::Ew(arg) => ("EW".to_string(), vec![arg]),
+            FieldFilter::Bw(lower, upper) => ("BW".to_string(), vec![lower, upper]),
+            FieldFilter::In(args) => ("IN".to_string(), args.iter().collect()),
+            FieldFilter::Out(args) => ("OUT".to_string(), args.iter().collect()),
+            FieldFilter::Fn(name, args) => (format!("FN {}", name), args.iter().collect()),
+        }
+    }
+
+    /// Returns the arguments of the filter in query order.
+    pub(crate) fn args(&self) -> Vec<&SqlArg> {
+        match self {
+            FieldFilter::Eqn | FieldFilter::Nen => vec![],
+            FieldFilter::Eq(arg)
+            | FieldFilter::Ne(arg)
+            | FieldFilter::Gt(arg)
+            | FieldFilter::Ge(arg)
+            | FieldFilter::Lt(arg)
+            | FieldFilter::Le(arg)
+            | FieldFilter::Lk(arg)
+            | FieldFilter::Re(arg)
+            | FieldFilter::Ilk(arg)
+            | FieldFilter::Nlk(arg)
+            | FieldFilter::Sw(arg)
+            | FieldFilter::Ct(arg)
+            | FieldFilter::Ew(arg) => vec![arg],
+            FieldFilter::Bw(lower, upper) => vec![lower, upper],
+            FieldFilter::In(args) | FieldFilter::Out(args) | FieldFilter::Fn(_, args) => {
+                args.iter().collect()
             }
-            FieldFilter::Fn(ref name, ref args) => {
-                s.push_str("FN ");
-                s.push_str(name);
-                s.push(' ');
-                s.push_str(
-                    &args
-                        .iter()
-                        .map(|a| a.to_query_string())
-                        .collect::<Vec<_>>()
-                        .join(" "),
-                )
+        }
+    }
+
+    /// Returns the mutable arguments of the filter in query order.
+    pub(crate) fn args_mut(&mut self) -> Vec<&mut SqlArg> {
+        match self {
+            FieldFilter::Eqn | FieldFilter::Nen => vec![],
+            FieldFilter::Eq(arg)
+            | FieldFilter::Ne(arg)
+            | FieldFilter::Gt(arg)
+            | FieldFilter::Ge(arg)
+            | FieldFilter::Lt(arg)
+            | FieldFilter::Le(arg)
+            | FieldFilter::Lk(arg)
+            | FieldFilter::Re(arg)
+            | FieldFilter::Ilk(arg)
+            | FieldFilter::Nlk(arg)
+            | FieldFilter::Sw(arg)
+            | FieldFilter::Ct(arg)
+            | FieldFilter::Ew(arg) => vec![arg],
+            FieldFilter::Bw(lower, upper) => vec![lower, upper],
+            FieldFilter::In(args) | FieldFilter::Out(args) | FieldFilter::Fn(_, args) => {
+                args.iter_mut().collect()
             }
         }
-        s
+    }
+
+    // Build the query string, the arguments are formatted with the closure
+    pub(crate) fn to_query_string_with<F>(&self, format_arg: F) -> String
+    where
+        F: Fn(usize, &SqlArg) -> String,
+    {
+        let (keyword, args) = self.parts();
+        if let FieldFilter::Eqn | FieldFilter::Nen = self {
+            return keyword;
+        }
+        let args = args
+            .iter()
+            .enumerate()
+            .map(|(i, a)| format_arg(i, a))
+            .collect::<Vec<_>>();
+        format!("{} {}", keyword, args.join(" "))
+    }
+}
+
+impl ToString for FieldFilter {
+    fn to_string(&self) -> String {
+        self.to_query_string_with(|_, a| a.to_query_string())
     }
 }
 
@@ -122,6 +150,30 @@ mod test {
             FieldFilter::Lk(SqlArg::Str("%ABC%".to_string())).to_string(),
             "LK '%ABC%'"
         );
+        assert_eq!(
+            FieldFilter::Re(SqlArg::Str("^A".to_string())).to_string(),
+            "RE '^A'"
+        );
+        assert_eq!(
+            FieldFilter::Ilk(SqlArg::Str("a%".to_string())).to_string(),
+            "ILK 'a%'"
+        );
+        assert_eq!(
+            FieldFilter::Nlk(SqlArg::Str("a%".to_string())).to_string(),
+            "NLK 'a%'"
+        );
+        assert_eq!(
+            FieldFilter::Sw(SqlArg::Str("50%".to_string())).to_string(),
+            "SW '50%'"
+        );
+        assert_eq!(
+            FieldFilter::Ct(SqlArg::Str("a_b".to_string())).to_string(),
+            "CT 'a_b'"
+        );
+        assert_eq!(
+            FieldFilter::Ew(SqlArg::Str("ABC".to_string())).to_string(),
+            "EW 'ABC'"
+        );
         assert_eq!(
             FieldFilter::Bw(SqlArg::U64(1), SqlArg::U64(10)).to_string(),
             "BW 1 10"
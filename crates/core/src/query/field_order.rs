@@ -1,5 +1,49 @@
+use std::fmt;
+
+/// The placement of null values when ordering by a nullable field.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_feature", serde(rename_all = "snake_case"))]
+pub enum NullsOrder {
+    /// Null values come before all other values.
+    First,
+    /// Null values come after all other values.
+    Last,
+}
+
+impl fmt::Display for NullsOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            NullsOrder::First => "NULLS FIRST",
+            NullsOrder::Last => "NULLS LAST",
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
-pub(crate) enum FieldOrder {
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_feature", serde(rename_all = "snake_case"))]
+pub enum FieldOrder {
     Asc(u8),
     Desc(u8),
 }
+
+impl FieldOrder {
+    /// Returns the ordering priority.
+    pub fn ordinal(&self) -> u8 {
+        match self {
+            FieldOrder::Asc(o) | FieldOrder::Desc(o) => *o,
+        }
+    }
+
+    /// Returns true for descending order.
+    pub fn is_desc(&self) -> bool {
+        matches!(self, FieldOrder::Desc(_))
+    }
+}
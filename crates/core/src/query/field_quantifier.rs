@@ -0,0 +1,30 @@
+use std::fmt;
+
+/// The quantifier of a field that filters through a merge.
+///
+/// A quantified field does not narrow the merged entities, instead
+/// it filters the parent entities with an `EXISTS` subquery.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_feature", serde(rename_all = "snake_case"))]
+pub enum FieldQuantifier {
+    /// At least one merged entity matches the filter.
+    Any,
+    /// All merged entities match the filter.
+    All,
+    /// No merged entity matches the filter.
+    None,
+}
+
+impl fmt::Display for FieldQuantifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            FieldQuantifier::Any => "ANY",
+            FieldQuantifier::All => "ALL",
+            FieldQuantifier::None => "NONE",
+        })
+    }
+}
@@ -11,12 +11,22 @@
 /// ```
 use super::concatenation::Concatenation;
 use crate::sql_arg::SqlArg;
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct Predicate {
     pub(crate) concatenation: Concatenation,
     pub(crate) name: String,
     pub(crate) args: Vec<SqlArg>,
+    #[cfg_attr(
+        feature = "serde_feature",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub(crate) placeholders: BTreeMap<usize, String>, // Argument position, parameter name
 }
 
 impl Predicate {
@@ -40,6 +50,7 @@ impl Predicate {
             concatenation: Concatenation::And,
             name,
             args: Vec::new(),
+            placeholders: BTreeMap::new(),
         }
     }
 
@@ -60,6 +71,37 @@ impl Predicate {
         }
         self
     }
+    /// Rename the predicate. The name must include the path, e.g. `user_search`.
+    pub fn rename<T>(mut self, name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.name = name.into();
+        self
+    }
+
+    /// Return the predicate name, including its path.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Return the concatenation to the preceding token.
+    pub fn concatenation(&self) -> &Concatenation {
+        &self.concatenation
+    }
+    /// Return the predicate arguments.
+    pub fn args(&self) -> &[SqlArg] {
+        &self.args
+    }
+    /// Mark the argument at `index` as named placeholder, e.g. `:status`.
+    /// The argument is replaced with the value from [Query::bind](crate::query::Query::bind), when the query is built.
+    pub fn placeholder(mut self, index: usize, name: impl Into<String>) -> Self {
+        self.placeholders.insert(index, name.into());
+        self
+    }
+    /// Return the named placeholders, keyed by the position of the argument.
+    pub fn placeholders(&self) -> &BTreeMap<usize, String> {
+        &self.placeholders
+    }
 }
 
 impl ToString for Predicate {
@@ -70,8 +112,14 @@ impl ToString for Predicate {
         s.push_str(&self.name);
         s.push(' ');
 
-        for a in &self.args {
-            s.push_str(&a.to_string());
+        for (i, a) in self.args.iter().enumerate() {
+            match self.placeholders.get(&i) {
+                Some(name) => {
+                    s.push(':');
+                    s.push_str(name);
+                }
+                None => s.push_str(&a.to_query_string()),
+            }
             s.push(' ');
         }
 
@@ -98,5 +146,12 @@ mod test {
             Predicate::from("pred").are(vec![5, 10]).to_string(),
             "@pred 5 10"
         );
+        assert_eq!(
+            Predicate::from("pred")
+                .are(vec![5, 0])
+                .placeholder(1, "max")
+                .to_string(),
+            "@pred 5 :max"
+        );
     }
 }
@@ -0,0 +1,47 @@
+//! A folder to rewrite a [Query](crate::query::Query).
+//!
+//! Every token is passed by value to the folder and replaced by the returned token.
+//! Returning `None` removes the token. Parentheses and concatenations are kept,
+//! parentheses that become empty are removed.
+//!
+//! ### Example
+//! Rename a legacy field and remove all orderings.
+//! ```rust
+//! use toql_core::query::{Query, field::Field, query_folder::QueryFolder};
+//!
+//! struct Legacy;
+//!
+//! impl QueryFolder for Legacy {
+//!     fn fold_field(&mut self, field: Field) -> Option<Field> {
+//!         let field = field.unordered();
+//!         if field.name() == "user_fullname" {
+//!             Some(field.rename("user_name"))
+//!         } else {
+//!             Some(field)
+//!         }
+//!     }
+//! }
+//!
+//! let q = Query::<()>::from(Field::from("id").asc(1)).or(Field::from("user_fullname").eq("Tom"));
+//! assert_eq!(q.fold(&mut Legacy).to_string(), "id;user_name EQ 'Tom'");
+//! ```
+use super::{field::Field, predicate::Predicate, selection::Selection, wildcard::Wildcard};
+
+pub trait QueryFolder {
+    /// Called for every field. Return `None` to remove the field.
+    fn fold_field(&mut self, field: Field) -> Option<Field> {
+        Some(field)
+    }
+    /// Called for every predicate. Return `None` to remove the predicate.
+    fn fold_predicate(&mut self, predicate: Predicate) -> Option<Predicate> {
+        Some(predicate)
+    }
+    /// Called for every wildcard. Return `None` to remove the wildcard.
+    fn fold_wildcard(&mut self, wildcard: Wildcard) -> Option<Wildcard> {
+        Some(wildcard)
+    }
+    /// Called for every selection. Return `None` to remove the selection.
+    fn fold_selection(&mut self, selection: Selection) -> Option<Selection> {
+        Some(selection)
+    }
+}
@@ -4,6 +4,11 @@ use super::{
 };
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_feature", serde(rename_all = "snake_case"))]
 pub(crate) enum QueryToken {
     LeftBracket(Concatenation),
     RightBracket,
@@ -46,10 +51,16 @@ impl ToString for QueryToken {
         match self {
             QueryToken::RightBracket => String::from(")"),
             QueryToken::LeftBracket(c) => match c {
-                Concatenation::And => String::from("("),
-                Concatenation::Or => String::from("("),
+                Concatenation::And | Concatenation::Or => String::from("("),
+                Concatenation::AndNot | Concatenation::OrNot => String::from("!("),
             },
+            QueryToken::Field(field) if field.concatenation.is_negated() => {
+                format!("!{}", field.to_string())
+            }
             QueryToken::Field(field) => field.to_string(),
+            QueryToken::Predicate(predicate) if predicate.concatenation.is_negated() => {
+                format!("!{}", predicate.to_string())
+            }
             QueryToken::Predicate(predicate) => predicate.to_string(),
             QueryToken::Selection(selection) => format!("${}", &selection.name),
             QueryToken::Wildcard(wildcard) => format!("{}*", wildcard.path),
@@ -66,7 +77,14 @@ mod test {
         assert_eq!(QueryToken::RightBracket.to_string(), ")");
         assert_eq!(QueryToken::LeftBracket(Concatenation::And).to_string(), "(");
         assert_eq!(QueryToken::LeftBracket(Concatenation::Or).to_string(), "(");
+        assert_eq!(
+            QueryToken::LeftBracket(Concatenation::OrNot).to_string(),
+            "!("
+        );
         assert_eq!(QueryToken::Field(Field::from("prop")).to_string(), "prop");
+        let mut field = Field::from("prop").eq(1);
+        field.concatenation = Concatenation::AndNot;
+        assert_eq!(QueryToken::Field(field).to_string(), "!prop EQ 1");
         assert_eq!(
             QueryToken::Predicate(Predicate::from("search")).to_string(),
             "@search"
@@ -0,0 +1,43 @@
+//! A visitor to inspect a [Query](crate::query::Query).
+//!
+//! Implement the methods of interest, all other tokens are ignored.
+//! The visitor is called in query order, parentheses are reported with
+//! [enter_parens](QueryVisitor::enter_parens) and [leave_parens](QueryVisitor::leave_parens).
+//!
+//! ### Example
+//! Collect all field names from a query.
+//! ```rust
+//! use toql_core::query::{Query, field::Field, query_visitor::QueryVisitor};
+//!
+//! struct FieldNames(Vec<String>);
+//!
+//! impl QueryVisitor for FieldNames {
+//!     fn visit_field(&mut self, field: &Field) {
+//!         self.0.push(field.name().to_string());
+//!     }
+//! }
+//!
+//! let q = Query::<()>::from(Field::from("id").eq(5)).or_parentized(Field::from("user_name"));
+//! let mut names = FieldNames(Vec::new());
+//! q.accept(&mut names);
+//! assert_eq!(names.0, vec!["id", "user_name"]);
+//! ```
+use super::{
+    concatenation::Concatenation, field::Field, predicate::Predicate, selection::Selection,
+    wildcard::Wildcard,
+};
+
+pub trait QueryVisitor {
+    /// Called for every field.
+    fn visit_field(&mut self, _field: &Field) {}
+    /// Called for every predicate.
+    fn visit_predicate(&mut self, _predicate: &Predicate) {}
+    /// Called for every wildcard.
+    fn visit_wildcard(&mut self, _wildcard: &Wildcard) {}
+    /// Called for every selection.
+    fn visit_selection(&mut self, _selection: &Selection) {}
+    /// Called for a left parenthesis with the concatenation of the group.
+    fn enter_parens(&mut self, _concatenation: &Concatenation) {}
+    /// Called for a right parenthesis.
+    fn leave_parens(&mut self) {}
+}
@@ -1,6 +1,10 @@
 use super::concatenation::Concatenation;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct Selection {
     pub(crate) concatenation: Concatenation,
     pub(crate) name: String,
@@ -25,6 +29,15 @@ impl Selection {
     pub fn into_string(self) -> String {
         self.name
     }
+
+    /// Return the selection name, including its path.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Return the concatenation to the preceding token.
+    pub fn concatenation(&self) -> &Concatenation {
+        &self.concatenation
+    }
 }
 
 impl ToString for Selection {
@@ -13,6 +13,10 @@
 use super::concatenation::Concatenation;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct Wildcard {
     pub(crate) concatenation: Concatenation,
     pub(crate) path: String,
@@ -64,6 +68,15 @@ impl Wildcard {
     pub fn into_path(self) -> String {
         self.path
     }
+
+    /// Return the wildcard path. A non empty path ends with an underscore, e.g. `user_`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// Return the concatenation to the preceding token.
+    pub fn concatenation(&self) -> &Concatenation {
+        &self.concatenation
+    }
 }
 
 impl Default for Wildcard {
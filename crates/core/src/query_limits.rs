@@ -0,0 +1,175 @@
+//! Guardrails for untrusted queries.
+//!
+//! Toql queries are often sent by web clients. A malicious query such as `*, a_*, a_b_*, a_b_c_*`
+//! or a huge page size can generate expensive joins and merge cascades.
+//! [QueryLimits] are configured on the [Context](crate::backend::context::Context) and enforced by the
+//! [SqlBuilder](crate::sql_builder::SqlBuilder) before any SQL is executed.
+//!
+//! ### Example
+//! ```rust
+//! use toql_core::query_limits::QueryLimits;
+//!
+//! let limits = QueryLimits::new()
+//!     .max_path_depth(3)
+//!     .max_joins(5)
+//!     .max_page_size(100);
+//!
+//! assert!(limits.check_page_size(50).is_ok());
+//! assert!(limits.check_page_size(500).is_err());
+//! ```
+use crate::sql_builder::sql_builder_error::SqlBuilderError;
+
+/// Unlimited query limits, used by backends that do not provide limits.
+pub(crate) static UNLIMITED: QueryLimits = QueryLimits::new();
+
+/// Limits for a Toql query.
+/// All limits are optional, an unset limit is not enforced.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryLimits {
+    pub(crate) max_path_depth: Option<usize>,
+    pub(crate) max_joins: Option<usize>,
+    pub(crate) max_merges: Option<usize>,
+    pub(crate) max_wildcards: Option<usize>,
+    pub(crate) max_filter_args: Option<usize>,
+    pub(crate) max_page_size: Option<u64>,
+}
+
+impl QueryLimits {
+    /// Create new limits. No limit is set.
+    pub const fn new() -> Self {
+        QueryLimits {
+            max_path_depth: None,
+            max_joins: None,
+            max_merges: None,
+            max_wildcards: None,
+            max_filter_args: None,
+            max_page_size: None,
+        }
+    }
+
+    /// Maximum number of path segments in a query field, wildcard, predicate or selection.
+    /// `user_address_country_id` has a depth of 3.
+    pub fn max_path_depth(mut self, depth: usize) -> Self {
+        self.max_path_depth = Some(depth);
+        self
+    }
+    /// Maximum number of joined paths in a single select, count or delete statement.
+    pub fn max_joins(mut self, joins: usize) -> Self {
+        self.max_joins = Some(joins);
+        self
+    }
+    /// Maximum number of merged paths a query may refer to.
+    pub fn max_merges(mut self, merges: usize) -> Self {
+        self.max_merges = Some(merges);
+        self
+    }
+    /// Maximum number of wildcards in a query.
+    pub fn max_wildcards(mut self, wildcards: usize) -> Self {
+        self.max_wildcards = Some(wildcards);
+        self
+    }
+    /// Maximum number of arguments for the `IN`, `OUT` and `FN` filters.
+    pub fn max_filter_args(mut self, args: usize) -> Self {
+        self.max_filter_args = Some(args);
+        self
+    }
+    /// Maximum number of records that can be loaded with a page.
+    pub fn max_page_size(mut self, size: u64) -> Self {
+        self.max_page_size = Some(size);
+        self
+    }
+
+    /// Returns true, if no limit is set.
+    pub fn is_unlimited(&self) -> bool {
+        self == &UNLIMITED
+    }
+
+    /// Fail, if the depth of the query path exceeds the limit.
+    pub fn check_path_depth(&self, query_path: &str) -> Result<(), SqlBuilderError> {
+        let depth = if query_path.is_empty() {
+            0
+        } else {
+            query_path.trim_end_matches('_').split('_').count()
+        };
+        Self::check(
+            "path depth",
+            self.max_path_depth.map(|l| l as u64),
+            depth as u64,
+        )
+    }
+    /// Fail, if the number of joins exceeds the limit.
+    pub fn check_joins(&self, joins: usize) -> Result<(), SqlBuilderError> {
+        Self::check("joins", self.max_joins.map(|l| l as u64), joins as u64)
+    }
+    /// Fail, if the number of merges exceeds the limit.
+    pub fn check_merges(&self, merges: usize) -> Result<(), SqlBuilderError> {
+        Self::check("merges", self.max_merges.map(|l| l as u64), merges as u64)
+    }
+    /// Fail, if the number of wildcards exceeds the limit.
+    pub fn check_wildcards(&self, wildcards: usize) -> Result<(), SqlBuilderError> {
+        Self::check(
+            "wildcards",
+            self.max_wildcards.map(|l| l as u64),
+            wildcards as u64,
+        )
+    }
+    /// Fail, if the number of filter arguments exceeds the limit.
+    pub fn check_filter_args(&self, args: usize) -> Result<(), SqlBuilderError> {
+        Self::check(
+            "filter arguments",
+            self.max_filter_args.map(|l| l as u64),
+            args as u64,
+        )
+    }
+    /// Fail, if the page size exceeds the limit.
+    pub fn check_page_size(&self, size: u64) -> Result<(), SqlBuilderError> {
+        Self::check("page size", self.max_page_size, size)
+    }
+
+    fn check(name: &str, limit: Option<u64>, actual: u64) -> Result<(), SqlBuilderError> {
+        match limit {
+            Some(l) if actual > l => Err(SqlBuilderError::QueryLimitExceeded(
+                name.to_string(),
+                actual,
+                l,
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QueryLimits;
+
+    #[test]
+    fn check() {
+        let l = QueryLimits::new();
+        assert!(l.is_unlimited());
+        assert!(l.check_joins(1000).is_ok());
+
+        let l = QueryLimits::new()
+            .max_path_depth(2)
+            .max_joins(1)
+            .max_merges(1)
+            .max_wildcards(1)
+            .max_filter_args(2)
+            .max_page_size(10);
+        assert!(!l.is_unlimited());
+
+        assert!(l.check_path_depth("").is_ok());
+        assert!(l.check_path_depth("level2_level3").is_ok());
+        assert!(l.check_path_depth("level2_level3_").is_ok());
+        assert!(l.check_path_depth("level2_level3_level4").is_err());
+        assert!(l.check_joins(1).is_ok());
+        assert!(l.check_joins(2).is_err());
+        assert!(l.check_merges(2).is_err());
+        assert!(l.check_wildcards(2).is_err());
+        assert!(l.check_filter_args(3).is_err());
+        assert!(l.check_page_size(10).is_ok());
+        assert_eq!(
+            l.check_page_size(11).err().unwrap().to_string(),
+            "query exceeds limit for page size: 11 > 10"
+        );
+    }
+}
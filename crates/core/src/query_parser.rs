@@ -13,21 +13,40 @@
 //! To build a query within your program, build it programmatically with the provided methods.
 //! This avoids typing mistakes and - unlike parsing - cannot fail.
 //!
+//! Parsing fails with a [QueryError](query_error::QueryError), that contains the position of the problem.
+//! Use [parse_checked](QueryParser::parse_checked) to detect also unknown fields, predicates and selections
+//! together with suggestions for similar names.
+//!
+pub mod query_error;
+
+use crate::backend::context::Context;
+use crate::cache::Cache;
 use crate::error::ToqlError;
+use crate::field_policy::{FieldOperation, FieldPolicy};
 use crate::query::concatenation::Concatenation;
 use crate::query::field::Field;
 use crate::query::field_filter::FieldFilter;
-use crate::query::field_order::FieldOrder;
+use crate::query::field_order::{FieldOrder, NullsOrder};
+use crate::query::field_path::FieldPath;
+use crate::query::field_quantifier::FieldQuantifier;
 use crate::query::predicate::Predicate;
 use crate::query::query_token::QueryToken;
 use crate::query::selection::Selection;
 use crate::query::wildcard::Wildcard;
 use crate::query::Query;
+use crate::role_expr::RoleExpr;
+use crate::role_validator::RoleValidator;
 use crate::sql_arg::SqlArg;
 use crate::sql_builder::sql_builder_error::SqlBuilderError;
+use crate::table_mapper::TableMapper;
+use crate::table_mapper_registry::TableMapperRegistry;
+use crate::tree::tree_map::TreeMap;
+use query_error::{QueryError, QueryErrorKind};
 use toql_query_parser::PestQueryParser;
 
+use chrono::{NaiveDate, NaiveDateTime};
 use pest::Parser;
+use std::collections::{BTreeMap, HashSet};
 
 use toql_query_parser::Rule;
 
@@ -48,7 +67,11 @@ struct TokenInfo {
     args: Vec<SqlArg>,
     hidden: bool,
     order: Option<FieldOrder>,
+    nulls: Option<NullsOrder>,
     filter: Option<String>,
+    quantifier: Option<FieldQuantifier>,
+    placeholders: BTreeMap<usize, String>,
+    field_refs: BTreeMap<usize, String>,
     //aggregation: bool,
     name: String,
     concatenation: Concatenation,
@@ -61,7 +84,11 @@ impl TokenInfo {
             args: Vec::new(),
             hidden: false,
             order: None,
+            nulls: None,
             filter: None,
+            quantifier: None,
+            placeholders: BTreeMap::new(),
+            field_refs: BTreeMap::new(),
             //  aggregation: false,
             name: String::new(),
             concatenation: Concatenation::And,
@@ -73,7 +100,11 @@ impl TokenInfo {
                 name: self.name.to_string(),
                 hidden: self.hidden,
                 order: self.order.clone(),
+                nulls: self.nulls.take(),
                 filter: self.build_filter()?,
+                quantifier: self.quantifier.take(),
+                placeholders: std::mem::take(&mut self.placeholders),
+                field_refs: std::mem::take(&mut self.field_refs),
                 //  aggregation: self.aggregation,
                 concatenation: self.concatenation.clone(),
             }))),
@@ -84,6 +115,7 @@ impl TokenInfo {
             TokenType::Predicate => Ok(Some(QueryToken::Predicate(Predicate {
                 name: self.name.to_string(),
                 args: self.args.drain(..).collect(),
+                placeholders: std::mem::take(&mut self.placeholders),
                 concatenation: self.concatenation.clone(),
             }))),
             TokenType::Selection => {
@@ -132,6 +164,24 @@ impl TokenInfo {
                     "LK" => Ok(Some(FieldFilter::Lk(self.args.pop().ok_or_else(|| {
                         SqlBuilderError::FilterInvalid(filtername.to_string())
                     })?))),
+                    "RE" => Ok(Some(FieldFilter::Re(self.args.pop().ok_or_else(|| {
+                        SqlBuilderError::FilterInvalid(filtername.to_string())
+                    })?))),
+                    "ILK" => Ok(Some(FieldFilter::Ilk(self.args.pop().ok_or_else(
+                        || SqlBuilderError::FilterInvalid(filtername.to_string()),
+                    )?))),
+                    "NLK" => Ok(Some(FieldFilter::Nlk(self.args.pop().ok_or_else(
+                        || SqlBuilderError::FilterInvalid(filtername.to_string()),
+                    )?))),
+                    "SW" => Ok(Some(FieldFilter::Sw(self.args.pop().ok_or_else(|| {
+                        SqlBuilderError::FilterInvalid(filtername.to_string())
+                    })?))),
+                    "CT" => Ok(Some(FieldFilter::Ct(self.args.pop().ok_or_else(|| {
+                        SqlBuilderError::FilterInvalid(filtername.to_string())
+                    })?))),
+                    "EW" => Ok(Some(FieldFilter::Ew(self.args.pop().ok_or_else(|| {
+                        SqlBuilderError::FilterInvalid(filtername.to_string())
+                    })?))),
                     "IN" => Ok(Some(FieldFilter::In(self.args.drain(..).collect()))),
                     "OUT" => Ok(Some(FieldFilter::Out(self.args.drain(..).collect()))),
                     "BW" => {
@@ -161,14 +211,185 @@ impl TokenInfo {
     }
 }
 
+// Roles and field policy to decide, which names are suggested
+struct Access<'a> {
+    registry: &'a TableMapperRegistry,
+    roles: &'a HashSet<String>,
+    field_policy: &'a FieldPolicy,
+}
+
+impl<'a> Access<'a> {
+    fn role_valid(&self, role_expr: &Option<RoleExpr>) -> bool {
+        match role_expr {
+            Some(e) => RoleValidator::is_valid(self.roles, e),
+            None => true,
+        }
+    }
+    // Field can be used for any operation
+    fn field_allowed(&self, query_field: &str) -> bool {
+        FieldOperation::ALL
+            .iter()
+            .any(|o| self.field_policy.is_allowed(query_field, *o))
+    }
+}
+
+// A name in the query string, that must be mapped
+struct QueryName {
+    kind: QueryErrorKind,
+    name: String,
+    start: usize,
+}
+
 impl QueryParser {
     /// Method to parse a string
-    /// This fails if the syntax is wrong. The original PEST error is wrapped with the ToqlError and
-    /// can be used to examine to problem in detail.
+    /// This fails with a [QueryError], if the syntax is wrong.
+    /// The error contains the position, the offending token and the expected alternatives.
     pub fn parse<M>(toql_query: &str) -> Result<Query<M>, ToqlError> {
-        let pairs = PestQueryParser::parse(Rule::query, toql_query)?;
+        Self::parse_with_names(toql_query).map(|(query, _)| query)
+    }
+
+    /// Parse a string and check all fields, paths, predicates and selections against the mapper of `M`.
+    /// Unknown names fail with a [QueryError], that contains suggestions for similar names.
+    /// Only names that the roles and the field policy of the `context` can access are suggested.
+    pub fn parse_checked<M: TreeMap>(
+        toql_query: &str,
+        cache: &Cache,
+        context: &Context,
+    ) -> Result<Query<M>, ToqlError> {
+        let (query, names) = Self::parse_with_names(toql_query)?;
+        if cache.registry.read()?.get(&M::type_name()).is_none() {
+            <M as TreeMap>::map(&mut *cache.registry.write()?)?;
+        }
+
+        let registry = cache.registry.read()?;
+        let root_mapper = registry
+            .get(&M::type_name())
+            .ok_or_else(|| ToqlError::MapperMissing(M::type_name()))?;
+        let access = Access {
+            registry: &registry,
+            roles: &context.roles,
+            field_policy: &context.field_policy,
+        };
+        for name in &names {
+            Self::check_name(&access, root_mapper, name)?;
+        }
+        Ok(query)
+    }
+
+    fn check_name(
+        access: &Access,
+        root_mapper: &TableMapper,
+        query_name: &QueryName,
+    ) -> Result<(), QueryError> {
+        let mut segments = query_name.name.split('_').collect::<Vec<_>>();
+        let basename = if query_name.kind == QueryErrorKind::UnknownPath {
+            None
+        } else {
+            segments.pop()
+        };
+
+        let mut mapper = root_mapper;
+        let mut start = query_name.start;
+        let mut path = FieldPath::default();
+        for segment in segments {
+            let next_mapper = mapper
+                .joined_mapper(segment)
+                .or_else(|| mapper.merged_mapper(segment))
+                .and_then(|m| access.registry.get(&m));
+            mapper = match next_mapper {
+                Some(m) => m,
+                None => {
+                    let paths = mapper
+                        .joins
+                        .iter()
+                        .map(|(n, j)| (n, &j.options.load_role_expr, &j.joined_mapper))
+                        .chain(
+                            mapper
+                                .merges
+                                .iter()
+                                .map(|(n, m)| (n, &m.options.load_role_expr, &m.merged_mapper)),
+                        )
+                        .filter(|(_, role_expr, mapper_name)| {
+                            let mapper_role_valid = match access.registry.get(mapper_name) {
+                                Some(m) => access.role_valid(&m.load_role_expr),
+                                None => false,
+                            };
+                            access.role_valid(role_expr) && mapper_role_valid
+                        })
+                        .map(|(n, _, _)| n);
+                    return Err(QueryError::new(
+                        QueryErrorKind::UnknownPath,
+                        start,
+                        start + segment.len(),
+                        segment,
+                    )
+                    .with_suggestions(paths));
+                }
+            };
+            start += segment.len() + 1;
+            path = path.append(segment);
+        }
+
+        let basename = match basename {
+            Some(b) => b,
+            None => return Ok(()),
+        };
+        let error = QueryError::new(
+            query_name.kind.clone(),
+            start,
+            start + basename.len(),
+            basename,
+        );
+        match query_name.kind {
+            QueryErrorKind::UnknownField if !mapper.fields.contains_key(basename) => {
+                let fields = mapper
+                    .fields
+                    .iter()
+                    .filter(|(n, f)| {
+                        access.role_valid(&f.options.load_role_expr)
+                            && access.field_allowed(path.append(n).as_str())
+                    })
+                    .map(|(n, _)| n);
+                Err(error.with_suggestions(fields))
+            }
+            QueryErrorKind::UnknownPredicate if !mapper.predicates.contains_key(basename) => {
+                let predicates = mapper
+                    .predicates
+                    .iter()
+                    .filter(|(_, p)| access.role_valid(&p.options.load_role_expr))
+                    .map(|(n, _)| n);
+                Err(error.with_suggestions(predicates))
+            }
+            QueryErrorKind::UnknownSelection
+                if !matches!(basename, "std" | "cnt" | "mut" | "all")
+                    && !mapper.selections.contains_key(basename) =>
+            {
+                Err(error.with_suggestions(mapper.selections.keys()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Dates are passed like chrono dates as strings
+    fn parse_date(date: &str) -> Option<SqlArg> {
+        if date.contains('T') {
+            NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%.f")
+                .or_else(|_| NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M"))
+                .ok()
+                .map(SqlArg::from)
+        } else {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()
+                .map(SqlArg::from)
+        }
+    }
+
+    fn parse_with_names<M>(toql_query: &str) -> Result<(Query<M>, Vec<QueryName>), ToqlError> {
+        let pairs = PestQueryParser::parse(Rule::query, toql_query)
+            .map_err(|e| QueryError::from_pest(e, toql_query))?;
 
         let mut query = Query::new();
+        let mut names = Vec::new();
 
         let mut token_info = TokenInfo::new();
 
@@ -189,12 +410,43 @@ impl QueryParser {
                 Rule::hidden => {
                     token_info.hidden = true;
                 }
+                Rule::nulls => {
+                    token_info.nulls = if span.as_str().to_uppercase().ends_with("FIRST") {
+                        Some(NullsOrder::First)
+                    } else {
+                        Some(NullsOrder::Last)
+                    };
+                }
+                Rule::quantifier => {
+                    token_info.quantifier = match span.as_str().to_uppercase().as_str() {
+                        "ANY" => Some(FieldQuantifier::Any),
+                        "ALL" => Some(FieldQuantifier::All),
+                        _ => Some(FieldQuantifier::None),
+                    };
+                }
 
                 Rule::field_path => {
                     token_info.name = span.as_str().to_string();
+                    let kind = match token_info.token_type {
+                        TokenType::Predicate => QueryErrorKind::UnknownPredicate,
+                        TokenType::Selection => QueryErrorKind::UnknownSelection,
+                        _ => QueryErrorKind::UnknownField,
+                    };
+                    names.push(QueryName {
+                        kind,
+                        name: span.as_str().to_string(),
+                        start: span.start(),
+                    });
                 }
                 Rule::wildcard => {
                     token_info.name = span.as_str().trim_end_matches('*').to_string();
+                    if !token_info.name.is_empty() {
+                        names.push(QueryName {
+                            kind: QueryErrorKind::UnknownPath,
+                            name: token_info.name.trim_end_matches('_').to_string(),
+                            start: span.start(),
+                        });
+                    }
                     // Wildcard path must end with underscore
                     if !token_info.name.is_empty() && !token_info.name.ends_with('_') {
                         token_info.name.push('_');
@@ -229,13 +481,49 @@ impl QueryParser {
                     token_info.args.push(SqlArg::from(v));
                 }
                 Rule::string => {
-                    let v = span
-                        .as_str()
-                        .trim_start_matches('\'')
-                        .trim_end_matches('\'')
-                        .replace("''", "'");
+                    let s = span.as_str();
+                    let v = s[1..s.len() - 1].replace("''", "'"); // Remove enclosing quotes
                     token_info.args.push(SqlArg::from(v));
                 }
+                Rule::boolean => {
+                    let v = span.as_str().eq_ignore_ascii_case("true");
+                    token_info.args.push(SqlArg::from(v));
+                }
+                Rule::null => {
+                    token_info.args.push(SqlArg::Null);
+                }
+                Rule::date => {
+                    let v = Self::parse_date(span.as_str()).ok_or_else(|| {
+                        QueryError::new(
+                            QueryErrorKind::Syntax,
+                            span.start(),
+                            span.end(),
+                            span.as_str(),
+                        )
+                        .with_expected(&["valid date"])
+                    })?;
+                    token_info.args.push(v);
+                }
+                Rule::named_placeholder => {
+                    // Null until bound
+                    let name = span.as_str().trim_start_matches(':').to_string();
+                    token_info.placeholders.insert(token_info.args.len(), name);
+                    token_info.args.push(SqlArg::Null);
+                }
+                Rule::field_ref => {
+                    // Replaced with the other field when the SQL is built
+                    let name = span.as_str().trim_start_matches('$').to_string();
+                    names.push(QueryName {
+                        kind: QueryErrorKind::UnknownField,
+                        name: name.to_string(),
+                        start: span.start() + 1,
+                    });
+                    token_info.field_refs.insert(token_info.args.len(), name);
+                    token_info.args.push(SqlArg::Null);
+                }
+                Rule::distinct => {
+                    query.distinct = true;
+                }
                 Rule::predicate_clause => {
                     token_info.token_type = TokenType::Predicate;
                 }
@@ -257,10 +545,15 @@ impl QueryParser {
                     }
                     query.tokens.push(QueryToken::RightBracket);
                 }
+                Rule::negation => {
+                    token_info.concatenation = token_info.concatenation.negate();
+                }
                 Rule::lpar => {
                     query
                         .tokens
                         .push(QueryToken::LeftBracket(token_info.concatenation.clone()));
+                    // Negation belongs to the parentheses
+                    token_info.concatenation = Concatenation::And;
                 }
                 Rule::separator => {
                     let concat_type = span.as_str().chars().next();
@@ -284,13 +577,19 @@ impl QueryParser {
         {
             query.tokens.push(token);
         }
-        Ok(query)
+        Ok((query, names))
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::QueryParser;
+    use super::{
+        Concatenation, Field, FieldFilter, FieldOrder, FieldQuantifier, NullsOrder, Predicate,
+        Query, QueryErrorKind, QueryParser, QueryToken, Selection, Wildcard,
+    };
+    use crate::error::ToqlError;
+    use crate::sql_arg::SqlArg;
+    use std::collections::BTreeMap;
 
     struct User;
 
@@ -309,14 +608,16 @@ mod test {
         );
 
         let q = QueryParser::parse::<User>(
-            "prop9 LK 'ABC', prop10 BW 1 10, prop11 IN 1 2 3, prop12 OUT 1 2 3, \
+            "prop9 LK 'ABC', prop9 RE '^A', prop9 ILK 'a%', prop9 NLK 'a%', \
+            prop9 SW 'a', prop9 CT 'a', prop9 EW 'a', prop10 BW 1 10, prop11 IN 1 2 3, prop12 OUT 1 2 3, \
             prop13 FN CUSTOM 'A' 'B' 2",
         )
         .unwrap();
 
         assert_eq!(
             q.to_string(),
-            "prop9 LK 'ABC',prop10 BW 1 10,prop11 IN 1 2 3,prop12 OUT 1 2 3,\
+            "prop9 LK 'ABC',prop9 RE '^A',prop9 ILK 'a%',prop9 NLK 'a%',\
+            prop9 SW 'a',prop9 CT 'a',prop9 EW 'a',prop10 BW 1 10,prop11 IN 1 2 3,prop12 OUT 1 2 3,\
             prop13 FN CUSTOM 'A' 'B' 2"
         );
     }
@@ -329,6 +630,34 @@ mod test {
         assert_eq!(q.to_string(), "(((prop1 EQ 1,prop2 EQN));prop3 NE 1)");
     }
     #[test]
+    fn parse_negation() {
+        let q = QueryParser::parse::<User>("not (prop1 eq 1; prop2 eqn), !prop3 FN X 1").unwrap();
+        assert_eq!(q.to_string(), "!(prop1 EQ 1;prop2 EQN),!prop3 FN X 1");
+
+        let q = QueryParser::parse::<User>("prop1; NOT @pred 1; !(!prop2 eq 1)").unwrap();
+        assert_eq!(q.to_string(), "prop1;!@pred 1;!(!prop2 EQ 1)");
+
+        let q = QueryParser::parse::<User>("notes eq 1").unwrap();
+        assert_eq!(q.to_string(), "notes EQ 1");
+        let q = QueryParser::parse::<User>("not nen, not eqnx").unwrap();
+        assert_eq!(q.to_string(), "not NEN,!eqnx");
+    }
+    #[test]
+    fn parse_quantifier() {
+        let q = QueryParser::parse::<User>("any items_prop eq 1, ALL .items_prop lk 'a%'").unwrap();
+        assert_eq!(q.to_string(), "ANY items_prop EQ 1,ALL .items_prop LK 'a%'");
+
+        let q = QueryParser::parse::<User>("none items_prop eqn; !any items_prop eq 2").unwrap();
+        assert_eq!(q.to_string(), "NONE items_prop EQN;!ANY items_prop EQ 2");
+
+        // Fields that look like quantifiers
+        let q = QueryParser::parse::<User>("any eq 1, all, nonempty eq 2").unwrap();
+        assert_eq!(q.to_string(), "any EQ 1,all,nonempty EQ 2");
+
+        // Quantifier requires filter
+        assert!(QueryParser::parse::<User>("any items_prop").is_err());
+    }
+    #[test]
     fn parse_predicate() {
         let q = QueryParser::parse::<User>("@level1_pred, @pred").unwrap();
         assert_eq!(q.to_string(), "@level1_pred,@pred");
@@ -348,4 +677,311 @@ mod test {
         let q = QueryParser::parse::<User>("+1level1_a, -2.b").unwrap();
         assert_eq!(q.to_string(), "+1level1_a,-2.b");
     }
+    #[test]
+    fn parse_distinct() {
+        let q = QueryParser::parse::<User>("distinct id, b").unwrap();
+        assert!(q.distinct);
+        assert_eq!(q.to_string(), "DISTINCT id,b");
+
+        // Fields that look like distinct
+        let q = QueryParser::parse::<User>("distinct, distinct eq 1").unwrap();
+        assert!(!q.distinct);
+        assert_eq!(q.to_string(), "distinct,distinct EQ 1");
+        let q = QueryParser::parse::<User>("distinct eqn").unwrap();
+        assert!(!q.distinct);
+        assert_eq!(q.to_string(), "distinct EQN");
+    }
+    #[test]
+    fn parse_named_placeholders() {
+        let q = QueryParser::parse::<User>("a eq :status, b bw 1 :max, @pred :x 2").unwrap();
+        assert_eq!(q.to_string(), "a EQ :status,b BW 1 :max,@pred :x 2");
+
+        assert!(QueryParser::parse::<User>("a eq :").is_err());
+    }
+    #[test]
+    fn parse_field_refs() {
+        let q = QueryParser::parse::<User>("a gt $b, c bw $level1_d 5").unwrap();
+        assert_eq!(q.to_string(), "a GT $b,c BW $level1_d 5");
+
+        assert!(QueryParser::parse::<User>("a eq $").is_err());
+        assert!(QueryParser::parse::<User>("a eq $level1_").is_err());
+    }
+    #[test]
+    fn parse_errors() {
+        let e = match QueryParser::parse::<User>("id xx 5") {
+            Err(ToqlError::QueryError(e)) => e,
+            _ => panic!("expected query error"),
+        };
+        assert_eq!(e.kind(), &QueryErrorKind::Syntax);
+        assert_eq!(e.span(), 3..5);
+        assert_eq!(e.token(), "xx");
+        assert_eq!(e.expected(), ["end of query", "filter", "`,`", "`;`"]);
+        assert_eq!(
+            e.to_string(),
+            "unexpected `xx` at position 3, expected end of query, filter, `,` or `;`"
+        );
+
+        let e = QueryParser::parse::<User>("id, name eq").err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "unexpected end of query at position 11, expected value"
+        );
+
+        let e = QueryParser::parse::<User>("id,, name").err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "unexpected `,` at position 3, expected field, wildcard `*`, \
+            predicate `@name`, selection `$name` or `(`"
+        );
+    }
+    #[test]
+    fn parse_literals() {
+        let q = QueryParser::parse::<User>(
+            "a eq true, b ne FALSE, c eq null, d in 1 -2 2.5 'it''s', \
+            e bw 2021-12-31 2021-12-31T23:59, f lt 2022-01-01T08:30:15.250, @p null true",
+        )
+        .unwrap();
+        assert_eq!(
+            q.to_string(),
+            "a EQ true,b NE false,c EQ null,d IN 1 -2 2.5 'it''s',\
+            e BW '2021-12-31' '2021-12-31 23:59:00',f LT '2022-01-01 08:30:15.250',@p null true"
+        );
+
+        // Keywords are only literals as whole words
+        assert!(QueryParser::parse::<User>("a eq trueish").is_err());
+
+        let e = QueryParser::parse::<User>("a eq 2021-02-30").err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "unexpected `2021-02-30` at position 5, expected valid date"
+        );
+    }
+
+    // Random queries must survive printing and parsing unchanged.
+    // A small xorshift generator keeps the test deterministic and free of dependencies.
+    #[test]
+    fn round_trip() {
+        struct Rng(u64);
+        impl Rng {
+            fn next(&mut self) -> u64 {
+                self.0 ^= self.0 << 13;
+                self.0 ^= self.0 >> 7;
+                self.0 ^= self.0 << 17;
+                self.0
+            }
+            fn below(&mut self, n: usize) -> usize {
+                (self.next() % n as u64) as usize
+            }
+            fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+                items[self.below(items.len())]
+            }
+        }
+
+        const NAMES: &[&str] = &[
+            "id", "name", "level2", "a1", "not", "any", "distinct", "null", "true", "nulls",
+        ];
+
+        fn name(rng: &mut Rng) -> String {
+            (0..=rng.below(3))
+                .map(|_| rng.pick(NAMES))
+                .collect::<Vec<_>>()
+                .join("_")
+        }
+        fn arg(rng: &mut Rng) -> SqlArg {
+            match rng.below(6) {
+                0 => SqlArg::U64(rng.next() >> rng.below(64)),
+                1 => SqlArg::I64(rng.next() as i64 >> rng.below(64)),
+                2 => loop {
+                    let f = f64::from_bits(rng.next());
+                    if f.is_finite() {
+                        break SqlArg::F64(f);
+                    }
+                },
+                3 => SqlArg::Str(
+                    (0..rng.below(8))
+                        .map(|_| rng.pick(&["a", "'", " ", "%", ",", ";", "(", "é", "$", "''"]))
+                        .collect(),
+                ),
+                4 => SqlArg::Bool(rng.below(2) == 0),
+                _ => SqlArg::Null,
+            }
+        }
+        fn args(rng: &mut Rng, count: usize) -> (Vec<SqlArg>, BTreeMap<usize, String>) {
+            let mut placeholders = BTreeMap::new();
+            let args = (0..count)
+                .map(|i| {
+                    if rng.below(8) == 0 {
+                        placeholders.insert(i, name(rng));
+                        SqlArg::Null
+                    } else {
+                        arg(rng)
+                    }
+                })
+                .collect();
+            (args, placeholders)
+        }
+        fn concatenation(rng: &mut Rng, first: bool) -> Concatenation {
+            match (first, rng.below(4)) {
+                (true, 0) | (_, 1) => Concatenation::AndNot,
+                (true, _) | (_, 0) => Concatenation::And,
+                (_, 2) => Concatenation::Or,
+                _ => Concatenation::OrNot,
+            }
+        }
+        fn unnegated(concatenation: Concatenation) -> Concatenation {
+            if concatenation.is_negated() {
+                concatenation.negate()
+            } else {
+                concatenation
+            }
+        }
+        fn field(rng: &mut Rng, concatenation: Concatenation) -> Field {
+            let mut field = Field::from(name(rng).as_str()).concatenate(concatenation);
+            if rng.below(3) == 0 {
+                field.hidden = true;
+            }
+            if rng.below(3) == 0 {
+                let ordinal = rng.below(256) as u8;
+                field.order = Some(if rng.below(2) == 0 {
+                    FieldOrder::Asc(ordinal)
+                } else {
+                    FieldOrder::Desc(ordinal)
+                });
+                if rng.below(3) == 0 {
+                    field.nulls = Some(NullsOrder::First);
+                }
+            }
+            let count = 1 + rng.below(3);
+            let (mut a, placeholders) = args(rng, count);
+            field.filter = match rng.below(8) {
+                0 => None,
+                1 => Some(FieldFilter::Eqn),
+                2 => Some(FieldFilter::Nen),
+                3 => {
+                    let lower = a.remove(0);
+                    let upper = if a.is_empty() { arg(rng) } else { a.remove(0) };
+                    Some(FieldFilter::Bw(lower, upper))
+                }
+                4 => Some(FieldFilter::In(a)),
+                5 => Some(FieldFilter::Out(a)),
+                6 => Some(FieldFilter::Fn("CUSTOM".to_string(), a)),
+                _ => {
+                    let arg = a.remove(0);
+                    Some(match rng.below(13) {
+                        0 => FieldFilter::Eq(arg),
+                        1 => FieldFilter::Ne(arg),
+                        2 => FieldFilter::Gt(arg),
+                        3 => FieldFilter::Ge(arg),
+                        4 => FieldFilter::Lt(arg),
+                        5 => FieldFilter::Le(arg),
+                        6 => FieldFilter::Lk(arg),
+                        7 => FieldFilter::Re(arg),
+                        8 => FieldFilter::Ilk(arg),
+                        9 => FieldFilter::Nlk(arg),
+                        10 => FieldFilter::Sw(arg),
+                        11 => FieldFilter::Ct(arg),
+                        _ => FieldFilter::Ew(arg),
+                    })
+                }
+            };
+            if let Some(filter) = &mut field.filter {
+                let count = filter.args_mut().len().min(count);
+                for (i, name) in placeholders.into_iter().filter(|(i, _)| *i < count) {
+                    // Arguments without value either bind a parameter or refer to another field
+                    if rng.below(2) == 0 {
+                        field.placeholders.insert(i, name);
+                    } else {
+                        field.field_refs.insert(i, name);
+                    }
+                }
+                if rng.below(6) == 0 {
+                    field.quantifier = Some(FieldQuantifier::Any);
+                }
+            }
+            field
+        }
+        fn clauses(rng: &mut Rng, depth: usize, tokens: &mut Vec<QueryToken>) {
+            for i in 0..=rng.below(4) {
+                let concatenation = concatenation(rng, i == 0);
+                let token = match rng.below(if depth < 2 { 6 } else { 5 }) {
+                    0 | 1 => QueryToken::Field(field(rng, concatenation)),
+                    2 => {
+                        let count = rng.below(3);
+                        let (args, placeholders) = args(rng, count);
+                        QueryToken::Predicate(Predicate {
+                            concatenation,
+                            name: name(rng),
+                            args,
+                            placeholders,
+                        })
+                    }
+                    3 => QueryToken::Selection(Selection {
+                        concatenation: unnegated(concatenation),
+                        name: name(rng),
+                    }),
+                    4 => QueryToken::Wildcard(Wildcard {
+                        concatenation: unnegated(concatenation),
+                        path: if rng.below(2) == 0 {
+                            String::new()
+                        } else {
+                            format!("{}_", name(rng))
+                        },
+                    }),
+                    _ => {
+                        tokens.push(QueryToken::LeftBracket(concatenation));
+                        clauses(rng, depth + 1, tokens);
+                        QueryToken::RightBracket
+                    }
+                };
+                tokens.push(token);
+            }
+        }
+
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..2000 {
+            let mut query = Query::<User>::new();
+            query.distinct = rng.below(4) == 0;
+            clauses(&mut rng, 0, &mut query.tokens);
+
+            let query_string = query.to_string();
+            let parsed = QueryParser::parse::<User>(&query_string)
+                .unwrap_or_else(|e| panic!("failed to parse `{}`: {}", query_string, e));
+            // Positive `I64` prints like `U64` and is parsed back as `U64`
+            let mut expected = query.tokens.clone();
+            for token in &mut expected {
+                let args = match token {
+                    QueryToken::Field(field) => match &mut field.filter {
+                        Some(filter) => filter.args_mut(),
+                        None => continue,
+                    },
+                    QueryToken::Predicate(predicate) => predicate.args.iter_mut().collect(),
+                    _ => continue,
+                };
+                for arg in args {
+                    if let SqlArg::I64(v) = *arg {
+                        if v >= 0 {
+                            *arg = SqlArg::U64(v as u64);
+                        }
+                    }
+                }
+            }
+            assert_eq!(
+                format!("{:?}", parsed.tokens),
+                format!("{:?}", expected),
+                "`{}`",
+                query_string
+            );
+            assert_eq!(parsed.distinct, query.distinct);
+            assert_eq!(parsed.to_string(), query_string);
+        }
+    }
+    #[test]
+    fn parse_nulls_order() {
+        let q = QueryParser::parse::<User>("+1a nulls first, -2.b NULLS  LAST eq 5").unwrap();
+        assert_eq!(q.to_string(), "+1a NULLS FIRST,-2.b NULLS LAST EQ 5");
+
+        // Nulls placement requires order
+        assert!(QueryParser::parse::<User>("a nulls first").is_err());
+        assert!(QueryParser::parse::<User>("+1a nulls").is_err());
+    }
 }
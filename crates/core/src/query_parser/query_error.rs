@@ -0,0 +1,292 @@
+//! Errors from [QueryParser](crate::query_parser::QueryParser)
+//!
+//! A [QueryError] describes a problem in a query string with its position, the offending token,
+//! the expected alternatives and suggestions for misspelled names.
+//! It is meant to be shown to the users that typed the query, for example as JSON response.
+use pest::error::{Error as PestError, ErrorVariant, InputLocation};
+use std::fmt;
+use toql_query_parser::Rule;
+
+/// The kind of a [QueryError].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_feature", serde(rename_all = "snake_case"))]
+pub enum QueryErrorKind {
+    /// The query does not follow the query syntax.
+    Syntax,
+    /// The field is not mapped.
+    UnknownField,
+    /// The predicate is not mapped.
+    UnknownPredicate,
+    /// The selection is not mapped.
+    UnknownSelection,
+    /// The path is neither a join nor a merge.
+    UnknownPath,
+}
+
+/// A position-aware error in a query string.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct QueryError {
+    kind: QueryErrorKind,
+    start: usize,
+    end: usize,
+    token: String,
+    expected: Vec<String>,
+    suggestions: Vec<String>,
+}
+
+impl QueryError {
+    pub(crate) fn new(kind: QueryErrorKind, start: usize, end: usize, token: &str) -> Self {
+        QueryError {
+            kind,
+            start,
+            end,
+            token: token.to_string(),
+            expected: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Build the error from a Pest parser error and the parsed query.
+    pub(crate) fn from_pest(error: PestError<Rule>, toql_query: &str) -> Self {
+        let start = match error.location {
+            InputLocation::Pos(p) => p,
+            InputLocation::Span((s, _)) => s,
+        };
+        let token = offending_token(toql_query, start);
+        let mut expected: Vec<String> = Vec::new();
+        if let ErrorVariant::ParsingError { positives, .. } = &error.variant {
+            for rule in positives {
+                // Pest reports only the wildcard at the start of a clause
+                let descriptions = if *rule == Rule::wildcard {
+                    CLAUSE_START
+                } else {
+                    describe_rule(rule)
+                };
+                for description in descriptions {
+                    if !expected.iter().any(|e| e == description) {
+                        expected.push(description.to_string());
+                    }
+                }
+            }
+        }
+        QueryError {
+            kind: QueryErrorKind::Syntax,
+            start,
+            end: start + token.len(),
+            token: token.to_string(),
+            expected,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Set the expected alternatives.
+    pub(crate) fn with_expected(mut self, expected: &[&str]) -> Self {
+        self.expected = expected.iter().map(|e| e.to_string()).collect();
+        self
+    }
+
+    /// Add the names that are similar to the offending token.
+    pub(crate) fn with_suggestions<'a>(mut self, names: impl Iterator<Item = &'a String>) -> Self {
+        self.suggestions = suggestions(&self.token, names);
+        self
+    }
+
+    /// Return the kind of error.
+    pub fn kind(&self) -> &QueryErrorKind {
+        &self.kind
+    }
+    /// Return the byte span of the offending token in the query string.
+    pub fn span(&self) -> std::ops::Range<usize> {
+        self.start..self.end
+    }
+    /// Return the offending token. Empty, if the query ended unexpectedly.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+    /// Return the expected alternatives in plain words.
+    pub fn expected(&self) -> &[String] {
+        &self.expected
+    }
+    /// Return similar names, if the token is an unknown name.
+    pub fn suggestions(&self) -> &[String] {
+        &self.suggestions
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let what = match self.kind {
+            QueryErrorKind::Syntax => "unexpected",
+            QueryErrorKind::UnknownField => "unknown field",
+            QueryErrorKind::UnknownPredicate => "unknown predicate",
+            QueryErrorKind::UnknownSelection => "unknown selection",
+            QueryErrorKind::UnknownPath => "unknown path",
+        };
+        if self.token.is_empty() {
+            write!(f, "{} end of query at position {}", what, self.start)?;
+        } else {
+            write!(f, "{} `{}` at position {}", what, self.token, self.start)?;
+        }
+        if !self.expected.is_empty() {
+            write!(f, ", expected {}", join_alternatives(&self.expected))?;
+        }
+        if !self.suggestions.is_empty() {
+            let suggestions = self
+                .suggestions
+                .iter()
+                .map(|s| format!("`{}`", s))
+                .collect::<Vec<_>>();
+            write!(f, ", did you mean {}?", join_alternatives(&suggestions))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+// The token at the error position reaches until the next delimiter
+fn offending_token(toql_query: &str, start: usize) -> &str {
+    let rest = toql_query.get(start..).unwrap_or_default();
+    let len = rest.find([' ', ',', ';', '(', ')']).unwrap_or(rest.len());
+    match len {
+        0 => rest
+            .get(..rest.chars().next().map_or(0, char::len_utf8))
+            .unwrap_or_default(),
+        n => &rest[..n],
+    }
+}
+
+fn join_alternatives(items: &[String]) -> String {
+    match items.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+const CLAUSE_START: &[&str] = &[
+    "field",
+    "wildcard `*`",
+    "predicate `@name`",
+    "selection `$name`",
+    "`(`",
+];
+
+fn describe_rule(rule: &Rule) -> &'static [&'static str] {
+    match rule {
+        Rule::field | Rule::field_clause | Rule::field_path | Rule::name => &["field"],
+        Rule::wildcard | Rule::wildcard_path => &["wildcard `*`"],
+        Rule::predicate_clause | Rule::predicate_name => &["predicate `@name`"],
+        Rule::selection_clause | Rule::selection_name => &["selection `$name`"],
+        Rule::sort => &["`+`", "`-`"],
+        Rule::hidden => &["`.`"],
+        Rule::nulls => &["`NULLS FIRST`", "`NULLS LAST`"],
+        Rule::quantifier => &["`ANY`", "`ALL`", "`NONE`"],
+        Rule::negation => &["`!`"],
+        Rule::field_filter
+        | Rule::filter0
+        | Rule::filter1
+        | Rule::filter2
+        | Rule::filterx
+        | Rule::filterc
+        | Rule::filter0_name
+        | Rule::filter1_name
+        | Rule::filter2_name
+        | Rule::filterx_name
+        | Rule::filterc_name => &["filter"],
+        Rule::value
+        | Rule::predicate_arg
+        | Rule::num_u64
+        | Rule::num_i64
+        | Rule::num_f64
+        | Rule::string
+        | Rule::boolean
+        | Rule::null
+        | Rule::date
+        | Rule::named_placeholder
+        | Rule::field_ref => &["value"],
+        Rule::separator => &["`,`", "`;`"],
+        Rule::lpar => &["`(`"],
+        Rule::rpar => &["`)`"],
+        Rule::EOI => &["end of query"],
+        _ => &[],
+    }
+}
+
+// Up to three names, that are at most a third of the token different from the token
+fn suggestions<'a>(token: &str, names: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let token = token.to_lowercase();
+    let max_distance = (token.chars().count() / 3).max(1);
+    let mut candidates = names
+        .filter_map(|n| {
+            let distance = edit_distance(&token, &n.to_lowercase());
+            if distance <= max_distance {
+                Some((distance, n))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(3)
+        .map(|(_, n)| n.to_string())
+        .collect()
+}
+
+// Edit distance, where swapping two adjacent characters counts as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut before = vec![0; b.len() + 1];
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        before = std::mem::replace(&mut previous, current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{edit_distance, suggestions};
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("name", "name"), 0);
+        assert_eq!(edit_distance("nmae", "name"), 1);
+        assert_eq!(edit_distance("name", "game"), 1);
+        assert_eq!(edit_distance("nam", "name"), 1);
+        assert_eq!(edit_distance("", "id"), 2);
+    }
+
+    #[test]
+    fn similar_names() {
+        let names = [
+            "name".to_string(),
+            "title".to_string(),
+            "username".to_string(),
+        ];
+        assert_eq!(suggestions("nme", names.iter()), ["name"]);
+        assert_eq!(suggestions("Titel", names.iter()), ["title"]);
+        assert!(suggestions("xyz", names.iter()).is_empty());
+    }
+}
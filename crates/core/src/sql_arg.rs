@@ -9,6 +9,11 @@ pub mod try_into;
 
 /// Enum to keep the different argument types.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde_feature",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_feature", serde(rename_all = "snake_case"))]
 pub enum SqlArg {
     U64(u64),
     I64(i64),
@@ -31,14 +36,24 @@ impl SqlArg {
         }
     }
     /// Build Toql query string.
+    /// The query parser turns the string back into the same argument,
+    /// except for positive `I64`, that becomes `U64`, and infinite or NaN floats.
     pub fn to_query_string(&self) -> String {
         match self {
             SqlArg::U64(t) => t.to_string(),
             SqlArg::I64(t) => t.to_string(),
-            SqlArg::F64(t) => t.to_string(),
-            SqlArg::Str(t) => format!("'{}'", t.to_string()),
-            SqlArg::Bool(t) => format!("{}", (if *t { 1 } else { 0 })),
-            SqlArg::Null => "0".to_string(),
+            SqlArg::F64(t) => {
+                // Keep decimal point to parse as float
+                let s = t.to_string();
+                if s.contains('.') || !t.is_finite() {
+                    s
+                } else {
+                    format!("{}.0", s)
+                }
+            }
+            SqlArg::Str(t) => format!("'{}'", t.replace("'", "''")),
+            SqlArg::Bool(t) => String::from(if *t { "true" } else { "false" }),
+            SqlArg::Null => "null".to_string(),
         }
     }
 
@@ -137,17 +152,17 @@ mod test {
         assert_eq!(a.to_sql_string(), "1");
         assert_eq!(a.to_query_string(), "1");
         assert_eq!(a.cmp_str("1"), false);
-        assert_eq!(1u64, a.try_into().unwrap());
+        assert_eq!(1u64, TryInto::<u64>::try_into(a).unwrap());
 
         let a = SqlArg::from(&1u64);
         let x = a.get_u64().unwrap();
         assert_eq!(x, 1u64);
-        assert_eq!(1u64, a.try_into().unwrap());
+        assert_eq!(1u64, TryInto::<u64>::try_into(a).unwrap());
 
         let a = SqlArg::from(&Some(1u64));
         let x = a.get_u64().unwrap();
         assert_eq!(x, 1u64);
-        assert_eq!(1u64, a.try_into().unwrap());
+        assert_eq!(1u64, TryInto::<u64>::try_into(a).unwrap());
     }
     #[test]
     fn convert_i64() {
@@ -158,17 +173,17 @@ mod test {
         assert_eq!(a.to_sql_string(), "1");
         assert_eq!(a.to_query_string(), "1");
         assert_eq!(a.cmp_str("1"), false);
-        assert_eq!(1i64, a.try_into().unwrap());
+        assert_eq!(1i64, TryInto::<i64>::try_into(a).unwrap());
 
         let a = SqlArg::from(&1i64);
         let x = a.get_i64().unwrap();
         assert_eq!(x, 1i64);
-        assert_eq!(1i64, a.try_into().unwrap());
+        assert_eq!(1i64, TryInto::<i64>::try_into(a).unwrap());
 
         let a = SqlArg::from(&Some(1i64));
         let x = a.get_i64().unwrap();
         assert_eq!(x, 1i64);
-        assert_eq!(1i64, a.try_into().unwrap());
+        assert_eq!(1i64, TryInto::<i64>::try_into(a).unwrap());
     }
     #[test]
     fn convert_f64() {
@@ -177,19 +192,20 @@ mod test {
         assert_eq!(x, 1f64);
         assert_eq!(a.to_string(), "1");
         assert_eq!(a.to_sql_string(), "1");
-        assert_eq!(a.to_query_string(), "1");
+        assert_eq!(a.to_query_string(), "1.0");
+        assert_eq!(SqlArg::from(-0.5f64).to_query_string(), "-0.5");
         assert_eq!(a.cmp_str("1"), false);
-        assert_eq!(1.0f64, a.try_into().unwrap());
+        assert_eq!(1.0f64, TryInto::<f64>::try_into(a).unwrap());
 
         let a = SqlArg::from(&1.0f64);
         let x = a.get_f64().unwrap();
         assert_eq!(x, 1f64);
-        assert_eq!(1.0f64, a.try_into().unwrap());
+        assert_eq!(1.0f64, TryInto::<f64>::try_into(a).unwrap());
 
         let a = SqlArg::from(&Some(1.0f64));
         let x = a.get_f64().unwrap();
         assert_eq!(x, 1f64);
-        assert_eq!(1.0f64, a.try_into().unwrap());
+        assert_eq!(1.0f64, TryInto::<f64>::try_into(a).unwrap());
     }
     #[test]
     fn convert_str() {
@@ -199,6 +215,7 @@ mod test {
         assert_eq!(a.to_string(), "1");
         assert_eq!(a.to_sql_string(), "'1'");
         assert_eq!(a.to_query_string(), "'1'");
+        assert_eq!(SqlArg::from("Tim's").to_query_string(), "'Tim''s'");
         assert_eq!(a.cmp_str("1"), true);
         let s: String = a.try_into().unwrap();
         assert_eq!("1", &s);
@@ -215,28 +232,50 @@ mod test {
         let x = a.get_bool().unwrap();
         assert_eq!(x, true);
         assert_eq!(a.to_string(), "True");
-        assert_eq!(a.to_query_string(), "1");
+        assert_eq!(a.to_query_string(), "true");
         assert_eq!(a.to_sql_string(), "TRUE");
         assert_eq!(a.cmp_str("true"), false);
-        assert_eq!(true, a.try_into().unwrap());
+        assert_eq!(true, TryInto::<bool>::try_into(a).unwrap());
 
         let a = SqlArg::from(false);
         let x = a.get_bool().unwrap();
         assert_eq!(x, false);
         assert_eq!(a.to_string(), "False");
-        assert_eq!(a.to_query_string(), "0");
+        assert_eq!(a.to_query_string(), "false");
         assert_eq!(a.to_sql_string(), "FALSE");
-        assert_eq!(false, a.try_into().unwrap());
+        assert_eq!(false, TryInto::<bool>::try_into(a).unwrap());
 
         let a = SqlArg::from(&true);
         let x = a.get_bool().unwrap();
         assert_eq!(x, true);
-        assert_eq!(true, a.try_into().unwrap());
+        assert_eq!(true, TryInto::<bool>::try_into(a).unwrap());
 
         let a = SqlArg::from(Some(true));
         let x = a.get_bool().unwrap();
         assert_eq!(x, true);
-        assert_eq!(true, a.try_into().unwrap());
+        assert_eq!(true, TryInto::<bool>::try_into(a).unwrap());
+    }
+    #[test]
+    fn convert_date() {
+        use chrono::{NaiveDate, NaiveDateTime};
+
+        let d = NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_opt(13, 5, 0)
+            .unwrap();
+        let a = SqlArg::from(d);
+        assert_eq!(a.to_string(), "2024-02-29 13:05:00");
+        assert_eq!(d, TryInto::<NaiveDateTime>::try_into(&a).unwrap());
+        assert_eq!(
+            Some(d),
+            TryInto::<Option<NaiveDateTime>>::try_into(SqlArg::from("2024-02-29T13:05:00"))
+                .unwrap()
+        );
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            TryInto::<NaiveDate>::try_into(SqlArg::from("2024-02-29")).unwrap()
+        );
+        assert!(TryInto::<NaiveDateTime>::try_into(SqlArg::from(1u64)).is_err());
     }
     #[test]
     fn convert_null() {
@@ -244,7 +283,7 @@ mod test {
         assert_eq!(a.is_null(), true);
         assert_eq!(a.to_string(), "Null");
         assert_eq!(a.to_sql_string(), "NULL");
-        assert_eq!(a.to_query_string(), "0");
+        assert_eq!(a.to_query_string(), "null");
         assert_eq!(a.cmp_str("null"), false);
 
         assert_eq!(a.get_u64().is_none(), true);
@@ -2,6 +2,7 @@
 //! Used to convert SqlArg into basic datatypes.
 use super::error::TryFromSqlArgError;
 use super::SqlArg;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use std::convert::TryInto;
 
 macro_rules! try_into_primitives {
@@ -68,3 +69,44 @@ try_into_primitives!(
     (String, get_str),
     (bool, get_bool)
 );
+
+// Date and time values are strings in a SqlArg.
+// A space between date and time is accepted as well as `T`.
+macro_rules! try_into_dates {
+       ($($type:ty),+) => {
+        $(
+             impl TryInto<$type> for SqlArg {
+                 type Error = TryFromSqlArgError;
+                 fn try_into(self) -> Result<$type, Self::Error> {
+                    (&self).try_into()
+                }
+             }
+             impl TryInto<$type> for &SqlArg {
+                 type Error = TryFromSqlArgError;
+                 fn try_into(self) -> Result<$type, Self::Error> {
+                    self.get_str()
+                        .and_then(|s| s.replacen(' ', "T", 1).parse::<$type>().ok())
+                        .ok_or_else(|| TryFromSqlArgError(self.to_owned()))
+                }
+             }
+             impl TryInto<Option<$type>> for SqlArg {
+                type Error = TryFromSqlArgError;
+                fn try_into(self) -> Result<Option<$type>, Self::Error> {
+                    (&self).try_into()
+                }
+            }
+             impl TryInto<Option<$type>> for &SqlArg {
+                type Error = TryFromSqlArgError;
+                fn try_into(self) -> Result<Option<$type>, Self::Error> {
+                    if self.is_null() {
+                        Ok(None)
+                    } else {
+                        self.try_into().map(Some)
+                    }
+                }
+            }
+        )+
+        };
+    }
+
+try_into_dates!(NaiveDateTime, NaiveDate, NaiveTime);
@@ -8,11 +8,22 @@ pub(crate) mod path_tree;
 
 use crate::{
     error::ToqlError,
+    field_handler::RegexSyntax,
+    field_policy::{FieldOperation, FieldPolicy, OnViolation},
     parameter_map::ParameterMap,
     query::{
-        concatenation::Concatenation, field_order::FieldOrder, field_path::FieldPath,
-        query_token::QueryToken, Query,
+        concatenation::Concatenation,
+        field::Field,
+        field_filter::FieldFilter,
+        field_order::{FieldOrder, NullsOrder},
+        field_path::FieldPath,
+        field_quantifier::FieldQuantifier,
+        predicate::Predicate,
+        query_folder::QueryFolder,
+        query_token::QueryToken,
+        Query,
     },
+    query_limits::QueryLimits,
     result::Result,
     role_validator::RoleValidator,
     sql_arg::SqlArg,
@@ -28,7 +39,7 @@ use path_tree::PathTree;
 use select_stream::Select;
 use std::{
     borrow::Cow,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
 };
 
 enum MapperOrMerge<'a> {
@@ -44,6 +55,11 @@ pub struct SqlBuilder<'a> {
     roles: HashSet<String>,
     aux_params: HashMap<String, SqlArg>, // Aux params used for all queries with this builder instance, contains typically config or auth data
     extra_joins: HashSet<String>,        // Use this joins
+    query_limits: QueryLimits,           // Guardrails for untrusted queries
+    field_policy: FieldPolicy,           // Runtime allow and deny lists for query fields
+    restrictions: HashMap<String, Query<()>>, // Mandatory restrictions for mappers
+    regex_syntax: RegexSyntax,           // Regex syntax of the database
+    stable_order: bool,                  // Order by keys after all query orders
 }
 
 impl<'a> SqlBuilder<'a> {
@@ -56,6 +72,11 @@ impl<'a> SqlBuilder<'a> {
             roles: HashSet::new(),
             aux_params: HashMap::new(),
             extra_joins: HashSet::new(),
+            query_limits: QueryLimits::new(),
+            field_policy: FieldPolicy::new(),
+            restrictions: HashMap::new(),
+            regex_syntax: RegexSyntax::Regexp,
+            stable_order: false,
         }
     }
     /// Use these roles with the builder.
@@ -68,6 +89,33 @@ impl<'a> SqlBuilder<'a> {
         self.aux_params = aux_params;
         self
     }
+    /// Enforce these limits on all queries built with the builder.
+    pub fn with_query_limits(mut self, query_limits: QueryLimits) -> Self {
+        self.query_limits = query_limits;
+        self
+    }
+    /// Consult this field policy in addition to the role restrictions.
+    pub fn with_field_policy(mut self, field_policy: FieldPolicy) -> Self {
+        self.field_policy = field_policy;
+        self
+    }
+    /// Add these mandatory restrictions to every select, count and delete statement.
+    /// The restrictions are keyed by the mapper name.
+    pub fn with_restrictions(mut self, restrictions: HashMap<String, Query<()>>) -> Self {
+        self.restrictions = restrictions;
+        self
+    }
+    /// Use this regex syntax to build the _RE_ filter.
+    pub fn with_regex_syntax(mut self, regex_syntax: RegexSyntax) -> Self {
+        self.regex_syntax = regex_syntax;
+        self
+    }
+    /// Order select statements additionally by the key columns of the entity.
+    /// This makes the order deterministic, if the ordered values tie, and is useful for paging.
+    pub fn with_stable_order(mut self, stable_order: bool) -> Self {
+        self.stable_order = stable_order;
+        self
+    }
     /// Add this raw SQL join statement to the result.
     /// (For internal merge joins)
     pub fn with_extra_join<T: Into<String>>(mut self, join: T) -> Self {
@@ -165,12 +213,29 @@ impl<'a> SqlBuilder<'a> {
         ))
     }
 
+    /// Returns the maximum number of merged entities per parent for the merge path.
+    /// A limit in the query takes precedence over the limit of the mapping.
+    pub fn merge_limit<M>(&self, query_field_path: &str, query: &Query<M>) -> Result<Option<u64>> {
+        if let Some(limit) = query.merge_limits.get(query_field_path) {
+            return Ok(Some(*limit));
+        }
+        let (query_path, basename) = FieldPath::split_basename(query_field_path);
+        let mapper = self.mapper_for_query_path(&query_path)?;
+        let merge = mapper
+            .merge(basename)
+            .ok_or_else(|| SqlBuilderError::MergeMissing(basename.to_string()))?;
+
+        Ok(merge.options.limit)
+    }
+
     /// Build a delete statement from the [Query].
     /// This build a delete filter predicate from the field filters and predicates in the query.
     /// Any field selections are ignored.
     ///
     /// Returns a [BuildResult] that can be turned into SQL.
     pub fn build_delete<M>(&mut self, query: &Query<M>) -> Result<BuildResult> {
+        let bound_query = self.bind_placeholders(query)?;
+        let query = bound_query.as_ref().unwrap_or(query);
         let mut context = BuildContext::new();
         let root_mapper = self
             .table_mapper_registry
@@ -188,6 +253,8 @@ impl<'a> SqlBuilder<'a> {
             }
         }
 
+        self.check_query_limits(query)?;
+
         let mut result = BuildResult::new(SqlExpr::literal("DELETE"));
 
         result.set_from(
@@ -196,6 +263,8 @@ impl<'a> SqlBuilder<'a> {
         );
         self.preparse_filter_joins(&query, &mut context, false)?;
         self.build_where_clause(&query, &mut context, false, &mut result)?;
+        self.build_restriction_clause(&mut context, &mut result)?;
+        self.build_tenant_clause(&mut result)?;
         self.build_join_clause(&query.aux_params, &mut context, &mut result, true, false)?;
 
         Ok(result)
@@ -236,7 +305,7 @@ impl<'a> SqlBuilder<'a> {
         delete_expr.push_literal("DELETE ");
         delete_expr.push_other_alias();
         delete_expr.push_literal(" FROM ");
-        delete_expr.push_literal(&merge_mapper.table_name);
+        delete_expr.push_table(&merge_mapper.table_name);
         delete_expr.push_literal(" ");
         delete_expr.push_other_alias();
         delete_expr.push_literal(" ");
@@ -262,6 +331,8 @@ impl<'a> SqlBuilder<'a> {
         query_home_path: &str,
         query: &Query<M>,
     ) -> Result<BuildResult> {
+        let bound_query = self.bind_placeholders(query)?;
+        let query = bound_query.as_ref().unwrap_or(query);
         let mut context = BuildContext::new();
         context.query_home_path = query_home_path.to_string();
 
@@ -286,16 +357,31 @@ impl<'a> SqlBuilder<'a> {
             }
         }
 
+        self.check_query_limits(query)?;
+
         let mut result = BuildResult::new(SqlExpr::literal("SELECT"));
         result.set_from(
             mapper.table_name.to_owned(),
             mapper.canonical_table_alias.to_owned(),
         );
+        if query.distinct {
+            result.distinct = true;
+            result.set_modifier("DISTINCT".to_string());
+        }
 
         self.preparse_query(&query, &mut context, &mut result)?;
         self.build_where_clause(&query, &mut context, false, &mut result)?;
+        self.build_restriction_clause(&mut context, &mut result)?;
+        self.build_tenant_clause(&mut result)?;
         self.build_select_clause(&query, &mut context, &mut result)?;
+        if self.stable_order {
+            let mapper = self.root_mapper()?;
+            self.preset_key_order(mapper, &FieldPath::default(), &mut context)?;
+        }
         self.build_join_clause(&query.aux_params, &mut context, &mut result, false, true)?;
+        if !query_home_path.is_empty() && context.ordering.is_empty() {
+            self.preset_merge_order(query_home_path, &mut context)?;
+        }
         self.build_order_clause(&query.aux_params, &mut context, &mut result)?;
 
         Ok(result)
@@ -313,9 +399,15 @@ impl<'a> SqlBuilder<'a> {
         query: &Query<M>,
         count_selection_only: bool,
     ) -> Result<BuildResult> {
+        let bound_query = self.bind_placeholders(query)?;
+        let query = bound_query.as_ref().unwrap_or(query);
+        if query.distinct {
+            return self.build_distinct_count(query_root_path, query, count_selection_only);
+        }
         let mut build_context = BuildContext::new();
         build_context.query_home_path = query_root_path.to_string();
         let root_mapper = self.root_mapper()?; // self.joined_mapper_for_path(&Self::root_field_path(root_path))?;
+        self.check_query_limits(query)?;
 
         let mut result = BuildResult::new(SqlExpr::literal("SELECT"));
         result.select_expr.push_literal("COUNT(*)");
@@ -333,6 +425,8 @@ impl<'a> SqlBuilder<'a> {
         )?;
 
         self.preparse_filter_joins(&query, &mut build_context, count_selection_only)?;
+        self.build_restriction_clause(&mut build_context, &mut result)?;
+        self.build_tenant_clause(&mut result)?;
 
         self.build_join_clause(
             &query.aux_params,
@@ -345,6 +439,53 @@ impl<'a> SqlBuilder<'a> {
         Ok(result)
     }
 
+    // Distinct rows depend on the selected columns, so the distinct select
+    // is counted as derived table.
+    fn build_distinct_count<M>(
+        &mut self,
+        query_root_path: &str,
+        query: &Query<M>,
+        count_selection_only: bool,
+    ) -> Result<BuildResult> {
+        let mut build_context = BuildContext::new();
+        build_context.query_home_path = query_root_path.to_string();
+        build_context.column_aliases = Some(0);
+        let root_mapper = self.root_mapper()?;
+        self.check_query_limits(query)?;
+
+        let mut distinct = BuildResult::new(SqlExpr::literal("SELECT"));
+        distinct.set_from(
+            root_mapper.table_name.to_owned(),
+            root_mapper.canonical_table_alias.to_owned(),
+        );
+        distinct.set_modifier("DISTINCT".to_string());
+
+        self.preparse_query(query, &mut build_context, &mut distinct)?;
+        self.build_where_clause(
+            query,
+            &mut build_context,
+            count_selection_only,
+            &mut distinct,
+        )?;
+        self.build_restriction_clause(&mut build_context, &mut distinct)?;
+        self.build_tenant_clause(&mut distinct)?;
+        self.build_select_clause(query, &mut build_context, &mut distinct)?;
+        self.build_join_clause(
+            &query.aux_params,
+            &mut build_context,
+            &mut distinct,
+            false,
+            true,
+        )?;
+
+        let mut result = BuildResult::new(SqlExpr::literal("SELECT"));
+        result.select_expr.push_literal("COUNT(*)");
+        result.from_expr.push_literal("(");
+        result.from_expr.extend(distinct.to_sql_expr());
+        result.from_expr.push_literal(") toql_distinct");
+        Ok(result)
+    }
+
     pub fn joined_mapper_for_local_path(&self, local_path: &FieldPath) -> Result<&TableMapper> {
         self.joined_mapper_for_path(&self.home_mapper, local_path)
     }
@@ -481,6 +622,8 @@ impl<'a> SqlBuilder<'a> {
         for local_path in &build_context.local_joined_paths {
             join_tree.insert(&FieldPath::from(&local_path));
         }
+        self.query_limits
+            .check_joins(build_context.local_joined_paths.len())?;
 
         // Build join
         let expr: SqlExpr = self.resolve_join(
@@ -606,6 +749,18 @@ impl<'a> SqlBuilder<'a> {
             };
 
             join_expr.extend(on_expr);
+
+            // Joined rows must belong to the tenant too
+            let joined_mapper = self
+                .table_mapper_registry
+                .get(&join.joined_mapper)
+                .ok_or_else(|| ToqlError::MapperMissing(join.joined_mapper.to_string()))?;
+            if let Some(tenant_expr) =
+                self.tenant_predicate(joined_mapper, &canonical_other_alias)?
+            {
+                join_expr.push_literal(" AND ");
+                join_expr.extend(tenant_expr);
+            }
             join_expr.push_literal(") ");
         }
 
@@ -658,6 +813,20 @@ impl<'a> SqlBuilder<'a> {
                         None => return Ok(()),
                     };
 
+                    // Quantified fields filter with a subquery on the merged entities
+                    if field.quantifier.is_some() {
+                        if let Some(expr) = self.build_quantified_filter(
+                            query,
+                            build_context,
+                            field,
+                            &query_path,
+                            &local_path,
+                        )? {
+                            Self::push_filter(&mut result.where_expr, &field.concatenation, expr);
+                        }
+                        continue;
+                    }
+
                     let mapper_or_merge = self.mapper_or_merge_for_path(&local_path)?;
 
                     match mapper_or_merge {
@@ -666,11 +835,19 @@ impl<'a> SqlBuilder<'a> {
                                 SqlBuilderError::FieldMissing(field.name.to_string())
                             })?;
 
+                            if !build_context.restriction
+                                && !self.policy_allows(&field.name, FieldOperation::Filter)?
+                            {
+                                continue;
+                            }
+
                             if let Some(role_expr) = &mapped_field.options.load_role_expr {
-                                if !crate::role_validator::RoleValidator::is_valid(
-                                    &self.roles,
-                                    role_expr,
-                                ) {
+                                if !build_context.restriction
+                                    && !crate::role_validator::RoleValidator::is_valid(
+                                        &self.roles,
+                                        role_expr,
+                                    )
+                                {
                                     return Err(SqlBuilderError::RoleRequired(
                                         role_expr.to_string(),
                                         format!(
@@ -691,7 +868,8 @@ impl<'a> SqlBuilder<'a> {
                                 &query.aux_params,
                                 &mapped_field.options.aux_params,
                             ];
-                            let aux_params = ParameterMap::new(&p);
+                            let aux_params =
+                                ParameterMap::new(&p).with_regex_syntax(&self.regex_syntax);
 
                             let handler = mapped_field
                                 .options
@@ -699,29 +877,42 @@ impl<'a> SqlBuilder<'a> {
                                 .as_ref()
                                 .unwrap_or(&mapper.field_handler);
                             let select_expr = handler
-                                .build_select(mapped_field.expression.clone(), &aux_params)?
+                                .build_select(self.field_expression(mapped_field)?, &aux_params)?
                                 .unwrap_or_default();
 
+                            // Referenced fields replace their filter arguments after the handler
+                            let mut ref_exprs = BTreeMap::new();
+                            for (i, name) in &field.field_refs {
+                                if let Some(ref_expr) =
+                                    self.field_ref_expr(query, build_context, name)?
+                                {
+                                    ref_exprs.insert(*i, ref_expr);
+                                }
+                            }
+                            // Skip filter, if a referenced field is dropped by the field policy
+                            if ref_exprs.len() < field.field_refs.len() {
+                                continue;
+                            }
+                            let filter = field.filter.as_ref().unwrap();
+
                             // Does filter apply
-                            if let Some(expr) = handler.build_filter(
-                                select_expr,
-                                field.filter.as_ref().unwrap(),
-                                &aux_params,
-                            )? {
+                            if let Some(expr) =
+                                handler.build_filter(select_expr, filter, &aux_params)?
+                            {
                                 let resolver = Resolver::new().with_self_alias(&canonical_alias);
-                                let expr = resolver.resolve(&expr)?;
-                                if !result.where_expr.is_empty()
-                                    && !result.where_expr.ends_with_literal("(")
+                                let mut expr = resolver.resolve(&expr)?;
+                                if !ref_exprs.is_empty()
+                                    && !expr.replace_filter_args(&filter.args(), &ref_exprs)
                                 {
-                                    result.where_expr.push_literal(
-                                        if field.concatenation == Concatenation::And {
-                                            " AND "
-                                        } else {
-                                            " OR "
-                                        },
+                                    return Err(
+                                        SqlBuilderError::FilterInvalid(field.to_string()).into()
                                     );
                                 }
-                                result.where_expr.extend(expr);
+                                Self::push_filter(
+                                    &mut result.where_expr,
+                                    &field.concatenation,
+                                    expr,
+                                );
                             }
                         }
                         MapperOrMerge::Merge(_merge_path) => {
@@ -758,7 +949,9 @@ impl<'a> SqlBuilder<'a> {
                             }
 
                             if let Some(role) = &mapped_predicate.options.load_role_expr {
-                                if !RoleValidator::is_valid(&self.roles, role) {
+                                if !build_context.restriction
+                                    && !RoleValidator::is_valid(&self.roles, role)
+                                {
                                     return Err(SqlBuilderError::RoleRequired(
                                         role.to_string(),
                                         format!(
@@ -791,18 +984,11 @@ impl<'a> SqlBuilder<'a> {
                                 &predicate.args,
                                 &aux_params,
                             )? {
-                                if !result.where_expr.is_empty()
-                                    && !result.where_expr.ends_with_literal("(")
-                                {
-                                    result.where_expr.push_literal(
-                                        if predicate.concatenation == Concatenation::And {
-                                            " AND "
-                                        } else {
-                                            " OR "
-                                        },
-                                    );
-                                }
-                                result.where_expr.extend(resolver.resolve(&expr)?);
+                                Self::push_filter(
+                                    &mut result.where_expr,
+                                    &predicate.concatenation,
+                                    resolver.resolve(&expr)?,
+                                );
                                 if !mapped_predicate.options.on_aux_params.is_empty() {
                                     for (i, a) in &mapped_predicate.options.on_aux_params {
                                         if let Some(v) = predicate.args.get(*i as usize) {
@@ -821,17 +1007,15 @@ impl<'a> SqlBuilder<'a> {
                     }
                 }
                 QueryToken::LeftBracket(concatenation) => {
-                    // Omit concatenation if where expression is empty or left bracket follows an outer left bracket
-                    if !result.where_expr.is_empty() && !result.where_expr.ends_with_literal("(") {
-                        result
-                            .where_expr
-                            .push_literal(if concatenation == &Concatenation::And {
-                                " AND "
-                            } else {
-                                " OR "
-                            });
-                    }
-                    result.where_expr.push_literal("(");
+                    Self::push_concatenation(&mut result.where_expr, concatenation);
+                    // Single token, so that empty parentheses can be removed together with NOT
+                    result
+                        .where_expr
+                        .push_literal(if concatenation.is_negated() {
+                            "NOT ("
+                        } else {
+                            "("
+                        });
                 }
                 QueryToken::RightBracket => {
                     // If parentheses are empty, remove right bracket and concatenation
@@ -856,6 +1040,262 @@ impl<'a> SqlBuilder<'a> {
         Ok(())
     }
 
+    // Build EXISTS subquery for a quantified field of a merged entity.
+    // Returns None, if the field is skipped.
+    fn build_quantified_filter<M>(
+        &self,
+        query: &Query<M>,
+        build_context: &BuildContext,
+        field: &Field,
+        query_path: &FieldPath,
+        local_path: &FieldPath,
+    ) -> Result<Option<SqlExpr>> {
+        let (quantifier, filter) = match (&field.quantifier, &field.filter) {
+            (Some(q), Some(f)) => (q, f),
+            _ => return Ok(None),
+        };
+        // Merged entities cannot be compared with other fields
+        if !field.field_refs.is_empty() {
+            return Err(SqlBuilderError::FilterInvalid(field.to_string()).into());
+        }
+
+        // Skip field when loading the merge itself
+        if local_path.is_empty() && !build_context.query_home_path.is_empty() {
+            return Ok(None);
+        }
+        // Only fields of merged entities can be quantified, no joins or nested merges
+        if self.next_merge_path(local_path)?.as_deref() != Some(local_path.as_str()) {
+            return Err(SqlBuilderError::FilterInvalid(field.name.to_string()).into());
+        }
+        if !build_context.restriction && !self.policy_allows(&field.name, FieldOperation::Filter)? {
+            return Ok(None);
+        }
+
+        let (parent_query_path, merge_name) = FieldPath::split_basename(query_path.as_str());
+        let parent_mapper = self.mapper_for_query_path(&parent_query_path)?;
+        let merge = parent_mapper
+            .merge(merge_name)
+            .ok_or_else(|| SqlBuilderError::MergeMissing(merge_name.to_string()))?;
+        let merged_mapper = self.mapper_for_query_path(query_path)?;
+        let (_, field_name) = FieldPath::split_basename(&field.name);
+        let mapped_field = merged_mapper
+            .fields
+            .get(field_name)
+            .ok_or_else(|| SqlBuilderError::FieldMissing(field.name.to_string()))?;
+
+        if !build_context.restriction {
+            if let Some(role_expr) = &merge.options.load_role_expr {
+                if !RoleValidator::is_valid(&self.roles, role_expr) {
+                    return Err(SqlBuilderError::RoleRequired(
+                        role_expr.to_string(),
+                        format!("path `{}`", query_path.to_string()),
+                    )
+                    .into());
+                }
+            }
+            if let Some(role_expr) = &mapped_field.options.load_role_expr {
+                if !RoleValidator::is_valid(&self.roles, role_expr) {
+                    return Err(SqlBuilderError::RoleRequired(
+                        role_expr.to_string(),
+                        format!("field `{}`", &field.name),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        let p = [
+            &self.aux_params,
+            &query.aux_params,
+            &mapped_field.options.aux_params,
+        ];
+        let aux_params = ParameterMap::new(&p).with_regex_syntax(&self.regex_syntax);
+        let handler = mapped_field
+            .options
+            .field_handler
+            .as_ref()
+            .unwrap_or(&merged_mapper.field_handler);
+        let select_expr = handler
+            .build_select(self.field_expression(mapped_field)?, &aux_params)?
+            .unwrap_or_default();
+        let filter_expr = match handler.build_filter(select_expr, filter, &aux_params)? {
+            Some(e) => e,
+            None => return Ok(None),
+        };
+
+        // The parent table is already in the outer query, so only a custom join is kept.
+        let (_, merge_predicate) = self.merge_expr(query_path.as_str())?;
+        let custom_join = merge
+            .custom_join(&parent_mapper.table_name)
+            .ok_or_else(|| SqlBuilderError::FilterInvalid(field.name.to_string()))?;
+
+        let parent_path = FieldPath::trim_basename(local_path.as_str());
+        let parent_alias = self.canonical_alias(&parent_path)?;
+        let merged_alias = self.canonical_alias(local_path)?;
+
+        let mut expr = SqlExpr::literal(match quantifier {
+            FieldQuantifier::Any => "EXISTS (SELECT 1 FROM ",
+            FieldQuantifier::All | FieldQuantifier::None => "NOT EXISTS (SELECT 1 FROM ",
+        });
+        expr.push_table(&merged_mapper.table_name)
+            .push_literal(" ")
+            .push_other_alias()
+            .push_literal(" ");
+        expr.extend(custom_join);
+        expr.push_literal("WHERE ");
+        expr.extend(merge_predicate);
+        if let Some(tenant_expr) = self.tenant_predicate(merged_mapper, &merged_alias)? {
+            expr.push_literal(" AND ");
+            expr.extend(tenant_expr);
+        }
+        let filter_expr = Resolver::new()
+            .with_self_alias(&merged_alias)
+            .resolve(&filter_expr)?;
+        match quantifier {
+            // A filter that evaluates to NULL does not satisfy ALL
+            FieldQuantifier::All => {
+                expr.push_literal(" AND (NOT (");
+                expr.extend(filter_expr.clone());
+                expr.push_literal(") OR (");
+                expr.extend(filter_expr);
+                expr.push_literal(") IS NULL))");
+            }
+            FieldQuantifier::Any | FieldQuantifier::None => {
+                expr.push_literal(" AND ");
+                expr.extend(filter_expr);
+                expr.push_literal(")");
+            }
+        }
+
+        let resolver = Resolver::new()
+            .with_self_alias(&parent_alias)
+            .with_other_alias(&merged_alias);
+        Ok(Some(resolver.resolve(&expr)?))
+    }
+
+    // Build the aliased expression of a field, that is compared in a filter.
+    // The field must be filterable for the user roles and the field policy, like the filtered field.
+    // Returns None, if the field policy drops the field.
+    fn field_ref_expr<M>(
+        &self,
+        query: &Query<M>,
+        build_context: &BuildContext,
+        name: &str,
+    ) -> Result<Option<SqlExpr>> {
+        let (query_path, field_name) = FieldPath::split_basename(name);
+        let local_path = query_path
+            .localize_path(&build_context.query_home_path)
+            .ok_or_else(|| SqlBuilderError::FieldMissing(name.to_string()))?;
+        let mapper = match self.mapper_or_merge_for_path(&local_path)? {
+            MapperOrMerge::Mapper(mapper) => mapper,
+            MapperOrMerge::Merge(_) => {
+                return Err(SqlBuilderError::FilterInvalid(format!("${}", name)).into())
+            }
+        };
+        let mapped_field = mapper
+            .fields
+            .get(field_name)
+            .ok_or_else(|| SqlBuilderError::FieldMissing(name.to_string()))?;
+
+        if !build_context.restriction {
+            if !self.policy_allows(name, FieldOperation::Filter)? {
+                return Ok(None);
+            }
+            self.check_join_path_roles(build_context, &local_path)?;
+            if let Some(role_expr) = &mapped_field.options.load_role_expr {
+                if !RoleValidator::is_valid(&self.roles, role_expr) {
+                    return Err(SqlBuilderError::RoleRequired(
+                        role_expr.to_string(),
+                        format!("field `{}`", name),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        let p = [
+            &self.aux_params,
+            &query.aux_params,
+            &mapped_field.options.aux_params,
+        ];
+        let aux_params = ParameterMap::new(&p);
+        let handler = mapped_field
+            .options
+            .field_handler
+            .as_ref()
+            .unwrap_or(&mapper.field_handler);
+        let select_expr = handler
+            .build_select(self.field_expression(mapped_field)?, &aux_params)?
+            .ok_or_else(|| SqlBuilderError::FilterInvalid(format!("${}", name)))?;
+
+        let canonical_alias = self.canonical_alias(&query_path)?;
+        Ok(Some(
+            Resolver::new()
+                .with_self_alias(&canonical_alias)
+                .resolve(&select_expr)?,
+        ))
+    }
+
+    // Check the roles of all joins and joined mappers along the local path.
+    fn check_join_path_roles(
+        &self,
+        build_context: &BuildContext,
+        local_path: &FieldPath,
+    ) -> Result<()> {
+        let mut mapper = self.joined_mapper_for_local_path(&FieldPath::default())?;
+        for (join_name, join_path) in local_path.children().zip(local_path.step_down()) {
+            let join = mapper.joins.get(join_name.as_str()).ok_or_else(|| {
+                SqlBuilderError::JoinMissing(join_name.to_string(), mapper.table_name.to_string())
+            })?;
+            mapper = self
+                .table_mapper_registry
+                .get(&join.joined_mapper)
+                .ok_or_else(|| ToqlError::MapperMissing(join.joined_mapper.to_string()))?;
+            let role_exprs = join
+                .options
+                .load_role_expr
+                .iter()
+                .chain(mapper.load_role_expr.iter());
+            for role_expr in role_exprs {
+                if !RoleValidator::is_valid(&self.roles, role_expr) {
+                    return Err(SqlBuilderError::RoleRequired(
+                        role_expr.to_string(),
+                        format!(
+                            "path `{}`",
+                            FieldPath::from(&build_context.query_home_path)
+                                .append(join_path.as_str())
+                                .to_string()
+                        ),
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Omit concatenation if where expression is empty or expression follows a left bracket
+    fn push_concatenation(where_expr: &mut SqlExpr, concatenation: &Concatenation) {
+        if !where_expr.is_empty() && !where_expr.ends_with_literal("(") {
+            where_expr.push_literal(if concatenation.is_and() {
+                " AND "
+            } else {
+                " OR "
+            });
+        }
+    }
+
+    fn push_filter(where_expr: &mut SqlExpr, concatenation: &Concatenation, expr: SqlExpr) {
+        Self::push_concatenation(where_expr, concatenation);
+        if concatenation.is_negated() {
+            where_expr.push_literal("NOT (");
+            where_expr.extend(expr);
+            where_expr.push_literal(")");
+        } else {
+            where_expr.extend(expr);
+        }
+    }
+
     fn canonical_alias<'c>(&'c self, query_path: &'c FieldPath) -> Result<Cow<String>> {
         let root_alias = &self.root_mapper()?.canonical_table_alias;
 
@@ -889,73 +1329,98 @@ impl<'a> SqlBuilder<'a> {
         }
         ordinals.sort();
 
-        for n in ordinals {
-            if let Some(orderings) = build_context.ordering.get(n) {
-                for (ord, local_path_with_basename) in orderings {
-                    let (local_path, field_name) =
-                        FieldPath::split_basename(local_path_with_basename);
-                    // Skip merge fields
-                    if let Ok(mapper) = self.joined_mapper_for_local_path(&local_path) {
-                        if let Some(role) = &mapper.load_role_expr {
-                            if !RoleValidator::is_valid(&self.roles, role) {
-                                return Err(SqlBuilderError::RoleRequired(
-                                    role.to_string(),
-                                    format!(
-                                        "field `{}`",
-                                        FieldPath::from(&build_context.query_home_path)
-                                            .append(&local_path)
-                                            .to_string(),
-                                    ),
-                                )
-                                .into());
-                            }
-                        }
+        // Keys order last and only, if they are not ordered by the query
+        let key_order = FieldOrder::Asc(u8::MAX);
+        let mut orderings: Vec<(&FieldOrder, Option<&NullsOrder>, &String, bool)> = ordinals
+            .iter()
+            .filter_map(|n| build_context.ordering.get(n))
+            .flatten()
+            .map(|(o, n, p)| (o, n.as_ref(), p, false))
+            .collect();
+        for key_path in &build_context.key_ordering {
+            if !orderings.iter().any(|(_, _, p, _)| *p == key_path) {
+                orderings.push((&key_order, None, key_path, true));
+            }
+        }
 
-                        let field_info = mapper
-                            .field(field_name)
-                            .ok_or_else(|| SqlBuilderError::FieldMissing(field_name.to_string()))?;
+        for (ord, nulls, local_path_with_basename, key) in orderings {
+            let (local_path, field_name) = FieldPath::split_basename(local_path_with_basename);
+            // Skip merge fields
+            if let Ok(mapper) = self.joined_mapper_for_local_path(&local_path) {
+                if let Some(role) = &mapper.load_role_expr {
+                    if !RoleValidator::is_valid(&self.roles, role) {
+                        return Err(SqlBuilderError::RoleRequired(
+                            role.to_string(),
+                            format!(
+                                "field `{}`",
+                                FieldPath::from(&build_context.query_home_path)
+                                    .append(&local_path)
+                                    .to_string(),
+                            ),
+                        )
+                        .into());
+                    }
+                }
 
-                        if let Some(load_role_expr) = &field_info.options.load_role_expr {
-                            if !RoleValidator::is_valid(&self.roles, load_role_expr) {
-                                return Err(SqlBuilderError::RoleRequired(
-                                    load_role_expr.to_string(),
-                                    format!(
-                                        "field `{}`",
-                                        FieldPath::from(&build_context.query_home_path)
-                                            .append(local_path_with_basename)
-                                            .to_string(),
-                                    ),
-                                )
-                                .into());
-                            }
-                        }
+                let field_info = mapper
+                    .field(field_name)
+                    .ok_or_else(|| SqlBuilderError::FieldMissing(field_name.to_string()))?;
 
-                        let p = [
-                            &self.aux_params,
-                            &field_info.options.aux_params,
-                            query_aux_params,
-                        ];
-                        let aux_params = ParameterMap::new(&p);
+                let query_field = FieldPath::from(&build_context.query_home_path)
+                    .append(local_path_with_basename);
+                if !key && !self.policy_allows(query_field.as_str(), FieldOperation::Order)? {
+                    continue;
+                }
 
-                        let handler = field_info
-                            .options
-                            .field_handler
-                            .as_ref()
-                            .unwrap_or(&mapper.field_handler);
-                        let select_expr =
-                            handler.build_select(field_info.expression.clone(), &aux_params)?;
-                        let canonical_alias = self.canonical_alias(&local_path)?;
-                        if let Some(expr) = select_expr {
-                            let resolver = Resolver::new().with_self_alias(&canonical_alias);
-                            let expr = resolver.resolve(&expr)?;
-                            result.order_expr.extend(expr);
-                            result.order_expr.push_literal(match ord {
-                                FieldOrder::Asc(_) => " ASC, ",
-                                FieldOrder::Desc(_) => " DESC, ",
-                            });
-                        }
+                if let Some(load_role_expr) = &field_info.options.load_role_expr {
+                    if !RoleValidator::is_valid(&self.roles, load_role_expr) {
+                        return Err(SqlBuilderError::RoleRequired(
+                            load_role_expr.to_string(),
+                            format!(
+                                "field `{}`",
+                                FieldPath::from(&build_context.query_home_path)
+                                    .append(local_path_with_basename)
+                                    .to_string(),
+                            ),
+                        )
+                        .into());
                     }
                 }
+
+                let p = [
+                    &self.aux_params,
+                    &field_info.options.aux_params,
+                    query_aux_params,
+                ];
+                let aux_params = ParameterMap::new(&p);
+
+                let handler = field_info
+                    .options
+                    .field_handler
+                    .as_ref()
+                    .unwrap_or(&mapper.field_handler);
+                let select_expr =
+                    handler.build_select(self.field_expression(field_info)?, &aux_params)?;
+                let canonical_alias = self.canonical_alias(&local_path)?;
+                if let Some(expr) = select_expr {
+                    let resolver = Resolver::new().with_self_alias(&canonical_alias);
+                    let expr = resolver.resolve(&expr)?;
+                    // Emulate NULLS FIRST / LAST, because not all databases support it
+                    if let Some(nulls) = nulls {
+                        result.order_expr.push_literal("CASE WHEN ");
+                        result.order_expr.extend(expr.clone());
+                        result.order_expr.push_literal(match nulls {
+                            NullsOrder::First => " IS NULL THEN 0 ELSE 1 END, ",
+                            NullsOrder::Last => " IS NULL THEN 1 ELSE 0 END, ",
+                        });
+                    }
+                    result.order_expr.extend(expr);
+                    result.order_expr.push_literal(if ord.is_desc() {
+                        " DESC, "
+                    } else {
+                        " ASC, "
+                    });
+                }
             }
         }
         if !result.order_expr.is_empty() {
@@ -1051,21 +1516,19 @@ impl<'a> SqlBuilder<'a> {
                             .field_handler
                             .as_ref()
                             .unwrap_or(&mapper.field_handler);
-                        let select_expr =
-                            handler.build_select(mapped_field.expression.clone(), &aux_params)?;
+                        let select_expr = handler
+                            .build_select(self.field_expression(mapped_field)?, &aux_params)?;
 
                         if let Some(expr) = select_expr {
                             let resolver = Resolver::new().with_self_alias(&canonical_alias);
-                            let mut expr = resolver.resolve(&expr)?;
-
-                            expr.push_literal(", ");
+                            let expr = resolver.resolve(&expr)?;
 
                             if local_path.is_empty()
                                 || build_context
                                     .local_joined_paths
                                     .contains(local_path.as_str())
                             {
-                                result.select_expr.extend(expr);
+                                Self::push_select_column(expr, build_context, result);
                                 result.select_stream.push(Select::Preselect);
                             } else {
                                 result.select_stream.push(Select::None);
@@ -1104,14 +1567,27 @@ impl<'a> SqlBuilder<'a> {
                             .into());
                         }
 
-                        if role_valid {
+                        let query_field =
+                            FieldPath::from(&build_context.query_home_path).append(&local_field);
+                        let policy_valid = if build_context
+                            .local_selected_fields
+                            .contains(local_field.as_ref())
+                        {
+                            self.policy_allows(query_field.as_str(), FieldOperation::Select)?
+                        } else {
+                            // Skip disallowed fields from wildcard selection
+                            self.field_policy
+                                .is_allowed(query_field.as_str(), FieldOperation::Select)
+                        };
+
+                        if role_valid && policy_valid {
                             let handler = mapped_field
                                 .options
                                 .field_handler
                                 .as_ref()
                                 .unwrap_or(&mapper.field_handler);
                             let select_expr = handler
-                                .build_select(mapped_field.expression.clone(), &aux_params)?;
+                                .build_select(self.field_expression(mapped_field)?, &aux_params)?;
                             if let Some(expr) = select_expr {
                                 // Fields with unresolved aux params that are selected through a wildcard are unselected
                                 match expr.first_aux_param() {
@@ -1126,8 +1602,7 @@ impl<'a> SqlBuilder<'a> {
                                         let resolver =
                                             Resolver::new().with_self_alias(&canonical_alias);
                                         let expr = resolver.resolve(&expr)?;
-                                        result.select_expr.extend(expr);
-                                        result.select_expr.push_literal(", ");
+                                        Self::push_select_column(expr, build_context, result);
                                         result.select_stream.push(Select::Query);
                                         result.column_counter += 1;
                                     }
@@ -1357,6 +1832,14 @@ impl<'a> SqlBuilder<'a> {
         for token in &query.tokens {
             if let QueryToken::Field(field) = token {
                 if field.filter.is_some() {
+                    // Disallowed filters are dropped or rejected later
+                    if !build_context.restriction
+                        && !self
+                            .field_policy
+                            .is_allowed(&field.name, FieldOperation::Filter)
+                    {
+                        continue;
+                    }
                     let query_path = FieldPath::from(&field.name);
                     if count_selection_only {
                         let root_mapper = self.root_mapper()?;
@@ -1372,6 +1855,7 @@ impl<'a> SqlBuilder<'a> {
                             None => continue,
                         }
                     }
+                    self.add_field_ref_joins(field, build_context)?;
                     if let Some(local_path_with_name) =
                         query_path.localize_path(&build_context.query_home_path)
                     {
@@ -1380,6 +1864,14 @@ impl<'a> SqlBuilder<'a> {
                             for path in field_path.step_up() {
                                 build_context.local_joined_paths.insert(path.to_string());
                             }
+                        } else if field.quantifier.is_some() {
+                            // Join parent of merge for subquery
+                            let parent_path = FieldPath::trim_basename(field_path.as_str());
+                            if self.next_merge_path(&parent_path)?.is_none() {
+                                for path in parent_path.step_up() {
+                                    build_context.local_joined_paths.insert(path.to_string());
+                                }
+                            }
                         }
                     }
                 }
@@ -1387,6 +1879,25 @@ impl<'a> SqlBuilder<'a> {
         }
         Ok(())
     }
+    // Join the paths of the fields, that a filter compares with
+    fn add_field_ref_joins(&self, field: &Field, build_context: &mut BuildContext) -> Result<()> {
+        for name in field.field_refs.values() {
+            if !self.field_policy.is_allowed(name, FieldOperation::Filter) {
+                continue;
+            }
+            if let Some(local_path_with_name) =
+                FieldPath::from(name).localize_path(&build_context.query_home_path)
+            {
+                let field_path = FieldPath::trim_basename(local_path_with_name.as_str());
+                if self.next_merge_path(&field_path)?.is_none() {
+                    for path in field_path.step_up() {
+                        build_context.local_joined_paths.insert(path.to_string());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
     fn selection_from_query<M>(
         &mut self,
         query: &Query<M>,
@@ -1397,26 +1908,40 @@ impl<'a> SqlBuilder<'a> {
         for token in &query.tokens {
             match token {
                 QueryToken::Field(field) => {
-                    self.add_query_field(
-                        &field.name,
-                        &mut build_context,
-                        &mut unmerged_home_paths,
-                        field.hidden,
-                    )?;
+                    // Hidden fields are only joined for filters and orders that the field policy allows
+                    let joined = !field.hidden
+                        || (field.filter.is_none() && field.order.is_none())
+                        || (field.filter.is_some()
+                            && self
+                                .field_policy
+                                .is_allowed(&field.name, FieldOperation::Filter))
+                        || (field.order.is_some()
+                            && self
+                                .field_policy
+                                .is_allowed(&field.name, FieldOperation::Order));
+                    if joined {
+                        self.add_query_field(
+                            &field.name,
+                            &mut build_context,
+                            &mut unmerged_home_paths,
+                            field.hidden,
+                        )?;
+                        self.add_field_ref_joins(field, build_context)?;
+                    }
                     if let Some(o) = &field.order {
-                        let order = match o {
-                            FieldOrder::Asc(o) => o,
-                            FieldOrder::Desc(o) => o,
-                        };
                         let query_path = FieldPath::from(&field.name);
                         if let Some(local_path_with_name) =
                             query_path.localize_path(&build_context.query_home_path)
                         {
                             build_context
                                 .ordering
-                                .entry(*order)
+                                .entry(o.ordinal())
                                 .or_insert_with(Vec::new)
-                                .push((o.to_owned(), local_path_with_name.to_string()));
+                                .push((
+                                    o.to_owned(),
+                                    field.nulls.clone(),
+                                    local_path_with_name.to_string(),
+                                ));
                         }
                     }
                 }
@@ -1658,6 +2183,290 @@ impl<'a> SqlBuilder<'a> {
         Ok(unmerged_home_paths)
     }
 
+    fn check_query_limits<M>(&self, query: &Query<M>) -> Result<()> {
+        if self.query_limits.is_unlimited() {
+            return Ok(());
+        }
+        let mut wildcards = 0;
+        let mut merge_paths: HashSet<String> = HashSet::new();
+
+        for token in &query.tokens {
+            let query_path = match token {
+                QueryToken::Field(field) => {
+                    match &field.filter {
+                        Some(FieldFilter::In(args))
+                        | Some(FieldFilter::Out(args))
+                        | Some(FieldFilter::Fn(_, args)) => {
+                            self.query_limits.check_filter_args(args.len())?
+                        }
+                        _ => {}
+                    }
+                    FieldPath::trim_basename(&field.name)
+                }
+                QueryToken::Wildcard(wildcard) => {
+                    wildcards += 1;
+                    FieldPath::from(wildcard.path.trim_end_matches('_'))
+                }
+                QueryToken::Predicate(predicate) => FieldPath::trim_basename(&predicate.name),
+                QueryToken::Selection(selection) => FieldPath::trim_basename(&selection.name),
+                _ => continue,
+            };
+            self.query_limits.check_path_depth(query_path.as_str())?;
+
+            // Collect merges on path, unknown paths are reported later
+            let mut current_mapper = self
+                .table_mapper_registry
+                .get(&self.root_mapper)
+                .ok_or_else(|| ToqlError::MapperMissing(self.root_mapper.to_string()))?;
+            for (name, path) in query_path.children().zip(query_path.step_down()) {
+                let next_mapper = if let Some(merge) = current_mapper.merges.get(name.as_str()) {
+                    merge_paths.insert(path.to_string());
+                    self.table_mapper_registry.get(&merge.merged_mapper)
+                } else if let Some(join) = current_mapper.joins.get(name.as_str()) {
+                    self.table_mapper_registry.get(&join.joined_mapper)
+                } else {
+                    None
+                };
+                match next_mapper {
+                    Some(m) => current_mapper = m,
+                    None => break,
+                }
+            }
+        }
+        self.query_limits.check_wildcards(wildcards)?;
+        self.query_limits.check_merges(merge_paths.len())?;
+
+        Ok(())
+    }
+
+    // Add the mandatory restriction of the home mapper to the where clause.
+    // The restriction is parenthesized together with the query filters.
+    fn build_restriction_clause(
+        &mut self,
+        build_context: &mut BuildContext,
+        result: &mut BuildResult,
+    ) -> Result<()> {
+        let restriction = match self.restrictions.get(&self.home_mapper) {
+            Some(r) => r.clone_for_type::<()>(),
+            None => return Ok(()),
+        };
+
+        // Restriction is relative to home path
+        let restriction = restriction.fold(&mut HomePath(&build_context.query_home_path));
+
+        let query_where = std::mem::take(&mut result.where_expr);
+        build_context.restriction = true;
+        self.preparse_filter_joins(&restriction, build_context, false)?;
+        self.build_where_clause(&restriction, build_context, false, result)?;
+        build_context.restriction = false;
+        let restriction_where = std::mem::replace(&mut result.where_expr, query_where);
+
+        if !restriction_where.is_empty() {
+            if result.where_expr.is_empty() {
+                result.where_expr = restriction_where;
+            } else {
+                let mut where_expr = SqlExpr::literal("(");
+                where_expr.extend(std::mem::take(&mut result.where_expr));
+                where_expr.push_literal(") AND (");
+                where_expr.extend(restriction_where);
+                where_expr.push_literal(")");
+                result.where_expr = where_expr;
+            }
+        }
+        Ok(())
+    }
+
+    // Add the tenant predicate of the home mapper to the where clause.
+    fn build_tenant_clause(&self, result: &mut BuildResult) -> Result<()> {
+        let mapper = self
+            .table_mapper_registry
+            .get(&self.home_mapper)
+            .ok_or_else(|| ToqlError::MapperMissing(self.home_mapper.to_string()))?;
+        let tenant_expr = match self.tenant_predicate(mapper, &result.table_alias)? {
+            Some(e) => e,
+            None => return Ok(()),
+        };
+        if result.where_expr.is_empty() {
+            result.where_expr = tenant_expr;
+        } else {
+            let mut where_expr = SqlExpr::literal("(");
+            where_expr.extend(std::mem::take(&mut result.where_expr));
+            where_expr.push_literal(") AND ");
+            where_expr.extend(tenant_expr);
+            result.where_expr = where_expr;
+        }
+        Ok(())
+    }
+
+    // Returns the expression of a mapped field with the tenant of a count subquery.
+    // Like in `tenant_predicate` the tenant is only taken from the builder aux params.
+    fn field_expression(
+        &self,
+        mapped_field: &crate::table_mapper::field::Field,
+    ) -> Result<SqlExpr> {
+        let aux_param = match &mapped_field.tenant {
+            Some(a) => a,
+            None => return Ok(mapped_field.expression.clone()),
+        };
+        let tenant = self
+            .aux_params
+            .get(aux_param)
+            .ok_or_else(|| SqlBuilderError::QueryParamMissing(aux_param.to_string()))?;
+        let mut exprs = HashMap::new();
+        exprs.insert(aux_param.to_string(), SqlExpr::arg(tenant.to_owned()));
+        Ok(Resolver::replace_aux_params(
+            mapped_field.expression.clone(),
+            &exprs,
+        ))
+    }
+
+    // Returns the predicate that restricts the mapper to the tenant, if the mapper is separated by tenant.
+    // The tenant is only taken from the builder aux params, queries cannot override it.
+    fn tenant_predicate(
+        &self,
+        mapper: &TableMapper,
+        canonical_alias: &str,
+    ) -> Result<Option<SqlExpr>> {
+        let (column, aux_param) = match mapper.tenant() {
+            Some(t) => t,
+            None => return Ok(None),
+        };
+        let tenant = self
+            .aux_params
+            .get(aux_param)
+            .ok_or_else(|| SqlBuilderError::QueryParamMissing(aux_param.to_string()))?;
+
+        let mut tenant_expr = SqlExpr::alias(canonical_alias);
+        tenant_expr
+            .push_literal(".")
+            .push_literal(column)
+            .push_literal(" = ")
+            .push_arg(tenant.to_owned());
+        Ok(Some(tenant_expr))
+    }
+
+    // Returns a copy of the query with bound placeholders or None, if the query has no placeholders.
+    // Values are only taken from the bind params of the query.
+    fn bind_placeholders<M>(&self, query: &Query<M>) -> Result<Option<Query<M>>> {
+        let has_placeholders = query.tokens.iter().any(|t| match t {
+            QueryToken::Field(f) => !f.placeholders.is_empty(),
+            QueryToken::Predicate(p) => !p.placeholders.is_empty(),
+            _ => false,
+        });
+        if !has_placeholders {
+            return Ok(None);
+        }
+        let mut binder = BindPlaceholders {
+            bind_params: &query.bind_params,
+            missing: None,
+        };
+        let bound_query = query.clone_for_type::<M>().fold(&mut binder);
+        match binder.missing {
+            Some(name) => Err(SqlBuilderError::QueryParamMissing(name).into()),
+            None => Ok(Some(bound_query)),
+        }
+    }
+
+    // Use default order of merge, if the query does not order the merged entities
+    fn preset_merge_order(
+        &self,
+        query_home_path: &str,
+        build_context: &mut BuildContext,
+    ) -> Result<()> {
+        let (query_path, basename) = FieldPath::split_basename(query_home_path);
+        let mapper = self.mapper_for_query_path(&query_path)?;
+        let merge = mapper
+            .merge(basename)
+            .ok_or_else(|| SqlBuilderError::MergeMissing(basename.to_string()))?;
+
+        for (field_name, o, nulls) in &merge.options.order {
+            build_context
+                .ordering
+                .entry(o.ordinal())
+                .or_default()
+                .push((o.to_owned(), nulls.to_owned(), field_name.to_string()));
+        }
+        Ok(())
+    }
+
+    // Collect the key fields of the mapper for a stable order.
+    // Key joins are joined to order by the keys of the joined entity.
+    fn preset_key_order(
+        &self,
+        mapper: &TableMapper,
+        local_path: &FieldPath,
+        build_context: &mut BuildContext,
+    ) -> Result<()> {
+        for order in &mapper.deserialize_order {
+            match order {
+                DeserializeType::Field(name) => {
+                    let field = mapper
+                        .field(name)
+                        .ok_or_else(|| SqlBuilderError::FieldMissing(name.to_string()))?;
+                    if !field.options.key {
+                        return Ok(());
+                    }
+                    build_context
+                        .key_ordering
+                        .push(local_path.append(name).to_string());
+                }
+                DeserializeType::Join(name) => {
+                    let join = mapper.join(name).ok_or_else(|| {
+                        SqlBuilderError::JoinMissing(
+                            name.to_string(),
+                            mapper.table_name.to_string(),
+                        )
+                    })?;
+                    if !join.options.key {
+                        return Ok(());
+                    }
+                    let joined_path = local_path.append(name);
+                    for p in joined_path.step_down() {
+                        build_context.local_joined_paths.insert(p.to_string());
+                    }
+                    let joined_mapper = self
+                        .table_mapper_registry
+                        .get(&join.joined_mapper)
+                        .ok_or_else(|| ToqlError::MapperMissing(join.joined_mapper.to_string()))?;
+                    self.preset_key_order(joined_mapper, &joined_path, build_context)?;
+                }
+                DeserializeType::Merge(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    // Returns true, if the field policy allows the operation on the query field.
+    // Disallowed fields are either skipped or raise an error.
+    fn policy_allows(&self, query_field: &str, operation: FieldOperation) -> Result<bool> {
+        if self.field_policy.is_allowed(query_field, operation) {
+            return Ok(true);
+        }
+        match self.field_policy.violation() {
+            OnViolation::Drop => Ok(false),
+            OnViolation::Reject => Err(SqlBuilderError::FieldNotAllowed(
+                query_field.to_string(),
+                operation.to_string(),
+            )
+            .into()),
+        }
+    }
+
+    // Add a column to the select clause.
+    // Columns of derived tables get unique aliases, because column names may repeat.
+    fn push_select_column(
+        expr: SqlExpr,
+        build_context: &mut BuildContext,
+        result: &mut BuildResult,
+    ) {
+        result.select_expr.extend(expr);
+        if let Some(n) = build_context.column_aliases.as_mut() {
+            result.select_expr.push_literal(format!(" AS toql_c{}", n));
+            *n += 1;
+        }
+        result.select_expr.push_literal(", ");
+    }
+
     fn root_mapper(&self) -> Result<&TableMapper> {
         self.table_mapper_registry
             .get(&self.home_mapper)
@@ -1698,3 +2507,58 @@ impl<'a> SqlBuilder<'a> {
         r
     }
 }
+
+// Replaces named placeholders with their values
+struct BindPlaceholders<'a> {
+    bind_params: &'a HashMap<String, SqlArg>,
+    missing: Option<String>,
+}
+
+impl<'a> BindPlaceholders<'a> {
+    fn value(&mut self, name: &str) -> Option<SqlArg> {
+        let value = self.bind_params.get(name).cloned();
+        if value.is_none() && self.missing.is_none() {
+            self.missing = Some(name.to_string());
+        }
+        value
+    }
+}
+
+impl<'a> QueryFolder for BindPlaceholders<'a> {
+    fn fold_field(&mut self, mut field: Field) -> Option<Field> {
+        for (i, name) in std::mem::take(&mut field.placeholders) {
+            let value = self.value(&name);
+            if let (Some(value), Some(filter)) = (value, field.filter.as_mut()) {
+                if let Some(arg) = filter.args_mut().into_iter().nth(i) {
+                    *arg = value;
+                }
+            }
+        }
+        Some(field)
+    }
+    fn fold_predicate(&mut self, mut predicate: Predicate) -> Option<Predicate> {
+        for (i, name) in std::mem::take(&mut predicate.placeholders) {
+            if let (Some(value), Some(arg)) = (self.value(&name), predicate.args.get_mut(i)) {
+                *arg = value;
+            }
+        }
+        Some(predicate)
+    }
+}
+
+// Prepends the home path to fields and predicates
+struct HomePath<'a>(&'a str);
+
+impl<'a> QueryFolder for HomePath<'a> {
+    fn fold_field(&mut self, mut field: Field) -> Option<Field> {
+        for name in field.field_refs.values_mut() {
+            *name = FieldPath::from(self.0).append(name).to_string();
+        }
+        let name = FieldPath::from(self.0).append(field.name()).to_string();
+        Some(field.rename(name))
+    }
+    fn fold_predicate(&mut self, predicate: Predicate) -> Option<Predicate> {
+        let name = FieldPath::from(self.0).append(predicate.name()).to_string();
+        Some(predicate.rename(name))
+    }
+}
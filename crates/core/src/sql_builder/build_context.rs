@@ -1,7 +1,10 @@
 //!  Aggregated data for function calls inside the SQL Builder.
 
 use crate::{
-    query::{field_order::FieldOrder, field_path::FieldPath},
+    query::{
+        field_order::{FieldOrder, NullsOrder},
+        field_path::FieldPath,
+    },
     sql_arg::SqlArg,
 };
 use std::collections::{HashMap, HashSet};
@@ -11,8 +14,11 @@ pub(crate) struct BuildContext {
     pub(crate) local_joined_paths: HashSet<String>,
     pub(crate) local_selected_paths: HashSet<String>,
     pub(crate) local_selected_fields: HashSet<String>,
-    pub(crate) ordering: HashMap<u8, Vec<(FieldOrder, String)>>,
+    pub(crate) ordering: HashMap<u8, Vec<(FieldOrder, Option<NullsOrder>, String)>>,
+    pub(crate) key_ordering: Vec<String>, // Key fields that order last for a stable order
     pub(crate) on_aux_params: HashMap<String, SqlArg>, // generic build params
+    pub(crate) restriction: bool, // Filters from a mandatory restriction skip role and policy checks
+    pub(crate) column_aliases: Option<usize>, // Next alias number, if select columns are aliased
 }
 
 impl BuildContext {
@@ -23,7 +29,10 @@ impl BuildContext {
             local_selected_paths: HashSet::new(),
             local_selected_fields: HashSet::new(),
             ordering: HashMap::new(),
+            key_ordering: Vec::new(),
             on_aux_params: HashMap::new(),
+            restriction: false,
+            column_aliases: None,
         }
     }
 
@@ -81,7 +81,7 @@ impl BuildResult {
 
     pub fn set_from(&mut self, table: String, canonical_alias: String) {
         self.table_alias = canonical_alias.to_owned();
-        self.from_expr.push_literal(table);
+        self.from_expr.push_table(table);
         self.from_expr.push_literal(" ");
         self.from_expr.push_alias(canonical_alias);
     }
@@ -90,6 +90,34 @@ impl BuildResult {
         self.join_expr.extend(j);
     }
 
+    // Returns the select statement without order as expression, e.g. to use it as derived table.
+    pub(crate) fn to_sql_expr(&self) -> SqlExpr {
+        let mut expr = self.verb_expr.clone();
+        expr.push_literal(" ");
+        if !self.modifier.is_empty() {
+            expr.push_literal(self.modifier.to_owned());
+            expr.push_literal(" ");
+        }
+        if !self.preselect_expr.is_empty() {
+            expr.extend(self.preselect_expr.clone());
+            expr.push_literal(", ");
+        }
+        expr.extend(self.select_expr.clone());
+        if !self.from_expr.is_empty() {
+            expr.push_literal(" FROM ");
+            expr.extend(self.from_expr.clone());
+        }
+        if !self.join_expr.is_empty() {
+            expr.push_literal(" ");
+            expr.extend(self.join_expr.clone());
+        }
+        if !self.where_expr.is_empty() {
+            expr.push_literal(" WHERE ");
+            expr.extend(self.where_expr.clone());
+        }
+        expr
+    }
+
     pub fn to_sql(
         &self,
         aux_params: &ParameterMap,
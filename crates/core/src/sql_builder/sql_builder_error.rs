@@ -20,6 +20,10 @@ pub enum SqlBuilderError {
     #[error("merge `{0}` is missing")]
     MergeMissing(String),
 
+    /// The merge has a limit, but the merged entity has a join as key. Contains the merge path.
+    #[error("limit on merge `{0}` requires keys without joins")]
+    MergeLimitInvalid(String),
+
     /// The selection is not known to the mapper. Contains the field name.
     #[error("selection `{0}` is missing")]
     SelectionMissing(String),
@@ -51,4 +55,12 @@ pub enum SqlBuilderError {
     /// A path was found for a selection that only exists in root, such as $all, $mut, $cnt
     #[error("a path `{0}` was found but no path is allowed")]
     PathUnexpected(String),
+
+    /// The query exceeds a configured [QueryLimits](crate::query_limits::QueryLimits). Contains the limit name, the actual and the maximum value.
+    #[error("query exceeds limit for {0}: {1} > {2}")]
+    QueryLimitExceeded(String, u64, u64),
+
+    /// The [FieldPolicy](crate::field_policy::FieldPolicy) does not allow the operation on the field. Contains the query field and the operation.
+    #[error("{1} is not allowed on field `{0}`")]
+    FieldNotAllowed(String, String),
 }
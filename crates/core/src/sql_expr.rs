@@ -2,6 +2,7 @@
 pub mod resolver;
 pub mod resolver_error;
 use crate::sql_arg::SqlArg;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 /// Hold information about a predicate column.
@@ -39,6 +40,9 @@ pub enum SqlExprToken {
     Arg(SqlArg),
     /// Canonical alias in expression
     Alias(String),
+    /// Table name in expression.
+    /// The name may contain aux params, such as `<schema>.Order_<year>`.
+    Table(String),
     /// Predicate in expression
     /// A predicate token has been introduced to
     /// improve SQL formatting.
@@ -111,6 +115,13 @@ impl SqlExpr {
             maybe_aux_params: false,
         }
     }
+    /// Create SQL expression from table name.
+    pub fn table(table_name: impl Into<String>) -> Self {
+        SqlExpr {
+            tokens: vec![SqlExprToken::Table(table_name.into())],
+            maybe_aux_params: false,
+        }
+    }
     /// Create SQL expression from self alias.
     pub fn self_alias() -> Self {
         SqlExpr {
@@ -189,6 +200,11 @@ impl SqlExpr {
         self.tokens.push(SqlExprToken::OtherAlias);
         self
     }
+    /// Add table name to the end of the list.
+    pub fn push_table(&mut self, table_name: impl Into<String>) -> &mut Self {
+        self.tokens.push(SqlExprToken::Table(table_name.into()));
+        self
+    }
     /// Add custom alias to the end of the list.
     pub fn push_alias(&mut self, alias: impl Into<String>) -> &mut Self {
         self.tokens.push(SqlExprToken::Alias(alias.into()));
@@ -199,6 +215,12 @@ impl SqlExpr {
         self.tokens.push(SqlExprToken::Arg(arg));
         self
     }
+    /// Add auxiliary parameter to the end of the list.
+    pub fn push_aux_param(&mut self, name: impl Into<String>) -> &mut Self {
+        self.tokens.push(SqlExprToken::AuxParam(name.into()));
+        self.maybe_aux_params = true;
+        self
+    }
     /// Add unresolved argument to the end of the list.
     pub fn push_unresolved_arg(&mut self) -> &mut Self {
         self.tokens.push(SqlExprToken::UnresolvedArg);
@@ -256,6 +278,52 @@ impl SqlExpr {
     pub fn tokens(&self) -> &[SqlExprToken] {
         &self.tokens
     }
+    /// Replace filter arguments with expressions, keyed by the position of the filter argument.
+    /// The filter arguments `args` must be the last arguments in the expression
+    /// and the replaced arguments must be unchanged.
+    /// Returns false and leaves the expression untouched otherwise.
+    pub(crate) fn replace_filter_args(
+        &mut self,
+        args: &[&SqlArg],
+        exprs: &BTreeMap<usize, SqlExpr>,
+    ) -> bool {
+        let positions = self
+            .tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| matches!(t, SqlExprToken::Arg(_)))
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        if positions.len() < args.len() {
+            return false;
+        }
+        let offset = positions.len() - args.len();
+        let mut replacements = HashMap::with_capacity(exprs.len());
+        for (i, expr) in exprs {
+            let position = match positions.get(offset + i) {
+                Some(p) => *p,
+                None => return false,
+            };
+            match &self.tokens[position] {
+                SqlExprToken::Arg(a) if Some(&a) == args.get(*i) => {
+                    replacements.insert(position, expr)
+                }
+                _ => return false,
+            };
+        }
+        let mut tokens = Vec::with_capacity(self.tokens.len());
+        for (p, t) in self.tokens.drain(..).enumerate() {
+            match replacements.get(&p) {
+                Some(expr) => {
+                    tokens.extend(expr.tokens.iter().cloned());
+                    self.maybe_aux_params |= expr.maybe_aux_params;
+                }
+                None => tokens.push(t),
+            }
+        }
+        self.tokens = tokens;
+        true
+    }
 }
 
 impl fmt::Display for SqlExpr {
@@ -277,6 +345,7 @@ impl fmt::Display for SqlExprToken {
             SqlExprToken::Literal(l) => write!(f, "{}", l),
             SqlExprToken::Arg(a) => write!(f, "{}", a.to_sql_string()),
             SqlExprToken::Alias(a) => write!(f, "{}", a),
+            SqlExprToken::Table(t) => write!(f, "{}", t),
             SqlExprToken::Predicate {
                 columns: _,
                 args: _,
@@ -151,6 +151,42 @@ impl<'a> Resolver<'a> {
         Ok(Sql(stmt, args))
     }
 
+    /// Replace the aux params in a table name, such as `<schema>.Order_<year>`.
+    /// The values are inserted literally, so they must only contain letters, digits and underscores.
+    pub fn table_name(
+        table_name: &str,
+        aux_params: Option<&ParameterMap>,
+    ) -> Result<String, ResolverError> {
+        let mut resolved = String::with_capacity(table_name.len());
+        let mut rest = table_name;
+        while let Some(start) = rest.find('<') {
+            let end = match rest[start..].find('>') {
+                Some(e) => start + e,
+                None => break,
+            };
+            let name = &rest[start + 1..end];
+            let value = aux_params.and_then(|p| p.get(name)).ok_or_else(|| {
+                ResolverError::TableAuxParamMissing(name.to_string(), table_name.to_string())
+            })?;
+            let value = match value {
+                SqlArg::Str(s) => s.to_owned(),
+                SqlArg::U64(_) | SqlArg::I64(_) => value.to_string(),
+                _ => String::new(),
+            };
+            if value.is_empty() || !value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(ResolverError::TableAuxParamInvalid(
+                    name.to_string(),
+                    table_name.to_string(),
+                ));
+            }
+            resolved.push_str(&rest[..start]);
+            resolved.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        resolved.push_str(rest);
+        Ok(resolved)
+    }
+
     fn resolve_alias_to_literals(&self, token: &'a SqlExprToken) -> Cow<'a, SqlExprToken> {
         match token {
             SqlExprToken::SelfAlias if self.self_alias.is_some() => {
@@ -189,6 +225,9 @@ impl<'a> Resolver<'a> {
                     .to_owned();
                 Ok(Cow::Owned(SqlExprToken::Arg(arg)))
             }
+            SqlExprToken::Table(table_name) if self.aux_params.is_some() => Ok(Cow::Owned(
+                SqlExprToken::Literal(Self::table_name(table_name, self.aux_params)?),
+            )),
             SqlExprToken::UnresolvedArg if arg_iter.is_some() => {
                 let arg = arg_iter
                     .unwrap()
@@ -271,6 +310,8 @@ impl<'a> Resolver<'a> {
 
             SqlExprToken::Literal(lit) => stmt.push_str(&lit),
 
+            SqlExprToken::Table(table_name) => stmt.push_str(&Self::table_name(table_name, None)?),
+
             SqlExprToken::Alias(canonical_alias) => {
                 let alias = alias_translator.translate(canonical_alias);
                 stmt.push_str(&alias);
@@ -377,6 +418,8 @@ impl Default for Resolver<'_> {
 mod test {
     use super::Resolver;
     use crate::{
+        alias_format::AliasFormat,
+        alias_translator::AliasTranslator,
         parameter_map::ParameterMap,
         sql_arg::SqlArg,
         sql_expr::{SqlExpr, SqlExprToken},
@@ -423,6 +466,42 @@ mod test {
         assert_eq!(expr.to_string(), "a");
     }
     #[test]
+    fn table_name() {
+        let mut params = HashMap::new();
+        params.insert("schema".to_string(), SqlArg::Str("acme".to_string()));
+        params.insert("year".to_string(), SqlArg::U64(2024));
+        params.insert("bad".to_string(), SqlArg::Str("a; DROP".to_string()));
+        let map = [&params];
+        let map = ParameterMap::new(&map);
+
+        assert_eq!(
+            Resolver::table_name("<schema>.Order_<year>", Some(&map)).unwrap(),
+            "acme.Order_2024"
+        );
+        assert_eq!(Resolver::table_name("Order", None).unwrap(), "Order");
+        assert_eq!(
+            Resolver::table_name("<tenant>.Order", Some(&map))
+                .err()
+                .unwrap()
+                .to_string(),
+            "aux param `tenant` for table `<tenant>.Order` is missing"
+        );
+        assert!(Resolver::table_name("<bad>.Order", Some(&map)).is_err());
+
+        // Resolved with aux params, unresolved fails
+        let expr = SqlExpr::table("<schema>.Order");
+        let expr = Resolver::new()
+            .with_aux_params(&map)
+            .resolve(&expr)
+            .unwrap();
+        assert_eq!(expr.to_string(), "acme.Order");
+        let expr = SqlExpr::table("<schema>.Order");
+        let mut alias_translator = AliasTranslator::new(AliasFormat::Canonical);
+        assert!(Resolver::new()
+            .to_sql(&expr, &mut alias_translator)
+            .is_err());
+    }
+    #[test]
     fn alias_to_literals() {
         // resolve all aliases
         let tokens = vec![
@@ -22,6 +22,12 @@ pub enum ResolverError {
     /// Value of aux param is unknown.
     #[error("unresolved aux param `{0}`")]
     UnresolvedAuxParameter(String),
+    /// Table name can't be resolved, because the aux param is missing. Contains the aux param and the table name.
+    #[error("aux param `{0}` for table `{1}` is missing")]
+    TableAuxParamMissing(String, String),
+    /// Aux param value is not a valid part of a table name. Contains the aux param and the table name.
+    #[error("aux param `{0}` for table `{1}` must only contain letters, digits and underscores")]
+    TableAuxParamInvalid(String, String),
 }
 
 // Result type alias with [ResolverError]
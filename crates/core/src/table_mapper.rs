@@ -17,7 +17,8 @@ use crate::{
     predicate_handler::{DefaultPredicateHandler, PredicateHandler},
     result::Result,
     role_expr::RoleExpr,
-    sql_expr::SqlExpr,
+    sql_builder::sql_builder_error::SqlBuilderError,
+    sql_expr::{resolver::Resolver, SqlExpr},
     table_mapper::{
         field::Field, field_options::FieldOptions, join::Join, join_options::JoinOptions,
         mapped::Mapped, merge::Merge, merge_options::MergeOptions, predicate::Predicate,
@@ -86,6 +87,9 @@ pub struct TableMapper {
     /// Delete role expressions for the struct.
     pub(crate) delete_role_expr: Option<RoleExpr>,
 
+    /// Tenant column and the aux param that holds the tenant of the context.
+    pub(crate) tenant: Option<(String, String)>,
+
     /// Maps a selection name to Toql query pathed fields or paths with wildcard
     /// Automatic created selection are
     /// $cnt - Fields for count query
@@ -124,6 +128,7 @@ where {
             selections: HashMap::new(),
             load_role_expr: None,
             delete_role_expr: None,
+            tenant: None,
         }
     }
     /// Create a new mapper from a struct that implements the [Mapped] trait.
@@ -231,6 +236,7 @@ where {
         let t = Field {
             expression,
             options,
+            tenant: None,
             // handler: Arc::clone(&self.field_handler),
             //  sql_aux_param_names,
         };
@@ -241,6 +247,66 @@ where {
 
         self
     }
+    /// Map a field that counts the entities of a merge.
+    ///
+    /// The field is loaded with a correlated `COUNT(*)` subquery on the merge predicate,
+    /// so the merge must be mapped before. The field cannot be updated and
+    /// requires the load roles of the merge in addition to its own.
+    /// Only entities of the tenant are counted, if the merged table is separated by tenant.
+    pub fn map_count_of_with_options<M: Mapped>(
+        &mut self,
+        toql_field: &str,
+        merge_path: &str,
+        mut options: FieldOptions,
+    ) -> Result<&mut Self> {
+        let merge_path = merge_path.to_mixed_case();
+        let merge = self
+            .merges
+            .get(&merge_path)
+            .ok_or_else(|| SqlBuilderError::MergeMissing(merge_path.to_string()))?;
+        let custom_join = merge
+            .custom_join(&self.table_name)
+            .ok_or_else(|| SqlBuilderError::MergeMissing(merge_path.to_string()))?;
+
+        let merged_alias = format!("{}_{}", &self.canonical_table_alias, &merge_path);
+        let mut expression = SqlExpr::literal("(SELECT COUNT(*) FROM ");
+        expression
+            .push_table(M::table_name())
+            .push_literal(" ")
+            .push_other_alias()
+            .push_literal(" ")
+            .extend(custom_join)
+            .push_literal("WHERE ")
+            .extend(merge.merge_predicate.clone());
+        let tenant = M::tenant();
+        if let Some((column, aux_param)) = &tenant {
+            expression
+                .push_literal(" AND ")
+                .push_other_alias()
+                .push_literal(".")
+                .push_literal(column)
+                .push_literal(" = ")
+                .push_aux_param(aux_param);
+        }
+        expression.push_literal(")");
+        let expression = Resolver::new()
+            .with_other_alias(&merged_alias)
+            .resolve(&expression)?;
+
+        if let Some(merge_role_expr) = &merge.options.load_role_expr {
+            options.load_role_expr = Some(match options.load_role_expr {
+                Some(role_expr) => role_expr.and(merge_role_expr.clone()),
+                None => merge_role_expr.clone(),
+            });
+        }
+        options.skip_mut = true;
+
+        self.map_expr_with_options(toql_field, expression, options);
+        if let Some(field) = self.fields.get_mut(&toql_field.to_mixed_case()) {
+            field.tenant = tenant.map(|(_, aux_param)| aux_param);
+        }
+        Ok(self)
+    }
     /// Map a join with default [JoinOptions]
     pub fn map_join(
         &mut self,
@@ -367,4 +433,18 @@ where {
     pub fn restrict_load(&mut self, role_expr: RoleExpr) {
         self.load_role_expr = Some(role_expr);
     }
+
+    /// Separate the rows of this `struct` by tenant.
+    /// Selects, counts, deletes and joins are filtered with the value of the aux param
+    /// and inserts take the column value from it.
+    pub fn map_tenant(&mut self, column: &str, aux_param: &str) {
+        self.tenant = Some((column.to_string(), aux_param.to_string()));
+    }
+
+    /// Return the tenant column and aux param, if the `struct` is separated by tenant.
+    pub fn tenant(&self) -> Option<(&str, &str)> {
+        self.tenant
+            .as_ref()
+            .map(|(column, aux_param)| (column.as_str(), aux_param.as_str()))
+    }
 }
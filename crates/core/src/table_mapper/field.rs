@@ -3,6 +3,7 @@ use crate::sql_expr::SqlExpr;
 
 #[derive(Debug)]
 pub(crate) struct Field {
-    pub(crate) options: FieldOptions, // Options
-    pub(crate) expression: SqlExpr,   // Column name or SQL expression
+    pub(crate) options: FieldOptions,  // Options
+    pub(crate) expression: SqlExpr,    // Column name or SQL expression
+    pub(crate) tenant: Option<String>, // Aux param of the tenant in a count subquery
 }
@@ -8,4 +8,8 @@ pub trait Mapped {
     fn table_alias() -> String;
     fn type_name() -> String;
     fn map(mapper: &mut TableMapper) -> crate::result::Result<()>; // Map entity fields
+    /// Column and aux param of the tenant, if the table is separated by tenant.
+    fn tenant() -> Option<(String, String)> {
+        None
+    }
 }
@@ -1,5 +1,5 @@
 use super::merge_options::MergeOptions;
-use crate::sql_expr::SqlExpr;
+use crate::sql_expr::{SqlExpr, SqlExprToken};
 
 #[derive(Debug)]
 pub(crate) struct Merge {
@@ -8,3 +8,19 @@ pub(crate) struct Merge {
     pub(crate) merge_predicate: SqlExpr, // ON ..
     pub(crate) options: MergeOptions,    // ON ..
 }
+
+impl Merge {
+    // Returns the merge join without the join to the parent table.
+    // This leaves the custom join, if any, for subqueries that correlate
+    // with the parent table of an outer query.
+    pub(crate) fn custom_join(&self, parent_table: &str) -> Option<SqlExpr> {
+        match self.merge_join.tokens() {
+            [custom_join @ .., SqlExprToken::Literal(j), SqlExprToken::Table(t), SqlExprToken::Literal(s), SqlExprToken::SelfAlias]
+                if j == "JOIN " && t == parent_table && s == " " =>
+            {
+                Some(SqlExpr::from(custom_join.to_vec()))
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::role_expr::RoleExpr;
+use crate::{
+    query::field_order::{FieldOrder, NullsOrder},
+    role_expr::RoleExpr,
+};
 
 /// Options for a mapped merge.
 #[derive(Debug)]
@@ -6,6 +9,8 @@ pub struct MergeOptions {
     pub(crate) preselect: bool, // Always select this merge, regardless of query fields
     //pub(crate) skip_mut: bool, // Ignore merge for updates
     pub(crate) load_role_expr: Option<RoleExpr>, // Only for use by these roles
+    pub(crate) order: Vec<(String, FieldOrder, Option<NullsOrder>)>, // Default order of merged entities
+    pub(crate) limit: Option<u64>, // Default number of merged entities per parent
 }
 
 impl MergeOptions {
@@ -14,6 +19,8 @@ impl MergeOptions {
         MergeOptions {
             preselect: false,
             load_role_expr: None,
+            order: Vec::new(),
+            limit: None,
         }
     }
 
@@ -29,6 +36,33 @@ impl MergeOptions {
         self.load_role_expr = Some(role_expr);
         self
     }
+
+    /// Order the merged entities by a field of the merged entity.
+    /// The order is only used, if the query does not order the merge.
+    pub fn order(mut self, toql_field: &str, order: FieldOrder) -> Self {
+        self.order.push((toql_field.to_string(), order, None));
+        self
+    }
+
+    /// Order the merged entities by a field of the merged entity
+    /// and place null values first or last.
+    pub fn order_with_nulls(
+        mut self,
+        toql_field: &str,
+        order: FieldOrder,
+        nulls: NullsOrder,
+    ) -> Self {
+        self.order
+            .push((toql_field.to_string(), order, Some(nulls)));
+        self
+    }
+
+    /// Load at most `limit` merged entities per parent entity.
+    /// The query can override the limit with [Query::limit_merge](crate::query::Query::limit_merge).
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
 }
 
 impl Default for MergeOptions {
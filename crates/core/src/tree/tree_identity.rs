@@ -17,6 +17,21 @@ pub enum IdentityAction {
     /// Refresh all valid foreign keys, that refer to this entity (merges).
     RefreshValid,
 }
+
+/// The values that [TreeIdentity::fill] writes back.
+/// The database takes them from the context instead of the struct.
+#[non_exhaustive]
+pub enum FillAction {
+    /// Set the tenant field to the tenant of the context.
+    Tenant(SqlArg),
+    /// Set `created_at` and `updated_at` fields to the time of an insert.
+    StampInsert(SqlArg),
+    /// Set `created_at` and `updated_at` fields to the time of an insert,
+    /// if the key is invalid (new merges).
+    StampInsertInvalid(SqlArg),
+    /// Set `updated_at` fields to the time of an update.
+    StampUpdate(SqlArg),
+}
 /// Deal with primary and foreign keys in nested structs.
 pub trait TreeIdentity {
     /// Returns true, if struct located at `descendents` has database generated keys.
@@ -32,4 +47,13 @@ pub trait TreeIdentity {
     ) -> Result<(), ToqlError>
     where
         I: Iterator<Item = FieldPath<'a>> + Clone;
+
+    /// Write filled values back into structs located at `descendents`.
+    /// By default nothing is written.
+    fn fill<'a, 'b, I>(&mut self, _descendents: I, _action: &'b FillAction) -> Result<(), ToqlError>
+    where
+        I: Iterator<Item = FieldPath<'a>> + Clone,
+    {
+        Ok(())
+    }
 }
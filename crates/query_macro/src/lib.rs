@@ -94,7 +94,7 @@ fn fields() {
 fn filters() {
     use query_macro::QueryMacro;
     let input = "User, \"prop eq 1.5, prop eqn, prop ne 1, prop nen, prop gt 1, prop ge 1,\
-                        prop lt 1, prop le 1, prop lk 'ABC', \
+                        prop lt 1, prop le 1, prop lk 'ABC', prop re '^A', prop ilk 'a', prop sw 'a', \
                         prop in 1 2 3, prop out 1 2 3, prop bw 1 10,\
                         prop fn cst 'A' 'B' 'C'\"";
 
@@ -119,6 +119,9 @@ fn filters() {
                 . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . lt ( 1u64 ) ) \
                 . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . le ( 1u64 ) ) \
                 . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . lk ( \"ABC\" ) ) \
+                . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . re ( \"^A\" ) ) \
+                . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . ilk ( \"a\" ) ) \
+                . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . sw ( \"a\" ) ) \
                 . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) \
                 . ins ( & [ 1u64 , 2u64 , 3u64 ] ) ) \
                 . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . out ( & [ 1u64 , 2u64 , 3u64 ] ) ) \
@@ -241,6 +244,173 @@ fn parenthesis() {
             . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . eq ( \"ABC\" ) ) ) )");
 }
 
+#[test]
+fn negation() {
+    use query_macro::QueryMacro;
+    let input = "User, \"!prop eq 1; not (prop; prop eq 2)\"";
+
+    let m = syn::parse_str(input);
+    assert!(m.is_ok());
+
+    let QueryMacro {
+        query,
+        struct_type,
+        arguments,
+    } = m.unwrap();
+    let f = query_macro::parse(&query, struct_type, &mut arguments.iter());
+
+    assert!(f.is_ok());
+
+    assert_eq!(f.unwrap().to_string(), "toql :: query :: Query :: < User > :: new ( ) \
+    . and ( toql :: query :: Query :: < User > :: from ( \
+        < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . eq ( 1u64 ) ) . not ( ) ) \
+    . or ( toql :: query :: Query :: < User > :: new ( ) \
+        . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) ) \
+        . or ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . eq ( 2u64 ) ) \
+        . parenthesize ( ) . not ( ) )");
+}
+
+#[test]
+fn quantifier() {
+    use query_macro::QueryMacro;
+    let input = "User, \"any items_prop eq 1, none .items_prop eqn\"";
+
+    let m = syn::parse_str(input);
+    assert!(m.is_ok());
+
+    let QueryMacro {
+        query,
+        struct_type,
+        arguments,
+    } = m.unwrap();
+    let f = query_macro::parse(&query, struct_type, &mut arguments.iter());
+
+    assert!(f.is_ok());
+
+    assert_eq!(f.unwrap().to_string(), "toql :: query :: Query :: < User > :: new ( ) \
+    . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#items ( ) . r#prop ( ) . eq ( 1u64 ) . any ( ) ) \
+    . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#items ( ) . r#prop ( ) . hide ( ) . eqn ( ) . none ( ) )");
+}
+
+#[test]
+fn nulls_order() {
+    use query_macro::QueryMacro;
+    let input = "User, \"+1prop nulls last, -2.prop NULLS FIRST eq 1\"";
+
+    let m = syn::parse_str(input);
+    assert!(m.is_ok());
+
+    let QueryMacro {
+        query,
+        struct_type,
+        arguments,
+    } = m.unwrap();
+    let f = query_macro::parse(&query, struct_type, &mut arguments.iter());
+
+    assert!(f.is_ok());
+
+    assert_eq!(f.unwrap().to_string(), "toql :: query :: Query :: < User > :: new ( ) \
+    . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . asc ( 1u8 ) . nulls_last ( ) ) \
+    . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . desc ( 2u8 ) . nulls_first ( ) . hide ( ) . eq ( 1u64 ) )");
+}
+
+#[test]
+fn distinct() {
+    use query_macro::QueryMacro;
+    let input = "User, \"DISTINCT prop eq 1\"";
+
+    let m = syn::parse_str(input);
+    assert!(m.is_ok());
+
+    let QueryMacro {
+        query,
+        struct_type,
+        arguments,
+    } = m.unwrap();
+    let f = query_macro::parse(&query, struct_type, &mut arguments.iter());
+
+    assert!(f.is_ok());
+    assert_eq!(f.unwrap().to_string(), "toql :: query :: Query :: < User > :: new ( ) . distinct ( ) \
+    . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . eq ( 1u64 ) )");
+}
+
+#[test]
+fn field_ref() {
+    use query_macro::QueryMacro;
+    let input = "User, \"prop gt $level2_other\"";
+
+    let m = syn::parse_str(input);
+    assert!(m.is_ok());
+
+    let QueryMacro {
+        query,
+        struct_type,
+        arguments,
+    } = m.unwrap();
+    let f = query_macro::parse(&query, struct_type, &mut arguments.iter());
+
+    assert!(f.is_ok());
+    assert_eq!(
+        f.unwrap().to_string(),
+        "toql :: query :: Query :: < User > :: new ( ) \
+    . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) \
+    . gt ( toql :: sql_arg :: SqlArg :: Null ) . field_ref ( 0usize , \"level2_other\" ) )"
+    );
+}
+
+#[test]
+fn literals() {
+    use query_macro::QueryMacro;
+    let input = "User, \"prop eq true, prop2 ne null, prop3 bw 2021-12-31 2021-12-31T23:59\"";
+
+    let m = syn::parse_str(input);
+    assert!(m.is_ok());
+
+    let QueryMacro {
+        query,
+        struct_type,
+        arguments,
+    } = m.unwrap();
+    let f = query_macro::parse(&query, struct_type, &mut arguments.iter());
+
+    assert!(f.is_ok());
+    assert_eq!(
+        f.unwrap().to_string(),
+        "toql :: query :: Query :: < User > :: new ( ) \
+    . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop ( ) . eq ( true ) ) \
+    . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop2 ( ) \
+    . ne ( toql :: sql_arg :: SqlArg :: Null ) ) \
+    . and ( < User as toql :: query_fields :: QueryFields > :: fields ( ) . r#prop3 ( ) \
+    . bw ( \"2021-12-31\" , \"2021-12-31 23:59:00\" ) )"
+    );
+
+    let input = "User, \"prop eq 2021-02-30\""; // invalid date
+    let m = syn::parse_str(input);
+    assert!(m.is_ok());
+    let QueryMacro {
+        query,
+        struct_type,
+        arguments,
+    } = m.unwrap();
+    let f = query_macro::parse(&query, struct_type, &mut arguments.iter());
+    assert!(f.is_err());
+}
+
+#[test]
+fn named_placeholder() {
+    use query_macro::QueryMacro;
+    let input = "User, \"prop eq :value\""; // named placeholders are not supported in macro
+    let m = syn::parse_str(input);
+    assert!(m.is_ok());
+    let QueryMacro {
+        query,
+        struct_type,
+        arguments,
+    } = m.unwrap();
+    let f = query_macro::parse(&query, struct_type, &mut arguments.iter());
+    assert!(f.is_err());
+}
+
 #[test]
 fn too_many_arguments() {
     use query_macro::QueryMacro;
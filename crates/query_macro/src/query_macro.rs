@@ -2,6 +2,7 @@ use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::{Expr, Ident, LitStr, Result, Token, Type};
 
+use chrono::{NaiveDate, NaiveDateTime};
 use heck::SnakeCase;
 use proc_macro2::TokenStream;
 use toql_query_parser::PestQueryParser;
@@ -56,13 +57,16 @@ enum Concatenation {
 struct FieldInfo {
     pub sort: TokenStream,
     pub hidden: TokenStream,
+    pub quantifier: TokenStream,
     pub field: TokenStream,
     pub args: Vec<TokenStream>,
+    pub field_refs: Vec<(usize, String)>,
     pub single_array_argument: bool,
     pub name: String,
     pub filter_name: Option<String>,
     pub token_type: TokenType,
     pub concat: Concatenation,
+    pub negated: bool,
 }
 
 impl FieldInfo {
@@ -70,15 +74,18 @@ impl FieldInfo {
         FieldInfo {
             sort: quote!(),
             hidden: quote!(),
+            quantifier: quote!(),
             field: quote!(),
             single_array_argument: false,
             args: Vec::new(),
+            field_refs: Vec::new(),
 
             name: String::new(),
             filter_name: None,
 
             token_type: TokenType::Unknown,
             concat: Concatenation::And,
+            negated: false,
         }
     }
 
@@ -93,9 +100,14 @@ impl FieldInfo {
                 // let reserved = Ident::new("r#", Span::call_site());
                 let sort = &self.sort;
                 let hidden = &self.hidden;
+                let quantifier = &self.quantifier;
                 let filter = self.filter();
+                let field_refs = self
+                    .field_refs
+                    .iter()
+                    .map(|(i, name)| quote!(.field_ref(#i, #name)));
                 Some(
-                    quote!(<#struct_type as toql::query_fields::QueryFields>::fields(). #(#fnname()).*  #sort #hidden #filter),
+                    quote!(<#struct_type as toql::query_fields::QueryFields>::fields(). #(#fnname()).*  #sort #hidden #filter #(#field_refs)* #quantifier),
                 )
             }
             TokenType::Wildcard => Some(if self.name.is_empty() {
@@ -163,6 +175,12 @@ impl FieldInfo {
             TokenType::Unknown => None,
         };
 
+        let token = if self.negated {
+            token.map(|t| quote!(toql::query::Query::<#struct_type>::from(#t).not()))
+        } else {
+            token
+        };
+
         match token {
             Some(token) => {
                 if self.concat == Concatenation::And {
@@ -189,6 +207,12 @@ impl FieldInfo {
                     "LT" => quote!(.lt(#(#args),*)),
                     "LE" => quote!(.le(#(#args),*)),
                     "LK" => quote!(.lk(#(#args),*)),
+                    "RE" => quote!(.re(#(#args),*)),
+                    "ILK" => quote!(.ilk(#(#args),*)),
+                    "NLK" => quote!(.nlk(#(#args),*)),
+                    "SW" => quote!(.sw(#(#args),*)),
+                    "CT" => quote!(.ct(#(#args),*)),
+                    "EW" => quote!(.ew(#(#args),*)),
                     "IN" => {
                         if self.single_array_argument {
                             quote!(.ins( #(#args),* ))
@@ -273,6 +297,19 @@ fn evaluate_pair(
         let span = pair.clone().as_span();
 
         match pair.as_rule() {
+            Rule::negation => {
+                field_info.negated = true;
+            }
+            Rule::lpar if field_info.negated => {
+                let content = evaluate_pair(pairs, struct_type, query_args)?;
+                let group = quote!(toql::query::Query::<#struct_type>::new() #content .parenthesize().not());
+                output_stream.extend(if field_info.concat == Concatenation::And {
+                    quote!( .and(#group))
+                } else {
+                    quote!( .or(#group))
+                });
+                field_info.negated = false;
+            }
             Rule::lpar => {
                 let content = evaluate_pair(pairs, struct_type, query_args)?;
                 output_stream.extend(if field_info.concat == Concatenation::And {
@@ -295,6 +332,22 @@ fn evaluate_pair(
             Rule::hidden => {
                 field_info.hidden = quote!(.hide());
             }
+            Rule::nulls => {
+                field_info
+                    .sort
+                    .extend(if span.as_str().to_uppercase().ends_with("FIRST") {
+                        quote!(.nulls_first())
+                    } else {
+                        quote!(.nulls_last())
+                    });
+            }
+            Rule::quantifier => {
+                field_info.quantifier = match span.as_str().to_uppercase().as_str() {
+                    "ANY" => quote!(.any()),
+                    "ALL" => quote!(.all()),
+                    _ => quote!(.none()),
+                };
+            }
 
             Rule::wildcard => {
                 field_info.name = span
@@ -332,13 +385,35 @@ fn evaluate_pair(
                 field_info.args.push(quote!(#v));
             }
             Rule::string => {
-                let v = span
-                    .as_str()
-                    .trim_start_matches('\'')
-                    .trim_end_matches('\'')
-                    .replace("''", "'");
+                let s = span.as_str();
+                let v = s[1..s.len() - 1].replace("''", "'"); // Remove enclosing quotes
                 field_info.args.push(quote!(#v));
             }
+            Rule::boolean => {
+                let v = span.as_str().eq_ignore_ascii_case("true");
+                field_info.args.push(quote!(#v));
+            }
+            Rule::null => {
+                field_info.args.push(quote!(toql::sql_arg::SqlArg::Null));
+            }
+            Rule::date => {
+                // Dates are passed like chrono dates as strings
+                let s = span.as_str();
+                let v = if s.contains('T') {
+                    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+                        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M"))
+                        .map(|d| d.to_string())
+                } else {
+                    NaiveDate::parse_from_str(s, "%Y-%m-%d").map(|d| d.to_string())
+                };
+                match v {
+                    Ok(v) => field_info.args.push(quote!(#v)),
+                    Err(_) => {
+                        let error = format!("Invalid date `{}`.", s);
+                        return Err(quote!(compile_error!(#error)));
+                    }
+                }
+            }
             Rule::num_placeholder => {
                 field_info.single_array_argument = true; // first argument contains whole array
                 let v = query_args.next();
@@ -349,6 +424,19 @@ fn evaluate_pair(
                     }
                 }
             }
+            Rule::named_placeholder => {
+                return Err(quote!(compile_error!(
+                    "Named placeholders are bound at runtime with `Query::bind`, use `?` in query macro"
+                )));
+            }
+            Rule::field_ref => {
+                let name = span.as_str().trim_start_matches('$').to_string();
+                field_info.field_refs.push((field_info.args.len(), name));
+                field_info.args.push(quote!(toql::sql_arg::SqlArg::Null));
+            }
+            Rule::distinct => {
+                output_stream.extend(quote!(.distinct()));
+            }
             Rule::selection_clause => {
                 field_info.token_type = TokenType::Selection;
             }
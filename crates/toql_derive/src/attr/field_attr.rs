@@ -1,4 +1,6 @@
-use super::literals::{parse_lit_str, set_unique_bool, set_unique_path_lit, set_unique_str_lit};
+use super::literals::{
+    parse_lit_str, set_unique_bool, set_unique_path_lit, set_unique_str_lit, set_unique_u64_lit,
+};
 use crate::{
     error::{attribute_err, DeriveError},
     parsed::field::{
@@ -23,11 +25,14 @@ pub(crate) struct FieldAttr {
     pub(crate) join: Option<JoinAttr>,
     pub(crate) merge: Option<MergeAttr>,
     pub(crate) sql: Option<String>,
+    pub(crate) count_of: Option<String>,
     pub(crate) column: Option<String>,
     pub(crate) handler: Option<Path>,
     pub(crate) aux_params: HashMap<String, String>,
     pub(crate) foreign_key: Option<bool>,
     pub(crate) key: Option<bool>,
+    pub(crate) created_at: Option<bool>,
+    pub(crate) updated_at: Option<bool>,
 }
 
 impl FieldAttr {
@@ -43,11 +48,14 @@ impl FieldAttr {
             join: None,
             merge: None,
             sql: None,
+            count_of: None,
             column: None,
             handler: None,
             aux_params: HashMap::new(),
             foreign_key: None,
             key: None,
+            created_at: None,
+            updated_at: None,
         }
     }
 }
@@ -64,6 +72,8 @@ pub struct MergeAttr {
     pub columns: Vec<MergeMatch>,
     pub join_sql: Option<String>,
     pub on_sql: Option<String>,
+    pub order: Option<String>,
+    pub limit: Option<u64>,
 }
 
 impl FieldAttr {
@@ -76,10 +86,13 @@ impl FieldAttr {
             "skip_mut",
             "skip_wildcard",
             "sql",
+            "count_of",
             "column",
             "handler",
             "foreign_key",
             "key",
+            "created_at",
+            "updated_at",
             "roles",
             "aux_params",
             "join",
@@ -111,6 +124,12 @@ impl FieldAttr {
                             // For fields or joins
                             set_unique_bool(&mut self.key, ident, true)?;
                         }
+                        "created_at" => {
+                            set_unique_bool(&mut self.created_at, ident, true)?;
+                        }
+                        "updated_at" => {
+                            set_unique_bool(&mut self.updated_at, ident, true)?;
+                        }
                         "join" => {
                             // Shorthand for join
                             self.join = Some(JoinAttr::default());
@@ -137,6 +156,9 @@ impl FieldAttr {
                         "sql" => {
                             set_unique_str_lit(&mut self.sql, ident, &lit)?;
                         }
+                        "count_of" => {
+                            set_unique_str_lit(&mut self.count_of, ident, &lit)?;
+                        }
                         "column" => {
                             set_unique_str_lit(&mut self.column, ident, &lit)?;
                         }
@@ -264,7 +286,7 @@ impl FieldAttr {
         nested_metas: impl Iterator<Item = syn::NestedMeta>,
         merge_attr: &mut MergeAttr,
     ) -> Result<()> {
-        const KEYWORDS: &[&str] = &["on_sql", "columns", "join_sql"];
+        const KEYWORDS: &[&str] = &["on_sql", "columns", "join_sql", "order", "limit"];
 
         for meta in nested_metas {
             if let syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
@@ -281,6 +303,12 @@ impl FieldAttr {
                         "join_sql" => {
                             set_unique_str_lit(&mut merge_attr.join_sql, ident, &lit)?;
                         }
+                        "order" => {
+                            set_unique_str_lit(&mut merge_attr.order, ident, &lit)?;
+                        }
+                        "limit" => {
+                            set_unique_u64_lit(&mut merge_attr.limit, ident, &lit)?;
+                        }
 
                         keyword => {
                             return Err(attribute_err(ident.span(), keyword, KEYWORDS));
@@ -510,6 +538,8 @@ mod test {
             "skip_wildcard",
             "foreign_key",
             "key",
+            "created_at",
+            "updated_at",
         ];
 
         for keyword in keywords {
@@ -574,6 +604,33 @@ mod test {
         field_attr.parse_field_meta(once(meta)).unwrap();
         assert!(field_attr.key.is_some());
     }
+    #[test]
+    fn parse_timestamps() {
+        // Succesful case
+        let mut field_attr = create_field();
+        let meta = syn::parse_str::<NestedMeta>("created_at").unwrap();
+        field_attr.parse_field_meta(once(meta)).unwrap();
+        let meta = syn::parse_str::<NestedMeta>("updated_at").unwrap();
+        field_attr.parse_field_meta(once(meta)).unwrap();
+        assert!(field_attr.created_at.is_some());
+        assert!(field_attr.updated_at.is_some());
+    }
+
+    #[test]
+    fn parse_count_of() {
+        // Succesful case
+        let input = r#"count_of="comments""#;
+        let mut field_attr = create_field();
+        let meta = syn::parse_str::<NestedMeta>(input).unwrap();
+        field_attr.parse_field_meta(once(meta)).unwrap();
+        assert_eq!(field_attr.count_of, Some("comments".to_string()));
+
+        // Missing argument
+        let input = r#"count_of"#;
+        let mut field_attr = create_field();
+        let meta = syn::parse_str::<NestedMeta>(input).unwrap();
+        assert!(field_attr.parse_field_meta(once(meta)).is_err());
+    }
 
     #[test]
     fn parse_sql() {
@@ -780,6 +837,26 @@ mod test {
         assert_eq!(column.this, "s1".to_string());
         assert_eq!(column.other, MergeColumn::Aliased("a.o2".to_string()));
 
+        // Order and limit
+        let input = r#"merge(order="created desc, id", limit=5)"#;
+        let mut field_attr = create_field();
+        let meta = syn::parse_str::<NestedMeta>(input).unwrap();
+        field_attr.parse_field_meta(once(meta)).unwrap();
+        let merge = field_attr.merge.unwrap();
+
+        assert_eq!(merge.order, Some("created desc, id".to_string()));
+        assert_eq!(merge.limit, Some(5));
+
+        // Duplicate limit
+        let input = r#"merge(limit=5, limit=6)"#;
+        let mut field_attr = create_field();
+        let meta = syn::parse_str::<NestedMeta>(input).unwrap();
+        let err = field_attr.parse_field_meta(once(meta)).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            DeriveError::AttributeDuplicate(Span::call_site()).to_string()
+        );
+
         // Duplicate on_sql
         let input = r#"merge(on_sql="pred1", on_sql="pred2")"#;
         let mut field_attr = create_field();
@@ -71,6 +71,14 @@ pub(crate) fn set_unique_usize_lit(
     }
     Ok(())
 }
+pub(crate) fn set_unique_u64_lit(value: &mut Option<u64>, ident: &Ident, lit: &Lit) -> Result<()> {
+    if value.is_some() {
+        return Err(DeriveError::AttributeDuplicate(ident.span()));
+    } else {
+        *value = Some(parse_lit_u64(lit)?);
+    }
+    Ok(())
+}
 pub(crate) fn set_unique_rename_case_lit(
     value: &mut Option<RenameCase>,
     ident: &Ident,
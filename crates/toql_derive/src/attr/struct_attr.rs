@@ -26,6 +26,8 @@ pub(crate) struct StructAttr {
     pub(crate) selections: HashMap<String, SelectionArg>,
     pub(crate) roles: StructRoles,
     pub(crate) handler: Option<Path>,
+    pub(crate) tenant: Option<String>,
+    pub(crate) aux_param: Option<String>,
 }
 
 impl StructAttr {
@@ -41,6 +43,8 @@ impl StructAttr {
             selections: HashMap::new(),
             roles: StructRoles::default(),
             handler: None,
+            tenant: None,
+            aux_param: None,
         }
     }
 
@@ -56,6 +60,8 @@ impl StructAttr {
             "predicate",
             "selection",
             "handler",
+            "tenant",
+            "aux_param",
         ];
 
         for meta in nested_meta {
@@ -92,6 +98,12 @@ impl StructAttr {
                         "handler" => {
                             set_unique_path_lit(&mut self.handler, ident, &lit)?;
                         }
+                        "tenant" => {
+                            set_unique_str_lit(&mut self.tenant, ident, &lit)?;
+                        }
+                        "aux_param" => {
+                            set_unique_str_lit(&mut self.aux_param, ident, &lit)?;
+                        }
                         keyword => {
                             return Err(attribute_err(ident.span(), keyword, KEYWORDS));
                         }
@@ -377,6 +389,29 @@ mod test {
         assert_eq!(struct_attr.auto_key, Some(true));
     }
     #[test]
+    fn parse_tenant() {
+        let mut struct_attr = create_struct();
+        let metas = [r#"tenant="tenant_id""#, r#"aux_param="tenant""#]
+            .iter()
+            .map(|m| syn::parse_str::<NestedMeta>(m).unwrap())
+            .collect::<Vec<_>>();
+        struct_attr.parse_meta(metas.into_iter()).unwrap();
+        assert_eq!(struct_attr.tenant, Some("tenant_id".to_string()));
+        assert_eq!(struct_attr.aux_param, Some("tenant".to_string()));
+
+        // Duplicate
+        let mut struct_attr = create_struct();
+        let metas = [r#"tenant="tenant_id""#, r#"tenant="other_id""#]
+            .iter()
+            .map(|m| syn::parse_str::<NestedMeta>(m).unwrap())
+            .collect::<Vec<_>>();
+        let err = struct_attr.parse_meta(metas.into_iter()).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            DeriveError::AttributeDuplicate(Span::call_site()).to_string()
+        );
+    }
+    #[test]
     fn parse_handler() {
         // Succesful case
         let input = r#"handler="abc""#;
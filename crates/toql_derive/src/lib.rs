@@ -453,6 +453,56 @@ fn test_merge_types() {
     assert!(!parsed.to_token_stream().is_empty())
 }
 #[test]
+fn test_merge_order() {
+    use crate::parsed::field::merge_field::MergeOrder;
+
+    let input = r#"
+    struct User {
+        #[toql(key)]
+        id: u64,
+        #[toql(merge(order = "created_at desc, id, text nulls last", limit = 5))]
+        merge1 : Vec<Other>,
+    }"#;
+
+    let parsed = syn::parse_str::<ParsedStruct>(input).unwrap();
+    let merge_kind = parsed.fields.get(1).unwrap().kind.as_merge().unwrap();
+    assert_eq!(
+        merge_kind.order,
+        vec![
+            MergeOrder {
+                field: "createdAt".to_string(),
+                rust_field: "created_at".to_string(),
+                desc: true,
+                nulls_first: None
+            },
+            MergeOrder {
+                field: "id".to_string(),
+                rust_field: "id".to_string(),
+                desc: false,
+                nulls_first: None
+            },
+            MergeOrder {
+                field: "text".to_string(),
+                rust_field: "text".to_string(),
+                desc: false,
+                nulls_first: Some(false)
+            }
+        ]
+    );
+    assert_eq!(merge_kind.limit, Some(5));
+    assert!(!parsed.to_token_stream().is_empty());
+
+    // Invalid direction
+    let input = r#"
+    struct User {
+        #[toql(key)]
+        id: u64,
+        #[toql(merge(order = "created down"))]
+        merge1 : Vec<Other>,
+    }"#;
+    assert!(syn::parse_str::<ParsedStruct>(input).is_err());
+}
+#[test]
 fn test_roles() {
     let input = r#"
     #[toql(roles(insert="role3", delete="role3,role4"))]
@@ -1,7 +1,7 @@
 use super::{
     join_field::{JoinField, JoinSelection},
-    merge_field::{MergeField, MergeSelection},
-    regular_field::{RegularField, RegularSelection, SqlTarget},
+    merge_field::{MergeField, MergeOrder, MergeSelection},
+    regular_field::{RegularField, RegularSelection, SqlTarget, Timestamp},
     type_info::TypeInfo,
 };
 use crate::{
@@ -129,6 +129,19 @@ pub(crate) fn build(
             .rename_str(&struct_attr.name.to_string());
         let join_alias = sql_join_table_name.to_snake_case();
 
+        let order = match &merge_attr.order {
+            Some(o) => MergeOrder::parse_list(o).ok_or_else(|| {
+                DeriveError::Custom(
+                    field_attr.name.span(),
+                    format!(
+                        "merge order `{}` is invalid. Use a list like `created desc, id`.",
+                        o
+                    ),
+                )
+            })?,
+            None => Vec::new(),
+        };
+
         Ok(FieldKind::Merge(MergeField {
             sql_join_table_name,
             join_alias,
@@ -136,6 +149,8 @@ pub(crate) fn build(
             join_sql: merge_attr.join_sql.clone(),
             on_sql: merge_attr.on_sql.clone(),
             selection,
+            order,
+            limit: merge_attr.limit,
         }))
     } else {
         let selection = match (
@@ -153,16 +168,18 @@ pub(crate) fn build(
             return Err(DeriveError::OptionalKey(field_attr.type_path.span()));
         }
 
-        let default_inverse_column = if field_attr.sql.is_some() {
+        let default_inverse_column = if field_attr.sql.is_some() || field_attr.count_of.is_some() {
             None
         } else {
-            let table_name = struct_attr.table.clone().unwrap_or_else(|| {
-                struct_attr
+            // Templated table names, such as `<schema>.Order`, fall back to the struct name
+            let table_name = match &struct_attr.table {
+                Some(table) if !table.contains('<') => table.clone(),
+                _ => struct_attr
                     .tables
                     .as_ref()
                     .unwrap_or(&RenameCase::CamelCase)
-                    .rename_str(&struct_attr.name.to_string())
-            });
+                    .rename_str(&struct_attr.name.to_string()),
+            };
             Some(
                 struct_attr
                     .columns
@@ -177,6 +194,8 @@ pub(crate) fn build(
         };
         let sql_target = if let Some(sql) = &field_attr.sql {
             SqlTarget::Expression(sql.clone())
+        } else if let Some(merge_name) = &field_attr.count_of {
+            SqlTarget::CountOf(merge_name.clone())
         } else {
             SqlTarget::Column(match &field_attr.column {
                 Some(string) => string.to_owned(),
@@ -188,6 +207,14 @@ pub(crate) fn build(
             })
         };
 
+        let timestamp = if field_attr.created_at.unwrap_or_default() {
+            Some(Timestamp::Created)
+        } else if field_attr.updated_at.unwrap_or_default() {
+            Some(Timestamp::Updated)
+        } else {
+            None
+        };
+
         // Build regular field
         Ok(FieldKind::Regular(RegularField {
             sql_target,
@@ -198,6 +225,7 @@ pub(crate) fn build(
             foreign_key: field_attr.foreign_key.unwrap_or_default(),
             selection,
             skip_wildcard: field_attr.skip_wildcard.unwrap_or_default(),
+            timestamp,
         }))
     }
 }
@@ -15,11 +15,14 @@ pub(crate) fn check_skipped_attr_integrity(field_attr: &FieldAttr) -> Result<()>
         || field_attr.roles.update.is_some()
         || field_attr.preselect.is_some()
         || field_attr.sql.is_some()
+        || field_attr.count_of.is_some()
         || field_attr.column.is_some()
         || field_attr.handler.is_some()
         || !field_attr.aux_params.is_empty()
         || field_attr.foreign_key.unwrap_or_default()
         || field_attr.key.unwrap_or_default()
+        || field_attr.created_at.unwrap_or_default()
+        || field_attr.updated_at.unwrap_or_default()
     {
         return Err(DeriveError::Custom(
             field_attr.name.span(),
@@ -67,6 +70,36 @@ pub(crate) fn check_regular_attr_integrity(
             "`column` and `sql` are not allowed together.".to_string(),
         ));
     }
+    if field_attr.count_of.is_some()
+        && (field_attr.key.unwrap_or_default()
+            || field_attr.sql.is_some()
+            || field_attr.column.is_some())
+    {
+        return Err(DeriveError::Custom(
+            field_attr.name.span(),
+            "`count_of` is not allowed together with `key`, `sql` or `column`.".to_string(),
+        ));
+    }
+    let created_at = field_attr.created_at.unwrap_or_default();
+    let updated_at = field_attr.updated_at.unwrap_or_default();
+    if created_at && updated_at {
+        return Err(DeriveError::Custom(
+            field_attr.name.span(),
+            "`created_at` and `updated_at` are not allowed together.".to_string(),
+        ));
+    }
+    if (created_at || updated_at)
+        && (field_attr.key.unwrap_or_default()
+            || field_attr.foreign_key.unwrap_or_default()
+            || field_attr.sql.is_some()
+            || field_attr.count_of.is_some())
+    {
+        return Err(DeriveError::Custom(
+            field_attr.name.span(),
+            "`created_at` and `updated_at` are not allowed together with `key`, `foreign_key`, `sql` or `count_of`."
+                .to_string(),
+        ));
+    }
 
     Ok(())
 }
@@ -85,6 +118,12 @@ pub(crate) fn check_join_attr_integrity(
             "`sql` not allowed for joins.".to_string(),
         ));
     }
+    if field_attr.created_at.unwrap_or_default() || field_attr.updated_at.unwrap_or_default() {
+        return Err(DeriveError::Custom(
+            field_attr.name.span(),
+            "`created_at` and `updated_at` are only allowed for regular fields.".to_string(),
+        ));
+    }
     if field_attr.skip_wildcard.unwrap_or_default() && field_attr.preselect.unwrap_or_default() {
         return Err(DeriveError::Custom(
             field_attr.name.span(),
@@ -137,6 +176,12 @@ pub(crate) fn check_merge_attr_integrity(
             "`sql` not allowed for merged fields. ".to_string(),
         ));
     }
+    if field_attr.created_at.unwrap_or_default() || field_attr.updated_at.unwrap_or_default() {
+        return Err(DeriveError::Custom(
+            field_attr.name.span(),
+            "`created_at` and `updated_at` are only allowed for regular fields.".to_string(),
+        ));
+    }
     if field_attr.preselect.unwrap_or_default() {
         return Err(DeriveError::Custom(
             field_attr.name.span(),
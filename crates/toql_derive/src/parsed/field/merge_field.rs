@@ -1,3 +1,5 @@
+use heck::{MixedCase, SnakeCase};
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum MergeColumn {
     Aliased(String),
@@ -16,6 +18,49 @@ pub enum MergeSelection {
     Preselect,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeOrder {
+    pub field: String,      // Toql field name
+    pub rust_field: String, // Rust field name of the merged struct
+    pub desc: bool,
+    pub nulls_first: Option<bool>,
+}
+
+impl MergeOrder {
+    // Parse order like `created desc nulls last, id`
+    pub fn parse_list(order: &str) -> Option<Vec<MergeOrder>> {
+        let mut orders = Vec::new();
+        for o in order.split(',') {
+            let parts = o
+                .split_whitespace()
+                .map(|p| p.to_ascii_lowercase())
+                .collect::<Vec<_>>();
+            let (_, rest) = parts.split_first()?;
+            let name = o.split_whitespace().next()?; // Keep case of field name
+            let field = name.to_mixed_case();
+            let rust_field = name.to_snake_case();
+            let (desc, rest) = match rest.split_first() {
+                Some((d, r)) if d == "asc" => (false, r),
+                Some((d, r)) if d == "desc" => (true, r),
+                _ => (false, rest),
+            };
+            let nulls_first = match rest {
+                [] => None,
+                [n, p] if n == "nulls" && p == "first" => Some(true),
+                [n, p] if n == "nulls" && p == "last" => Some(false),
+                _ => return None,
+            };
+            orders.push(MergeOrder {
+                field,
+                rust_field,
+                desc,
+                nulls_first,
+            });
+        }
+        Some(orders)
+    }
+}
+
 #[derive(Debug)]
 pub struct MergeField {
     pub sql_join_table_name: String,
@@ -24,4 +69,6 @@ pub struct MergeField {
     pub join_sql: Option<String>,
     pub on_sql: Option<String>,
     pub selection: MergeSelection,
+    pub order: Vec<MergeOrder>,
+    pub limit: Option<u64>,
 }
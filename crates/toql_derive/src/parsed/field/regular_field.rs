@@ -5,6 +5,7 @@ use syn::Path;
 pub enum SqlTarget {
     Column(String),
     Expression(String),
+    CountOf(String), // Rust name of merge field
 }
 
 #[derive(Debug, PartialEq)]
@@ -19,6 +20,14 @@ pub enum RegularSelection {
     PreselectNullable,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum Timestamp {
+    // #[toql(created_at)]
+    Created,
+    // #[toql(updated_at)]
+    Updated,
+}
+
 #[derive(Debug)]
 pub struct RegularField {
     pub sql_target: SqlTarget,
@@ -29,4 +38,5 @@ pub struct RegularField {
     pub foreign_key: bool, // Column of this field is used as foreign key
     pub selection: RegularSelection,
     pub skip_wildcard: bool,
+    pub timestamp: Option<Timestamp>,
 }
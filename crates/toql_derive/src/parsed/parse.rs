@@ -1,6 +1,6 @@
 use super::{
-    field::field_kind::FieldKind, field::Field, parsed_struct::ParsedStruct,
-    rename_case::RenameCase,
+    field::field_kind::FieldKind, field::regular_field::SqlTarget, field::Field,
+    parsed_struct::ParsedStruct, rename_case::RenameCase,
 };
 use crate::attr::{field_attr::FieldAttr, struct_attr::StructAttr};
 use crate::error::DeriveError;
@@ -36,6 +36,22 @@ impl Parse for ParsedStruct {
         }
 
         check_key_integrity(&derive_input.ident, parsed_fields.iter())?;
+        check_count_of_integrity(&parsed_fields)?;
+
+        // Tenant aux param defaults to `tenant`
+        let tenant = match (struct_attr.tenant, struct_attr.aux_param) {
+            (Some(column), aux_param) => {
+                Some((column, aux_param.unwrap_or_else(|| String::from("tenant"))))
+            }
+            (None, Some(_)) => {
+                return Err(DeriveError::Custom(
+                    derive_input.ident.span(),
+                    "`aux_param` requires `tenant`.".to_string(),
+                )
+                .into())
+            }
+            (None, None) => None,
+        };
 
         // Table name is either user defined or calculated from struct name and renaming scheme
         let tables = struct_attr.tables.unwrap_or(RenameCase::CamelCase);
@@ -56,6 +72,7 @@ impl Parse for ParsedStruct {
             fields: parsed_fields,
             roles: struct_attr.roles,
             field_handler: struct_attr.handler,
+            tenant,
         })
     }
 }
@@ -133,3 +150,29 @@ pub(crate) fn check_key_integrity<'a>(
 
     Ok(())
 }
+
+/// Check that `count_of` refers to a preceding merge field
+/// - Merge must be mapped before the count
+pub(crate) fn check_count_of_integrity(fields: &[Field]) -> syn::Result<()> {
+    for (i, field) in fields.iter().enumerate() {
+        if let FieldKind::Regular(regular_kind) = &field.kind {
+            if let SqlTarget::CountOf(merge_name) = &regular_kind.sql_target {
+                let merge_field = fields[..i].iter().find(|f| {
+                    f.field_name.to_string().trim_start_matches("r#") == merge_name
+                        && matches!(f.kind, FieldKind::Merge(_))
+                });
+                if merge_field.is_none() {
+                    return Err(DeriveError::Custom(
+                        field.field_name.span(),
+                        format!(
+                            "`count_of` requires a preceding merge field `{}`.",
+                            merge_name
+                        ),
+                    )
+                    .into());
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use super::{
-    field::Field, predicate_arg::PredicateArg, rename_case::RenameCase, selection_arg::SelectionArg,
+    field::{regular_field::SqlTarget, Field},
+    predicate_arg::PredicateArg,
+    rename_case::RenameCase,
+    selection_arg::SelectionArg,
 };
 use std::collections::HashMap;
 use syn::{Ident, Path};
@@ -39,4 +42,16 @@ pub(crate) struct ParsedStruct {
     pub(crate) fields: Vec<Field>,
     //  Default field handler
     pub(crate) field_handler: Option<Path>,
+    // Tenant column and aux param with the tenant
+    pub(crate) tenant: Option<(String, String)>,
+}
+
+impl ParsedStruct {
+    /// Returns true, if the target is the tenant column
+    pub(crate) fn is_tenant_column(&self, sql_target: &SqlTarget) -> bool {
+        match (&self.tenant, sql_target) {
+            (Some((tenant_column, _)), SqlTarget::Column(column)) => tenant_column == column,
+            _ => false,
+        }
+    }
 }
@@ -195,6 +195,12 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
     } else {
         quote!()
     };
+    // Users may not depend on serde directly
+    let serde_crate = if cfg!(feature = "serde") {
+        quote!(#[serde(crate = "toql::serde")])
+    } else {
+        quote!()
+    };
 
     let key_constr_code = if key_constr_code.len() == 1 {
         let key_constr_code = key_constr_code.get(0).unwrap();
@@ -216,6 +222,7 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
 
     let key = quote! {
         #[derive(Debug, Eq, PartialEq, Hash, #serde Clone)]
+        #serde_crate
 
         #vis struct #struct_key_ident {
             #(#key_field_declarations),*
@@ -1,4 +1,4 @@
-use quote::quote;
+use quote::{quote, quote_spanned};
 
 use crate::parsed::{
     field::{
@@ -171,7 +171,7 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                 field_mappings.push(quote! {
                     mapper.map_join_with_options(#toql_field_name, #sql_join_mapper_name,
                     #join_type,
-                    {let mut t = toql::sql_expr::SqlExpr::literal(< #sql_join_table_name_ident as toql::table_mapper::mapped::Mapped>::table_name()); t.push_literal(" "); t.push_other_alias(); t }, 
+                    {let mut t = toql::sql_expr::SqlExpr::table(< #sql_join_table_name_ident as toql::table_mapper::mapped::Mapped>::table_name()); t.push_literal(" "); t.push_other_alias(); t }, 
                     { let mut t = toql::sql_expr::SqlExpr::new(); #join_predicate; t },
                      toql::table_mapper::join_options::JoinOptions::new() #(#aux_params)*
                      #preselect_ident #key_ident #skip_mut_ident #load_restriction_code #partial_table_ident #handler
@@ -225,6 +225,22 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                 };
 
                 field_mappings.push( match &regular_kind.sql_target {
+                    SqlTarget::CountOf(ref merge_name) => {
+                        let merge_field = parsed_struct.fields.iter().find(|f| {
+                            f.field_name.to_string().trim_start_matches("r#") == merge_name
+                        });
+                        if let Some(merge_field) = merge_field {
+                            let merge_toql_name = &merge_field.toql_query_name;
+                            let merged_type = &merge_field.field_base_type;
+                            quote! {
+                                mapper.map_count_of_with_options::<#merged_type>( #toql_field_name, #merge_toql_name,
+                                toql::table_mapper::field_options::FieldOptions::new() #(#aux_params)*
+                                #preselect_ident #skip_wc_ident #load_restriction_code #handler)?;
+                            }
+                        } else {
+                            quote!()
+                        }
+                    }
                     SqlTarget::Expression(ref expression) => {
                         quote! {
                             mapper.map_expr_with_options( #toql_field_name,  toql::sql_expr_macro::sql_expr!( #expression),
@@ -252,7 +268,7 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                 let table_name = &parsed_struct.table;
                 let table_join = quote!(toql::sql_expr::SqlExpr::from(vec![
                      toql::sql_expr::SqlExprToken::Literal("JOIN ".to_string()),
-                     toql::sql_expr::SqlExprToken::Literal(#table_name.to_string()),
+                     toql::sql_expr::SqlExprToken::Table(#table_name.to_string()),
                      toql::sql_expr::SqlExprToken::Literal(" ".to_string()),
                      toql::sql_expr::SqlExprToken::SelfAlias
                 ]));
@@ -339,11 +355,40 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                     quote!()
                 };
 
+                let order_code = merge_kind.order.iter().enumerate().map(|(i, o)| {
+                    let field = &o.field;
+                    let ordinal = (i + 1) as u8;
+                    let order = if o.desc {
+                        quote!(toql::query::field_order::FieldOrder::Desc(#ordinal))
+                    } else {
+                        quote!(toql::query::field_order::FieldOrder::Asc(#ordinal))
+                    };
+                    match o.nulls_first {
+                        Some(true) => quote!( .order_with_nulls(#field, #order, toql::query::field_order::NullsOrder::First)),
+                        Some(false) => quote!( .order_with_nulls(#field, #order, toql::query::field_order::NullsOrder::Last)),
+                        None => quote!( .order(#field, #order)),
+                    }
+                });
+                // Order fields must be regular fields of the merged struct.
+                // Calling their field methods fails to compile otherwise.
+                let order_checks = merge_kind.order.iter().map(|o| {
+                    let field_base_type = &field.field_base_type;
+                    let method = syn::Ident::new(&o.rust_field, field.field_name.span());
+                    quote_spanned!(field.field_name.span() =>
+                        let _: toql::query::field::Field = <#field_base_type as toql::query_fields::QueryFields>::fields(). #method ();
+                    )
+                });
+                let limit_code = match merge_kind.limit {
+                    Some(l) => quote!( .limit(#l)),
+                    None => quote!(),
+                };
+
                 field_mappings.push(quote! {
+                        let _ = || { #(#order_checks)* };
                         mapper.map_merge_with_options(#toql_field_name, #sql_merge_mapper_name,
                            {#join_statement},
                             { #join_predicate },
-                            toql::table_mapper::merge_options::MergeOptions::new() #preselect_ident #load_restriction_code
+                            toql::table_mapper::merge_options::MergeOptions::new() #preselect_ident #load_restriction_code #(#order_code)* #limit_code
                             );
                 });
             }
@@ -360,6 +405,18 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
         None => quote!(),
     };
 
+    let (tenant_code, tenant_fn_code) = match &parsed_struct.tenant {
+        Some((column, aux_param)) => (
+            quote!(mapper.map_tenant(#column, #aux_param); ),
+            quote!(
+                fn tenant() -> Option<(String, String)> {
+                    Some((String::from(#column), String::from(#aux_param)))
+                }
+            ),
+        ),
+        None => (quote!(), quote!()),
+    };
+
     let sql_table_name = &parsed_struct.table;
     let sql_table_alias = parsed_struct.table.to_snake_case();
 
@@ -377,6 +434,7 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
             fn table_alias() -> String {
                 String::from(#sql_table_alias)
             }
+            #tenant_fn_code
             #[allow(redundant_semicolons)]
             fn map(mapper: &mut toql::table_mapper::TableMapper) -> toql::result::Result<()>{
 
@@ -386,6 +444,7 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
 
                 #load_role_code
                 #delete_role_code
+                #tenant_code
                 Ok(())
             }
         }
@@ -402,6 +461,9 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
             fn table_alias() -> String {
                 <#struct_name_ident as toql::table_mapper::mapped::Mapped>::table_alias()
             }
+            fn tenant() -> Option<(String, String)> {
+                <#struct_name_ident as toql::table_mapper::mapped::Mapped>::tenant()
+            }
             fn map(mapper: &mut toql::table_mapper::TableMapper) -> toql::result::Result<()>{
                 <#struct_name_ident as toql::table_mapper::mapped::Mapped>::map(mapper)
             }
@@ -419,6 +481,9 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
             fn table_alias() -> String {
                 <#struct_name_ident as toql::table_mapper::mapped::Mapped>::table_alias()
             }
+            fn tenant() -> Option<(String, String)> {
+                <#struct_name_ident as toql::table_mapper::mapped::Mapped>::tenant()
+            }
             fn map(mapper: &mut toql::table_mapper::TableMapper) -> toql::result::Result<()>{
                 <#struct_name_ident as toql::table_mapper::mapped::Mapped>::map(mapper)
             }
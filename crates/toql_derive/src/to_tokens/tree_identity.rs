@@ -3,7 +3,7 @@ use crate::parsed::{
         field_kind::FieldKind,
         join_field::JoinSelection,
         merge_field::{MergeColumn, MergeSelection},
-        regular_field::SqlTarget,
+        regular_field::{RegularSelection, SqlTarget, Timestamp},
     },
     parsed_struct::ParsedStruct,
 };
@@ -12,8 +12,12 @@ use proc_macro2::TokenStream;
 pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream) {
     let mut dispatch_auto_id_code = Vec::new();
     let mut dispatch_identity_code = Vec::new();
+    let mut dispatch_fill_code = Vec::new();
     let mut identity_set_merges_key_code = Vec::new();
     let mut key_columns = Vec::new();
+    let mut stamp_insert_code = Vec::new();
+    let mut stamp_update_code = Vec::new();
+    let mut fill_tenant_code = Vec::new();
     let mut number_of_keys = 0;
 
     let struct_name_ident = &parsed_struct.struct_name;
@@ -34,44 +38,59 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                         key_columns.push(column.to_string())
                     }
                 }
+                if parsed_struct.is_tenant_column(&field_attrs.sql_target) {
+                    fill_tenant_code.push(setter_code(
+                        field_ident,
+                        &field_attrs.selection,
+                        quote!(tenant),
+                    ));
+                }
+                // Timestamps of `skip_mut` fields are never written
+                if let (Some(timestamp), false) = (&field_attrs.timestamp, field.skip_mut) {
+                    let setter_code = setter_code(field_ident, &field_attrs.selection, quote!(now));
+                    if timestamp == &Timestamp::Updated {
+                        stamp_update_code.push(setter_code.clone());
+                    }
+                    stamp_insert_code.push(setter_code);
+                }
             }
             FieldKind::Join(join_attrs) => {
                 if join_attrs.key {
                     number_of_keys += 1;
                 }
 
-                dispatch_identity_code.push(
-                    match join_attrs.selection {
-                         JoinSelection::SelectLeft => {
-                            quote!(
-                                #toql_query_name => {
-                                    if let Some(e) = self. #field_ident .as_mut() {
-                                        if let Some(e2) = e .as_mut() {
-                                            toql::tree::tree_identity::TreeIdentity::set_id(e2, descendents, action)?;
-                                        }
+                let dispatch_code = |method: TokenStream| match join_attrs.selection {
+                    JoinSelection::SelectLeft => {
+                        quote!(
+                            #toql_query_name => {
+                                if let Some(e) = self. #field_ident .as_mut() {
+                                    if let Some(e2) = e .as_mut() {
+                                        toql::tree::tree_identity::TreeIdentity::#method(e2, descendents, action)?;
                                     }
                                 }
-                            )
-                         }
+                            }
+                        )
+                    }
 
-                        JoinSelection::PreselectInner => {
-                            quote!(
-                                #toql_query_name => {
-                                    toql::tree::tree_identity::TreeIdentity::set_id(&mut self. #field_ident, descendents, action)?;
-                                }
-                            )
-                        }
-                        JoinSelection::PreselectLeft | JoinSelection::SelectInner => {
-                            quote!(
-                                #toql_query_name => {
-                                    if let Some(e) = self. #field_ident. as_mut() {
-                                        toql::tree::tree_identity::TreeIdentity::set_id(e, descendents, action)?;
-                                    }
+                    JoinSelection::PreselectInner => {
+                        quote!(
+                            #toql_query_name => {
+                                toql::tree::tree_identity::TreeIdentity::#method(&mut self. #field_ident, descendents, action)?;
+                            }
+                        )
+                    }
+                    JoinSelection::PreselectLeft | JoinSelection::SelectInner => {
+                        quote!(
+                            #toql_query_name => {
+                                if let Some(e) = self. #field_ident. as_mut() {
+                                    toql::tree::tree_identity::TreeIdentity::#method(e, descendents, action)?;
                                 }
-                            )
-                        }
+                            }
+                        )
                     }
-                );
+                };
+                dispatch_identity_code.push(dispatch_code(quote!(set_id)));
+                dispatch_fill_code.push(dispatch_code(quote!(fill)));
                 dispatch_auto_id_code.push(
                    quote!(
                        #toql_query_name => {
@@ -139,29 +158,31 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                                 auto_id(descendents)?)
                         }
                 ));
-                match merge.selection {
+                let dispatch_code = |method: TokenStream| match merge.selection {
                     MergeSelection::Select => {
-                        dispatch_identity_code.push(quote!(
-                       #toql_query_name => {
-                           if let Some(fs) = self. #field_ident. as_mut() {
-                            for f in fs {
-                                toql::tree::tree_identity::TreeIdentity::set_id(f, descendents.clone(),  action.clone())?
-                            }
-                           }
-                        }
-                ));
+                        quote!(
+                               #toql_query_name => {
+                                   if let Some(fs) = self. #field_ident. as_mut() {
+                                    for f in fs {
+                                        toql::tree::tree_identity::TreeIdentity::#method(f, descendents.clone(),  action.clone())?
+                                    }
+                                   }
+                                }
+                        )
                     }
                     MergeSelection::Preselect => {
                         // Vec<T>
-                        dispatch_identity_code.push(quote!(
-                       #toql_query_name => {
-                            for f in &mut self. #field_ident  {
-                                toql::tree::tree_identity::TreeIdentity::set_id(f, descendents.clone(),  action.clone())?
-                            }
-                        }
-                ));
+                        quote!(
+                               #toql_query_name => {
+                                    for f in &mut self. #field_ident  {
+                                        toql::tree::tree_identity::TreeIdentity::#method(f, descendents.clone(),  action.clone())?
+                                    }
+                                }
+                        )
                     }
-                }
+                };
+                dispatch_identity_code.push(dispatch_code(quote!(set_id)));
+                dispatch_fill_code.push(dispatch_code(quote!(fill)));
 
                 let mut columns_merge = Vec::new();
                 for c in &merge.columns {
@@ -359,7 +380,8 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
             }
     );
 
-    let identity_set_key = quote!( #identity_set_self_key_code
+    let identity_set_key = quote!(
+                    #identity_set_self_key_code
 
                     let self_key = toql::keyed::Keyed::key(&self);
                     let self_key_params = toql::key::Key::params(&self_key);
@@ -418,6 +440,51 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                     }
                     Ok(())
                 }
+
+             #[allow(unused_variables, unused_mut)]
+             fn fill < 'a, 'b, I >(&mut self, mut descendents : I, action: &'b toql::tree::tree_identity::FillAction)
+                        -> std :: result :: Result < (), toql::error::ToqlError >
+                        where I: Iterator<Item = toql::query::field_path::FieldPath<'a>> + Clone
+            {
+                     match descendents.next() {
+                           Some(d) => match d.as_str() {
+                               #(#dispatch_fill_code),*
+                               f @ _ => {
+                                    return Err(
+                                        toql::error::ToqlError::SqlBuilderError (
+                                         toql::sql_builder::sql_builder_error::SqlBuilderError::FieldMissing(f.to_string()))
+                                        .into());
+                                }
+                           },
+                           None => {
+                             match action {
+                                 toql::tree::tree_identity::FillAction::Tenant(tenant) => {
+                                     #(#fill_tenant_code)*
+                                 }
+                                 toql::tree::tree_identity::FillAction::StampInsert(now) => {
+                                     #(#stamp_insert_code)*
+                                 }
+                                 toql::tree::tree_identity::FillAction::StampInsertInvalid(now) => {
+                                     let self_key = toql::keyed::Keyed::key(&self);
+                                     let self_key_params = toql::key::Key::params(&self_key);
+                                     if !toql::sql_arg::valid_key(&self_key_params) {
+                                         #(#stamp_insert_code)*
+                                     }
+                                 }
+                                 toql::tree::tree_identity::FillAction::StampUpdate(now) => {
+                                     // Entities with invalid keys are not updated
+                                     let self_key = toql::keyed::Keyed::key(&self);
+                                     let self_key_params = toql::key::Key::params(&self_key);
+                                     if toql::sql_arg::valid_key(&self_key_params) {
+                                         #(#stamp_update_code)*
+                                     }
+                                 }
+                                 _ => {}
+                             }
+                           }
+                    }
+                    Ok(())
+                }
            }
             impl toql::tree::tree_identity::TreeIdentity for &mut #struct_name_ident {
              #[allow(unused_mut)]
@@ -432,6 +499,13 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                  where I: Iterator<Item = toql::query::field_path::FieldPath<'a>> + Clone
             {
                  toql::tree::tree_identity::TreeIdentity::set_id(*self, descendents, action)
+            }
+              #[allow(unused_mut)]
+             fn fill < 'a, 'b, I >(&mut self, mut descendents : I, action: &'b toql::tree::tree_identity::FillAction)
+                        -> std::result::Result<(),toql::error::ToqlError>
+                 where I: Iterator<Item = toql::query::field_path::FieldPath<'a>> + Clone
+            {
+                 toql::tree::tree_identity::TreeIdentity::fill(*self, descendents, action)
             }
             }
     };
@@ -439,3 +513,22 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
     log::debug!("Source code for `{}`:\n{}", struct_name, mods.to_string());
     tokens.extend(mods);
 }
+
+// Code to set a regular field from the `SqlArg` in `value`.
+fn setter_code(
+    field_ident: &syn::Ident,
+    selection: &RegularSelection,
+    value: TokenStream,
+) -> TokenStream {
+    match selection {
+        RegularSelection::SelectNullable => {
+            quote!(self. #field_ident = Some(if #value.is_null(){ None}else {Some(std::convert::TryInto::try_into(#value)?)});)
+        }
+        RegularSelection::PreselectNullable | RegularSelection::Select => {
+            quote!(self. #field_ident = if #value.is_null(){ None}else {Some(std::convert::TryInto::try_into(#value)?)};)
+        }
+        RegularSelection::Preselect => {
+            quote!(self. #field_ident = std::convert::TryInto::try_into(#value)?;)
+        }
+    }
+}
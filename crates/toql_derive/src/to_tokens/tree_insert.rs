@@ -30,15 +30,27 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                 if regular_kind.key && parsed_struct.auto_key {
                     continue;
                 }
+                // Tenant column is filled from aux param
+                if parsed_struct.is_tenant_column(&regular_kind.sql_target) {
+                    continue;
+                }
                 match regular_kind.sql_target {
                     SqlTarget::Column(ref sql_column) => insert_columns_code.push(quote!(
                                 e.push_literal(#sql_column);
                                 e.push_literal(", ");
                     )),
-                    SqlTarget::Expression(_) => {
+                    SqlTarget::Expression(_) | SqlTarget::CountOf(_) => {
                         continue;
                     }
                 }
+                // Timestamps are filled with the time of the clock or a database expression
+                if regular_kind.timestamp.is_some() {
+                    insert_values_code.push(quote!(
+                        values.push_aux_param(toql::clock::TIMESTAMP_AUX_PARAM);
+                        values.push_literal(", ");
+                    ));
+                    continue;
+                }
                 insert_values_code.push( match regular_kind.selection {
                     RegularSelection::SelectNullable => {
                         // Option<Option<T>> (toql selectable of nullable column)
@@ -242,6 +254,17 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
             }
         };
     }
+    if let Some((column, aux_param)) = &parsed_struct.tenant {
+        insert_columns_code.push(quote!(
+                    e.push_literal(#column);
+                    e.push_literal(", ");
+        ));
+        insert_values_code.push(quote!(
+                    values.push_aux_param(#aux_param);
+                    values.push_literal(", ");
+        ));
+    }
+
     let struct_name_ident = &parsed_struct.struct_name;
     let struct_name = parsed_struct.struct_name.to_string();
     let role_assert = if let Some(role_expr_string) = &parsed_struct.roles.insert {
@@ -3,7 +3,7 @@ use crate::parsed::{
         field_kind::FieldKind,
         join_field::JoinSelection,
         merge_field::MergeSelection,
-        regular_field::{RegularSelection, SqlTarget},
+        regular_field::{RegularSelection, SqlTarget, Timestamp},
     },
     parsed_struct::ParsedStruct,
 };
@@ -11,6 +11,7 @@ use proc_macro2::TokenStream;
 
 pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream) {
     let mut update_set_code = Vec::new();
+    let mut update_stamp_code = Vec::new();
     let mut dispatch_update_code = Vec::new();
 
     let struct_name = parsed_struct.struct_name.to_string();
@@ -57,10 +58,26 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
             FieldKind::Skipped => {}
             FieldKind::Regular(regular_kind) => {
                 // SQL code cannot be updated, skip field
-                if let SqlTarget::Expression(_) = regular_kind.sql_target {
+                if let SqlTarget::Expression(_) | SqlTarget::CountOf(_) = regular_kind.sql_target {
                     continue;
                 };
-                if regular_kind.key {
+                // Keys and tenant column cannot be updated
+                if regular_kind.key || parsed_struct.is_tenant_column(&regular_kind.sql_target) {
+                    continue;
+                }
+                // Timestamps are not taken from the entity,
+                // `updated_at` is set whenever another column is updated
+                if let Some(timestamp) = &regular_kind.timestamp {
+                    if let (Timestamp::Updated, SqlTarget::Column(sql_column)) =
+                        (timestamp, &regular_kind.sql_target)
+                    {
+                        update_stamp_code.push(quote!(
+                            expr.push_literal(#sql_column);
+                            expr.push_literal(" = ");
+                            expr.push_aux_param(toql::clock::TIMESTAMP_AUX_PARAM);
+                            expr.push_literal(", ");
+                        ));
+                    }
                     continue;
                 }
 
@@ -261,6 +278,15 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
     // Generate Stream
     let struct_name_ident = &parsed_struct.struct_name;
     let struct_name = parsed_struct.struct_name.to_string();
+    let update_tenant_code = match &parsed_struct.tenant {
+        Some((column, aux_param)) => quote!(
+            expr.push_literal(" AND ");
+            expr.push_literal(#column);
+            expr.push_literal(" = ");
+            expr.push_aux_param(#aux_param);
+        ),
+        None => quote!(),
+    };
     let mods = {
         let sql_table_name = &parsed_struct.table;
 
@@ -307,10 +333,13 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
 
                                     let mut expr = toql::sql_expr::SqlExpr::new();
                                     expr.push_literal("UPDATE ");
-                                    expr.push_literal(#sql_table_name);
+                                    expr.push_table(#sql_table_name);
                                     expr.push_literal(" SET ");
                                     let tokens = expr.tokens().len();
                                     #(#update_set_code)*
+                                    if expr.tokens().len() > tokens {
+                                        #(#update_stamp_code)*
+                                    }
 
                                    expr.pop(); // remove ', '
                                     if expr.tokens().len() > tokens {
@@ -320,6 +349,7 @@ pub(crate) fn to_tokens(parsed_struct: &ParsedStruct, tokens: &mut TokenStream)
                                         // Qualifierd column name
                                         let resolver = toql::sql_expr::resolver::Resolver::new().with_self_alias(#sql_table_name);
                                         expr.extend( resolver.alias_to_literals(&toql::key::Key::unaliased_predicate_expr(&key))?);
+                                        #update_tenant_code
                                         exprs.push(expr);
                                     }
 
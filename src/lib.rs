@@ -91,10 +91,14 @@ pub use toql_core::none_error;
 pub use toql_core::ok_or_fail; // Export macro (TODO: check for removal) // Export macro for macros
 
 pub use toql_core::backend;
+pub use toql_core::clock;
 pub use toql_core::field_handler;
+pub use toql_core::field_policy;
 pub use toql_core::join_handler;
+pub use toql_core::metrics;
 pub use toql_core::predicate_handler;
 pub use toql_core::query_fields;
+pub use toql_core::query_limits;
 pub use toql_core::query_parser;
 pub use toql_core::query_path;
 pub use toql_core::sql_builder;
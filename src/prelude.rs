@@ -2,7 +2,8 @@ pub use toql_core::alias_format::AliasFormat;
 pub use toql_core::backend::{context::Context, context_builder::ContextBuilder};
 pub use toql_core::cache::Cache;
 pub use toql_core::error::ToqlError;
-pub use toql_core::field_handler::{DefaultFieldHandler, FieldHandler};
+pub use toql_core::field_handler::{DefaultFieldHandler, FieldHandler, RegexSyntax};
+pub use toql_core::field_policy::{FieldOperation, FieldPolicy, OnViolation};
 pub use toql_core::from_row::FromRow;
 pub use toql_core::join::Join;
 pub use toql_core::join_handler::JoinHandler;
@@ -13,8 +14,12 @@ pub use toql_core::page::Page;
 pub use toql_core::page_counts::PageCounts;
 pub use toql_core::parameter_map::ParameterMap;
 pub use toql_core::predicate_handler::PredicateHandler;
-pub use toql_core::query::{field::Field, field_filter::FieldFilter, query_with::QueryWith, Query};
+pub use toql_core::query::{
+    field::Field, field_filter::FieldFilter, field_order::FieldOrder, query_folder::QueryFolder,
+    query_visitor::QueryVisitor, query_with::QueryWith, Query,
+};
 pub use toql_core::query_fields::QueryFields;
+pub use toql_core::query_limits::QueryLimits;
 pub use toql_core::query_parser::QueryParser;
 pub use toql_core::result::Result;
 pub use toql_core::sql::Sql;
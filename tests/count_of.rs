@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use pretty_assertions::assert_eq;
+use toql::mock_db::MockDb;
+use toql::prelude::{paths, query, Cache, ContextBuilder, Toql, ToqlApi};
+use toql::row;
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+pub struct Level1 {
+    #[toql(key)]
+    id: u64,
+    text: Option<String>,
+
+    #[toql(merge)]
+    level2: Option<Vec<Level2>>,
+
+    #[toql(count_of = "level2")]
+    level2_count: Option<u64>,
+
+    #[toql(
+        merge(
+            columns(self = "id", other = "la.level1_id"),
+            join_sql = "JOIN LevelAssoc la ON (la.level3_id = ...id)"
+        ),
+        roles(load = "admin")
+    )]
+    level3: Option<Vec<Level3>>,
+
+    #[toql(count_of = "level3")]
+    level3_count: Option<u64>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level2 {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    level1_id: u64,
+    text: String,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level3 {
+    #[toql(key)]
+    id: u64,
+    text: String,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn load() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "id, level2Count");
+    let select = "SELECT level1.id, \
+        (SELECT COUNT(*) FROM Level2 level1_level2 \
+        WHERE level1.id = level1_level2.level1_id) \
+        FROM Level1 level1";
+    toql.mock_rows(select, vec![row!(1u64, 3u64)]);
+    let r = toql.load_many(q).await.unwrap();
+    assert_eq!(toql.take_unsafe_sql(), select);
+    assert_eq!(r[0].level2_count, Some(3));
+    assert!(r[0].level2.is_none());
+
+    // Filter and order by count
+    let q = query!(Level1, "id, -1.level2Count gt 2");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id FROM Level1 level1 \
+        WHERE (SELECT COUNT(*) FROM Level2 level1_level2 \
+        WHERE level1.id = level1_level2.level1_id) > 2 \
+        ORDER BY (SELECT COUNT(*) FROM Level2 level1_level2 \
+        WHERE level1.id = level1_level2.level1_id) DESC"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn load_with_merge_roles() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    // Count requires load role of merge
+    let q = query!(Level1, "id, level3Count");
+    assert!(toql.load_many(q).await.is_err());
+
+    let mut roles = HashSet::new();
+    roles.insert("admin".to_string());
+    let context = ContextBuilder::new().with_roles(roles).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Level1, "id, level3Count");
+    let select = "SELECT level1.id, \
+        (SELECT COUNT(*) FROM Level3 level1_level3 \
+        JOIN LevelAssoc la ON (la.level3_id = level1_level3.id) \
+        WHERE level1.id = la.level1_id) \
+        FROM Level1 level1";
+    toql.mock_rows(select, vec![row!(1u64, 2u64)]);
+    let r = toql.load_many(q).await.unwrap();
+    assert_eq!(toql.take_unsafe_sql(), select);
+    assert_eq!(r[0].level3_count, Some(2));
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn insert_and_update() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let mut l = Level1 {
+        id: 1,
+        text: Some("level1".to_string()),
+        level2_count: Some(3),
+        ..Default::default()
+    };
+
+    assert!(toql.insert_one(&mut l, paths!(top)).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "INSERT INTO Level1 (id, text) VALUES (1, 'level1')"
+    );
+
+    assert!(toql
+        .update_one(&mut l, toql::prelude::fields!(top))
+        .await
+        .is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "UPDATE Level1 SET text = 'level1' WHERE id = 1"
+    );
+}
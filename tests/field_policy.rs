@@ -0,0 +1,142 @@
+use pretty_assertions::assert_eq;
+use toql::mock_db::MockDb;
+use toql::prelude::{
+    query, Cache, ContextBuilder, FieldOperation, FieldPolicy, Join, OnViolation, SqlBuilderError,
+    Toql, ToqlApi, ToqlError,
+};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+pub struct Level1 {
+    #[toql(key)]
+    id: u64,
+    text: Option<String>,
+    secret: Option<String>,
+
+    #[toql(join)]
+    level2: Option<Option<Join<Level2>>>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level2 {
+    #[toql(key)]
+    id: u64,
+    text: Option<String>,
+    secret: Option<String>,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn select() {
+    let cache = Cache::new();
+    let policy = FieldPolicy::new().deny("secret", &[FieldOperation::Select]);
+    let context = ContextBuilder::new().with_field_policy(policy).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    // Wildcard skips denied field
+    let q = query!(Level1, "*");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text FROM Level1 level1"
+    );
+
+    // Explicit field is rejected
+    let q = query!(Level1, "secret");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::FieldNotAllowed(field, operation))
+            if field == "secret" && operation == "select"
+    ));
+
+    // Denied field may still be filtered
+    let q = query!(Level1, ".secret eq 'ABC'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id FROM Level1 level1 WHERE level1.secret = 'ABC'"
+    );
+
+    // Allow list with path pattern
+    let policy = FieldPolicy::new()
+        .allow("text", &[FieldOperation::Select])
+        .allow("level2_*", &[FieldOperation::Select])
+        .deny("level2_secret", &[FieldOperation::Select]);
+    let context = ContextBuilder::new().with_field_policy(policy).build();
+    let mut toql = MockDb::with_context(&cache, context);
+    let q = query!(Level1, "*, level2_*");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text, level1_level2.id, level1_level2.text \
+            FROM Level1 level1 \
+            LEFT JOIN (Level2 level1_level2) \
+            ON (level1.level2_id = level1_level2.id)"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn filter_and_order() {
+    let cache = Cache::new();
+    let policy = FieldPolicy::new()
+        .allow("id", &[FieldOperation::Filter, FieldOperation::Order])
+        .deny("*", &[FieldOperation::Order]);
+    let context = ContextBuilder::new().with_field_policy(policy).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Level1, "id eq 5");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id FROM Level1 level1 WHERE level1.id = 5"
+    );
+
+    let q = query!(Level1, "id, text eq 'ABC'");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::FieldNotAllowed(field, operation))
+            if field == "text" && operation == "filter"
+    ));
+
+    let q = query!(Level1, "+id");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::FieldNotAllowed(field, operation))
+            if field == "id" && operation == "order"
+    ));
+
+    let q = query!(Level1, "level2_id eq 5");
+    let err = toql.delete_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::FieldNotAllowed(field, operation))
+            if field == "level2_id" && operation == "filter"
+    ));
+    assert!(toql.sqls_empty());
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn drop() {
+    let cache = Cache::new();
+    let policy = FieldPolicy::new()
+        .deny("secret", &FieldOperation::ALL)
+        .deny("level2_*", &[FieldOperation::Filter])
+        .on_violation(OnViolation::Drop);
+    let context = ContextBuilder::new().with_field_policy(policy).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(
+        Level1,
+        "id eq 5, text, secret eq 'ABC', -secret, .level2_text eq 'ABC'"
+    );
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text FROM Level1 level1 WHERE level1.id = 5"
+    );
+}
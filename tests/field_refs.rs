@@ -0,0 +1,135 @@
+use pretty_assertions::assert_eq;
+use std::collections::HashSet;
+use toql::mock_db::MockDb;
+use toql::prelude::{
+    query, Cache, ContextBuilder, Field, FieldOperation, FieldPolicy, Join, OnViolation, Query,
+    QueryParser, SqlArg, SqlBuilderError, Toql, ToqlApi, ToqlError,
+};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+pub struct Task {
+    #[toql(key)]
+    id: u64,
+    done_at: Option<u64>,
+    due_at: Option<u64>,
+    #[toql(roles(load = "admin"))]
+    estimate: Option<u64>,
+    #[toql(join)]
+    project: Option<Join<Project>>,
+    #[toql(join, roles(load = "admin"))]
+    review: Option<Join<Project>>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Project {
+    #[toql(key)]
+    id: u64,
+    deadline: Option<u64>,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn compare_fields() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Task, "id, doneAt gt $dueAt");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT task.id, task.done_at FROM Task task WHERE task.done_at > task.due_at"
+    );
+
+    // Joined field
+    let q = QueryParser::parse::<Task>("id, .dueAt bw 5 $project_deadline").unwrap();
+    assert_eq!(q.to_string(), "id,.dueAt BW 5 $project_deadline");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT task.id, task_project.id FROM Task task \
+        JOIN (Project task_project) ON (task.project_id = task_project.id) \
+        WHERE task.due_at BETWEEN 5 AND task_project.deadline"
+    );
+
+    // Builder
+    let q = Query::<Task>::from(Field::from("id").eq(SqlArg::Null).field_ref(0, "doneAt"));
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT task.id FROM Task task WHERE task.id = task.done_at"
+    );
+
+    // Filters that rewrite their argument cannot compare with fields
+    let q = query!(Task, "id, doneAt sw $dueAt");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert_eq!(err.to_string(), "filter `doneAt SW $dueAt` is invalid");
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn roles() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Task, "id, doneAt gt $estimate");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "role expression `admin` failed for `field `estimate``"
+    );
+
+    // Roles of joins on the path are checked
+    let q = query!(Task, "id, doneAt gt $review_deadline");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "role expression `admin` failed for `path `review``"
+    );
+
+    let q = query!(Task, "id, doneAt gt $unknown");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert_eq!(err.to_string(), "field `unknown` is missing");
+
+    let mut roles = HashSet::new();
+    roles.insert("admin".to_string());
+    let context = ContextBuilder::new().with_roles(roles).build();
+    let mut toql = MockDb::with_context(&cache, context);
+    let q = query!(Task, "id, doneAt gt $estimate");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT task.id, task.done_at FROM Task task WHERE task.done_at > task.estimate"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn field_policy() {
+    let cache = Cache::new();
+
+    // Referenced fields must be allowed to filter
+    let policy = FieldPolicy::new().deny("dueAt", &[FieldOperation::Filter]);
+    let context = ContextBuilder::new().with_field_policy(policy).build();
+    let mut toql = MockDb::with_context(&cache, context);
+    let q = query!(Task, "id, doneAt gt $dueAt");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::FieldNotAllowed(field, operation))
+            if field == "dueAt" && operation == "filter"
+    ));
+
+    // Filter is dropped with the referenced field
+    let policy = FieldPolicy::new()
+        .deny("dueAt", &[FieldOperation::Filter])
+        .on_violation(OnViolation::Drop);
+    let context = ContextBuilder::new().with_field_policy(policy).build();
+    let mut toql = MockDb::with_context(&cache, context);
+    let q = query!(Task, "id, doneAt gt $dueAt");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT task.id, task.done_at FROM Task task"
+    );
+}
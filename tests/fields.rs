@@ -1,8 +1,8 @@
 use toql::{
     mock_db::MockDb,
     prelude::{
-        query, sql_expr, Cache, FieldFilter, FieldHandler, ParameterMap, SqlArg, SqlBuilderError,
-        SqlExpr, Toql, ToqlApi,
+        query, sql_expr, Cache, ContextBuilder, FieldFilter, FieldHandler, ParameterMap,
+        RegexSyntax, SqlArg, SqlBuilderError, SqlExpr, Toql, ToqlApi,
     },
     row,
 };
@@ -170,6 +170,85 @@ async fn filter() {
     );
 }
 
+#[tokio::test]
+#[traced_test("info")]
+async fn text_filters() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "text1 ILK 'a%'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 WHERE LOWER(level1.text1) LIKE LOWER('a%')"
+    );
+
+    let q = query!(Level1, "text1 NLK 'a%'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 WHERE level1.text1 NOT LIKE 'a%'"
+    );
+
+    // Wildcards in user input are escaped
+    let q = query!(Level1, "text1 SW '50%'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 WHERE level1.text1 LIKE '50!%%' ESCAPE '!'"
+    );
+
+    let q = query!(Level1, "text1 CT 'a_b!'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 WHERE level1.text1 LIKE '%a!_b!!%' ESCAPE '!'"
+    );
+
+    let q = query!(Level1, "text1 EW 'C'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 WHERE level1.text1 LIKE '%C' ESCAPE '!'"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn regex() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "text1 RE '^A'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 WHERE level1.text1 REGEXP '^A'"
+    );
+
+    let context = ContextBuilder::new()
+        .with_regex_syntax(RegexSyntax::Tilde)
+        .build();
+    let mut toql = MockDb::with_context(&cache, context);
+    let q = query!(Level1, "text1 re '^A'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 WHERE level1.text1 ~ '^A'"
+    );
+
+    let context = ContextBuilder::new()
+        .with_regex_syntax(RegexSyntax::Function("regexp".to_string()))
+        .build();
+    let mut toql = MockDb::with_context(&cache, context);
+    let q = query!(Level1, "text1 RE '^A'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 WHERE regexp('^A', level1.text1)"
+    );
+}
+
 #[tokio::test]
 #[traced_test("info")]
 async fn concatenation() {
@@ -186,6 +265,37 @@ async fn concatenation() {
     );
 }
 
+#[tokio::test]
+#[traced_test("info")]
+async fn negation() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "id eq 5, not (text1 eq 'ABC'; text1 FN LK 'D%')");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 \
+            WHERE level1.id = 5 AND NOT (level1.text1 = 'ABC' OR level1.text1 LIKE 'D%')"
+    );
+
+    let q = query!(Level1, "!text1 eq 'ABC'; !id eq 5");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 \
+            WHERE NOT (level1.text1 = 'ABC') OR NOT (level1.id = 5)"
+    );
+
+    // Empty negated parentheses are removed
+    let q = query!(Level1, "id eq 5, !(text1)");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text1 FROM Level1 level1 WHERE level1.id = 5"
+    );
+}
+
 #[tokio::test]
 #[traced_test("info")]
 async fn order() {
@@ -0,0 +1,161 @@
+use pretty_assertions::assert_eq;
+use toql::mock_db::MockDb;
+use toql::prelude::{paths, query, Cache, Toql, ToqlApi};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+pub struct Level1 {
+    #[toql(key)]
+    id: u64,
+    text: String,
+
+    #[toql(merge)]
+    level2: Vec<Level2>,
+
+    #[toql(merge(
+        columns(self = "id", other = "la.level1_id"),
+        join_sql = "JOIN LevelAssoc la ON (la.level3_id = ...id)"
+    ))]
+    level3: Option<Vec<Level3>>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level2 {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    level1_id: u64,
+    text: String,
+
+    #[toql(merge(columns(self = "id", other = "level2_id")))]
+    level3: Option<Vec<Level3>>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level3 {
+    #[toql(key)]
+    id: u64,
+    text: String,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Shelf {
+    #[toql(key)]
+    id: u64,
+    #[toql(merge)]
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Item {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    shelf_id: u64,
+    #[toql(preselect)]
+    size: Option<u64>,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn quantifiers() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "id, any .level2_text eq 'ABC'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text FROM Level1 level1 \
+            WHERE EXISTS (SELECT 1 FROM Level2 level1_level2 \
+            WHERE level1.id = level1_level2.level1_id AND level1_level2.text = 'ABC')"
+    );
+
+    let q = query!(Level1, "id, all .level2_text lk 'A%'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text FROM Level1 level1 \
+            WHERE NOT EXISTS (SELECT 1 FROM Level2 level1_level2 \
+            WHERE level1.id = level1_level2.level1_id \
+            AND (NOT (level1_level2.text LIKE 'A%') OR (level1_level2.text LIKE 'A%') IS NULL))"
+    );
+
+    let q = query!(Level1, "id, none .level2_text eq 'ABC'; text eq 'ABC'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text FROM Level1 level1 \
+            WHERE NOT EXISTS (SELECT 1 FROM Level2 level1_level2 \
+            WHERE level1.id = level1_level2.level1_id AND level1_level2.text = 'ABC') \
+            OR level1.text = 'ABC'"
+    );
+
+    let q = query!(Level1, "id, !any .level2_text eq 'ABC'");
+    assert!(toql.count(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT COUNT(*) FROM Level1 level1 \
+            WHERE NOT (EXISTS (SELECT 1 FROM Level2 level1_level2 \
+            WHERE level1.id = level1_level2.level1_id AND level1_level2.text = 'ABC'))"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn custom_join() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "id, any .level3_text eq 'ABC'");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text FROM Level1 level1 \
+            WHERE EXISTS (SELECT 1 FROM Level3 level1_level3 \
+            JOIN LevelAssoc la ON (la.level3_id = level1_level3.id) \
+            WHERE level1.id = la.level1_id AND level1_level3.text = 'ABC')"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn invalid_quantifiers() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    // Field is not merged
+    let q = query!(Level1, "id, any text eq 'ABC'");
+    assert!(toql.load_many(q).await.is_err());
+
+    // Nested merges cannot be quantified
+    let q = query!(Level1, "id, any .level2_level3_text eq 'ABC'");
+    assert!(toql.load_many(q).await.is_err());
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn all_with_null() {
+    let cache = Cache::new();
+    let mut toql = MockDb::in_memory(&cache);
+
+    let item = |id, shelf_id, size| Item { id, shelf_id, size };
+    let mut shelves = vec![
+        Shelf {
+            id: 1,
+            items: vec![item(1, 1, Some(5)), item(2, 1, None)],
+        },
+        Shelf {
+            id: 2,
+            items: vec![item(3, 2, Some(5))],
+        },
+    ];
+    toql.insert_many::<Shelf, _>(&mut shelves, paths!(Shelf, "items"))
+        .await
+        .unwrap();
+
+    // An unknown size does not satisfy ALL
+    let q = query!(Shelf, "id, all .items_size eq 5");
+    let loaded = toql.load_many(q).await.unwrap();
+    assert_eq!(loaded.iter().map(|s| s.id).collect::<Vec<_>>(), [2]);
+}
@@ -0,0 +1,130 @@
+use pretty_assertions::assert_eq;
+use toql::mock_db::MockDb;
+use toql::prelude::{query, Cache, Toql, ToqlApi};
+use toql::row;
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+pub struct Level1 {
+    #[toql(key)]
+    id: u64,
+    text: String,
+
+    #[toql(merge(order = "text desc, level1_id, id"))]
+    level2: Option<Vec<Level2>>,
+
+    #[toql(merge(
+        columns(self = "id", other = "level1_id"),
+        order = "id desc",
+        limit = 2
+    ))]
+    level3: Option<Vec<Level3>>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level2 {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    level1_id: u64,
+    text: String,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level3 {
+    #[toql(key)]
+    id: u64,
+    level1_id: u64,
+    text: String,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn default_order() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "*, level2_*");
+    let select1 = "SELECT level1.id, level1.text FROM Level1 level1";
+    let select2 = "SELECT level1.id, level2.id, level2.level1_id, level2.text \
+                        FROM Level2 level2 \
+                        JOIN Level1 level1 \
+                        ON (level1.id = level2.level1_id AND level1.id = 1) \
+                        ORDER BY level2.text DESC, level2.level1_id ASC, level2.id ASC";
+
+    toql.mock_rows(select1, vec![row!(1u64, "level1")]);
+    toql.mock_rows(
+        select2,
+        vec![row!(1u64, 3u64, 1u64, "b"), row!(1u64, 2u64, 1u64, "a")],
+    );
+
+    let r = toql.load_many(q).await.unwrap();
+    assert_eq!(toql.take_unsafe_sqls(), [select1, select2]);
+    let level2 = r[0].level2.as_ref().unwrap();
+    assert_eq!(level2[0].text, "b");
+    assert_eq!(level2[1].id, 2);
+    assert_eq!(level2[1].level1_id, 1);
+
+    // Query order replaces default order
+    let q = query!(Level1, "*, +level2_id, level2_*");
+    let select2 = "SELECT level1.id, level2.id, level2.level1_id, level2.text \
+                        FROM Level2 level2 \
+                        JOIN Level1 level1 \
+                        ON (level1.id = level2.level1_id AND level1.id = 1) \
+                        ORDER BY level2.id ASC";
+    toql.mock_rows(select1, vec![row!(1u64, "level1")]);
+    toql.mock_rows(select2, vec![row!(1u64, 2u64, 1u64, "a")]);
+
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(toql.take_unsafe_sqls(), [select1, select2]);
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn limit() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "*, level3_*");
+    let select1 = "SELECT level1.id, level1.text FROM Level1 level1";
+    let select2 = "SELECT level1.id, level3.id, level3.level1_id, level3.text \
+                        FROM Level3 level3 \
+                        JOIN Level1 level1 \
+                        ON (level1.id = level3.level1_id AND level1.id = 1) \
+                        WHERE (level1.id, level3.id) IN (SELECT toql_k0, toql_k1 FROM \
+                            (SELECT level1.id AS toql_k0, level3.id AS toql_k1, \
+                            ROW_NUMBER() OVER (PARTITION BY level1.id ORDER BY level3.id DESC) AS toql_rn \
+                            FROM Level3 level3 \
+                            JOIN Level1 level1 \
+                            ON (level1.id = level3.level1_id AND level1.id = 1)) toql_ranked \
+                        WHERE toql_rn <= 2) \
+                        ORDER BY level3.id DESC";
+
+    toql.mock_rows(select1, vec![row!(1u64, "level1")]);
+    toql.mock_rows(select2, vec![row!(1u64, 3u64, 1u64, "level3")]);
+
+    let r = toql.load_many(q).await.unwrap();
+    assert_eq!(toql.take_unsafe_sqls(), [select1, select2]);
+    assert_eq!(r[0].level3.as_ref().unwrap()[0].level1_id, 1);
+
+    // Query overrides limit
+    let q = query!(Level1, "*, level3_*").limit_merge("level3", 5);
+    let select2 = "SELECT level1.id, level3.id, level3.level1_id, level3.text \
+                        FROM Level3 level3 \
+                        JOIN Level1 level1 \
+                        ON (level1.id = level3.level1_id AND level1.id = 1) \
+                        WHERE (level1.id, level3.id) IN (SELECT toql_k0, toql_k1 FROM \
+                            (SELECT level1.id AS toql_k0, level3.id AS toql_k1, \
+                            ROW_NUMBER() OVER (PARTITION BY level1.id ORDER BY level3.id DESC) AS toql_rn \
+                            FROM Level3 level3 \
+                            JOIN Level1 level1 \
+                            ON (level1.id = level3.level1_id AND level1.id = 1)) toql_ranked \
+                        WHERE toql_rn <= 5) \
+                        ORDER BY level3.id DESC";
+    toql.mock_rows(select1, vec![row!(1u64, "level1")]);
+    toql.mock_rows(select2, vec![row!(1u64, 3u64, 1u64, "ABC")]);
+
+    let r = toql.load_many(q).await.unwrap();
+    assert_eq!(toql.take_unsafe_sqls(), [select1, select2]);
+    assert_eq!(r[0].level3.as_ref().unwrap()[0].text, "ABC");
+}
@@ -0,0 +1,241 @@
+use pretty_assertions::assert_eq;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use toql::backend::context_builder::ContextBuilder;
+use toql::metrics::{Metrics, Purpose, StatementMetrics};
+use toql::mock_db::expectation::Expectation;
+use toql::mock_db::mock_db_error::MockDbError;
+use toql::mock_db::MockDb;
+use toql::prelude::{fields, paths, query, Cache, Page, Toql, ToqlApi};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+#[toql(auto_key)]
+pub struct Book {
+    #[toql(key)]
+    id: u64,
+    title: String,
+    #[toql(merge)]
+    chapters: Vec<Chapter>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Chapter {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    book_id: u64,
+    title: String,
+}
+
+// Type name, path, purpose, rows and failure
+type Entry = (String, String, Purpose, u64, bool);
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<Entry>>);
+
+impl Metrics for Recorder {
+    fn record(&self, s: &StatementMetrics) {
+        self.0.lock().unwrap().push((
+            s.type_name.to_string(),
+            s.path.to_string(),
+            s.purpose,
+            s.rows,
+            s.failed,
+        ));
+    }
+}
+
+impl Recorder {
+    fn take(&self) -> Vec<Entry> {
+        self.0.lock().unwrap().drain(..).collect()
+    }
+}
+
+// Span name and fields
+type SpanEntry = (&'static str, Vec<(String, String)>);
+
+// Collects the spans with their fields
+#[derive(Default, Clone)]
+struct Spans(Arc<Mutex<Vec<SpanEntry>>>);
+
+struct Fields<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{:?}", value)));
+    }
+}
+
+impl Subscriber for Spans {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let mut spans = self.0.lock().unwrap();
+        let mut fields = Vec::new();
+        attributes.record(&mut Fields(&mut fields));
+        spans.push((attributes.metadata().name(), fields));
+        Id::from_u64(spans.len() as u64)
+    }
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.0.lock().unwrap();
+        let index = span.into_u64() as usize - 1;
+        values.record(&mut Fields(&mut spans[index].1));
+    }
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, _: &Event<'_>) {}
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+impl Spans {
+    // Take the names and fields of all spans, except the duration
+    fn take(&self) -> Vec<SpanEntry> {
+        self.0
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(|(name, fields)| {
+                let fields = fields
+                    .into_iter()
+                    .filter(|(f, _)| f != "duration_us")
+                    .collect();
+                (name, fields)
+            })
+            .collect()
+    }
+}
+
+fn span(name: &'static str, fields: &[(&str, &str)]) -> SpanEntry {
+    let fields = fields
+        .iter()
+        .map(|(f, v)| (f.to_string(), v.to_string()))
+        .collect();
+    (name, fields)
+}
+
+fn entry(path: &str, purpose: Purpose, rows: u64) -> Entry {
+    ("Book".to_string(), path.to_string(), purpose, rows, false)
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn record_statements() {
+    let cache = Cache::new();
+    let recorder = Arc::new(Recorder::default());
+    let context = ContextBuilder::new().with_metrics(recorder.clone()).build();
+    let mut toql = MockDb::in_memory_with_context(&cache, context);
+    let spans = Spans::default();
+    let _guard = tracing::subscriber::set_default(spans.clone());
+
+    let mut books = (1..=2)
+        .map(|i| Book {
+            id: 0,
+            title: format!("Book {}", i),
+            chapters: vec![Chapter {
+                id: 1,
+                book_id: 0,
+                title: "Intro".to_string(),
+            }],
+        })
+        .collect::<Vec<_>>();
+    toql.insert_many::<Book, _>(&mut books, paths!(Book, "chapters"))
+        .await
+        .unwrap();
+    assert_eq!(
+        recorder.take(),
+        [
+            entry("", Purpose::Insert, 2),
+            entry("chapters", Purpose::Insert, 2)
+        ]
+    );
+    assert_eq!(
+        spans.take(),
+        [
+            span(
+                "toql",
+                &[("ty", "Book"), ("op", "\"insert\""), ("paths", "[\"chapters_\"]")]
+            ),
+            span(
+                "sql",
+                &[
+                    ("ty", "Book"),
+                    ("path", ""),
+                    ("purpose", "insert"),
+                    ("sql", "INSERT INTO Book (title) VALUES ('Book 1'), ('Book 2')"),
+                    ("ids", "101, 100"),
+                    ("rows", "2")
+                ]
+            ),
+            span(
+                "sql",
+                &[
+                    ("ty", "Book"),
+                    ("path", "chapters"),
+                    ("purpose", "insert"),
+                    (
+                        "sql",
+                        "INSERT INTO Chapter (id, book_id, title) VALUES (1, 100, 'Intro'), (1, 101, 'Intro')"
+                    ),
+                    ("rows", "2")
+                ]
+            )
+        ]
+    );
+
+    toql.load_page(query!(Book, "*, chapters_*"), Page::Counted(0, 1))
+        .await
+        .unwrap();
+    assert_eq!(
+        recorder.take(),
+        [
+            entry("", Purpose::Load, 1),
+            entry("", Purpose::PageCount, 2),
+            entry("", Purpose::TotalCount, 2),
+            entry("chapters", Purpose::Merge, 1)
+        ]
+    );
+
+    books[0].title = "Book 0".to_string();
+    toql.update_one(&mut books[0], fields!(top)).await.unwrap();
+    assert_eq!(toql.count(query!(Book, "*")).await.unwrap(), 2);
+    assert_eq!(
+        recorder.take(),
+        [entry("", Purpose::Update, 0), entry("", Purpose::Count, 2)]
+    );
+
+    spans.take();
+    toql.expect(Expectation::regex("^DELETE").fails(MockDbError::Deadlock));
+    assert!(toql.delete_many(query!(Book, "id eq 1")).await.is_err());
+    assert_eq!(
+        recorder.take(),
+        [("Book".to_string(), String::new(), Purpose::Delete, 0, true)]
+    );
+    assert_eq!(
+        spans.take(),
+        [
+            span(
+                "toql",
+                &[("ty", "Book"), ("op", "\"delete\""), ("query", "id EQ 1")]
+            ),
+            span(
+                "sql",
+                &[
+                    ("ty", "Book"),
+                    ("path", ""),
+                    ("purpose", "delete"),
+                    ("sql", "DELETE book FROM Book book WHERE book.id = 1"),
+                    (
+                        "error",
+                        "mock database failed: deadlock found when trying to get lock"
+                    )
+                ]
+            )
+        ]
+    );
+}
@@ -0,0 +1,151 @@
+use pretty_assertions::assert_eq;
+use toql::mock_db::expectation::Expectation;
+use toql::mock_db::mock_db_error::MockDbError;
+use toql::mock_db::MockDb;
+use toql::prelude::{paths, query, Cache, Toql, ToqlApi, ToqlError};
+use toql::row;
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+pub struct Author {
+    #[toql(key)]
+    id: u64,
+    name: String,
+}
+
+fn author(id: u64, name: &str) -> Author {
+    Author {
+        id,
+        name: name.to_string(),
+    }
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn inject_errors() {
+    let cache = Cache::new();
+    let mut toql = MockDb::in_memory(&cache);
+
+    toql.expect(
+        Expectation::regex("^INSERT INTO Author")
+            .fails(MockDbError::DuplicateKey("PRIMARY".to_string())),
+    )
+    .expect(Expectation::regex("^INSERT INTO Author").fails(MockDbError::Deadlock));
+
+    let mut a = author(1, "Ann");
+    let err = toql.insert_one(&mut a, paths!(top)).await.err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "mock database failed: duplicate entry for key `PRIMARY`"
+    );
+    let err = toql.insert_one(&mut a, paths!(top)).await.err().unwrap();
+    assert!(matches!(err, ToqlError::MockDbError(MockDbError::Deadlock)));
+
+    // Expectations are consumed, retry reaches the in-memory tables
+    // and counts as over-call of the first matching expectation
+    toql.insert_one(&mut a, paths!(top)).await.unwrap();
+    assert_eq!(toql.table_rows("Author").len(), 1);
+    assert_eq!(toql.take_unsafe_sqls().len(), 3);
+    assert_eq!(
+        toql.verify().err().unwrap().to_string(),
+        "expectation `/^INSERT INTO Author/` was called 2 times instead of 1"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn return_rows_and_counts() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    // Normalized SQL ignores whitespace
+    toql.expect(
+        Expectation::sql("SELECT author.id,author.name\n FROM Author author")
+            .returns(vec![row!(1u64, "Ann"), row!(2u64, "Bob")]),
+    )
+    .expect(Expectation::regex("^SELECT COUNT").affects(3).times(2));
+
+    let authors = toql.load_many(query!(Author, "*")).await.unwrap();
+    assert_eq!(authors.len(), 2);
+    assert_eq!(authors[1].name, "Bob");
+    assert_eq!(toql.count(query!(Author, "*")).await.unwrap(), 3);
+    assert_eq!(
+        toql.take_unsafe_sqls(),
+        [
+            "SELECT author.id, author.name FROM Author author",
+            "SELECT COUNT(*) FROM Author author"
+        ]
+    );
+
+    assert_eq!(
+        toql.verify().err().unwrap().to_string(),
+        "expectation `/^SELECT COUNT/` was called 1 of 2 times"
+    );
+    assert_eq!(toql.count(query!(Author, "*")).await.unwrap(), 3);
+    assert!(toql.verify().is_ok());
+
+    // Statement of consumed expectation
+    assert!(toql
+        .load_many(query!(Author, "*"))
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        toql.verify().err().unwrap(),
+        MockDbError::Overcalled(
+            "SELECT author.id,author.name FROM Author author".to_string(),
+            1,
+            2
+        )
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn report_unexpected() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    // Without expectations no statement is unexpected
+    toql.delete_one(AuthorKey::from(1)).await.unwrap();
+    assert!(toql.verify().is_ok());
+
+    toql.expect(Expectation::regex("^INSERT"));
+    toql.insert_one(&mut author(1, "Ann"), paths!(top))
+        .await
+        .unwrap();
+    toql.delete_one(AuthorKey::from(1)).await.unwrap();
+    assert_eq!(
+        toql.verify().err().unwrap(),
+        MockDbError::Unexpected("DELETE author FROM Author author WHERE author.id = 1".to_string())
+    );
+
+    toql.clear_expectations();
+    assert!(toql.verify().is_ok());
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn assert_order() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    toql.expect(Expectation::regex("^INSERT").in_order())
+        .expect(Expectation::regex("^DELETE").in_order());
+
+    let err = toql.delete_one(AuthorKey::from(1)).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::MockDbError(MockDbError::OutOfOrder(_))
+    ));
+    assert_eq!(
+        toql.verify().err().unwrap(),
+        MockDbError::Unconsumed("/^INSERT/".to_string(), 1, 0)
+    );
+
+    toql.insert_one(&mut author(1, "Ann"), paths!(top))
+        .await
+        .unwrap();
+    toql.delete_one(AuthorKey::from(1)).await.unwrap();
+    assert!(toql.verify().is_ok());
+}
@@ -0,0 +1,165 @@
+#![cfg(feature = "fixtures")]
+use pretty_assertions::assert_eq;
+use toql::mock_db::MockDb;
+use toql::prelude::{query, Cache, Join, Toql, ToqlApi};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+#[toql(auto_key)]
+pub struct Book {
+    #[toql(key)]
+    id: u64,
+    title: String,
+    pages: Option<u64>,
+    #[toql(join)]
+    author: Option<Join<Author>>,
+    #[toql(merge)]
+    chapters: Vec<Chapter>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Author {
+    #[toql(key)]
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Chapter {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    book_id: u64,
+    title: String,
+}
+
+static FIXTURE: &str = r#"{
+    "Book": [
+        {
+            "id": 1,
+            "title": "Rust",
+            "pages": 300,
+            "author": { "id": 7, "name": "Ann" },
+            "chapters": [
+                { "id": 1, "title": "Ownership" },
+                { "id": 2, "title": "Traits" }
+            ]
+        },
+        { "id": 2, "title": "Go", "author": 7 }
+    ],
+    "Author": [
+        { "id": 7, "name": "Ann" },
+        { "id": 8, "name": "Bob" }
+    ]
+}"#;
+
+#[tokio::test]
+#[traced_test("info")]
+async fn seed_nested() {
+    let cache = Cache::new();
+    let mut toql = MockDb::in_memory(&cache);
+    toql.map::<Book>().unwrap().seed_json(FIXTURE).unwrap();
+
+    // Nested author is seeded once
+    assert_eq!(toql.table_rows("Author").len(), 2);
+    assert_eq!(toql.table_columns("Chapter"), ["book_id", "id", "title"]);
+
+    let books = toql
+        .load_many(query!(Book, "*, author_*, chapters_*, id"))
+        .await
+        .unwrap();
+    assert_eq!(books.len(), 2);
+    assert_eq!(books[0].title, "Rust");
+    assert_eq!(books[0].pages, Some(300));
+    assert_eq!(
+        books[0].author.as_ref().unwrap().entity().unwrap().name,
+        "Ann"
+    );
+    assert_eq!(
+        books[0]
+            .chapters
+            .iter()
+            .map(|c| (c.book_id, c.title.as_str()))
+            .collect::<Vec<_>>(),
+        [(1, "Ownership"), (1, "Traits")]
+    );
+    assert_eq!(books[1].author.as_ref().unwrap().key().id, 7);
+    assert!(books[1].chapters.is_empty());
+
+    let authors = toql.load_many(query!(Author, "*, id")).await.unwrap();
+    assert_eq!(authors[1].name, "Bob");
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn seed_json() {
+    let cache = Cache::new();
+    let mut toql = MockDb::in_memory(&cache);
+    toql.map::<Book>()
+        .unwrap()
+        .seed_json(r#"{"Book": {"title": "C", "chapters": [{"id": 1, "title": "Pointers"}]}}"#)
+        .unwrap();
+
+    // Auto key is generated and merged into chapters
+    let book = toql.load_one(query!(Book, "*, chapters_*")).await.unwrap();
+    assert_eq!(book.id, 100);
+    assert_eq!(book.chapters[0].book_id, 100);
+    assert!(book.author.is_none());
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn seed_yaml() {
+    let cache = Cache::new();
+    let mut toql = MockDb::in_memory(&cache);
+    toql.map::<Book>()
+        .unwrap()
+        .seed_yaml(
+            r#"
+Book:
+  - id: 1
+    title: Rust
+    author: { id: 7, name: Ann }
+    chapters:
+      - { id: 1, title: Ownership }
+"#,
+        )
+        .unwrap();
+
+    let book = toql
+        .load_one(query!(Book, "*, author_*, chapters_*"))
+        .await
+        .unwrap();
+    assert_eq!(book.title, "Rust");
+    assert_eq!(book.author.as_ref().unwrap().entity().unwrap().name, "Ann");
+    assert_eq!(book.chapters[0].title, "Ownership");
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn invalid_fixtures() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+    let err = toql.map::<Book>().unwrap().seed_json("{}").err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "mock database failed: invalid fixture: fixtures need a Mock Db with in-memory tables"
+    );
+
+    let mut toql = MockDb::in_memory(&cache);
+    let err = toql.seed_json(r#"{"Book": {"isbn": 1}}"#).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "mock database failed: invalid fixture: `Book` has no field `isbn`"
+    );
+    let err = toql.seed_json(r#"{"Shelf": []}"#).err().unwrap();
+    assert!(err.to_string().ends_with("type `Shelf` is not mapped"));
+    let err = toql.seed_json(r#"{"Book": ["#).err().unwrap();
+    assert!(err
+        .to_string()
+        .starts_with("mock database failed: invalid fixture:"));
+    let err = toql.seed_yaml("Book: [").err().unwrap();
+    assert!(err
+        .to_string()
+        .starts_with("mock database failed: invalid fixture:"));
+}
@@ -0,0 +1,202 @@
+use pretty_assertions::assert_eq;
+use toql::mock_db::MockDb;
+use toql::prelude::{fields, paths, query, Cache, Join, Page, Toql, ToqlApi};
+use toql::row;
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+#[toql(auto_key)]
+pub struct Book {
+    #[toql(key)]
+    id: u64,
+    title: String,
+    pages: Option<u64>,
+    #[toql(join)]
+    author: Option<Join<Author>>,
+    #[toql(merge)]
+    chapters: Vec<Chapter>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Author {
+    #[toql(key)]
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Chapter {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    book_id: u64,
+    title: String,
+}
+
+fn book(title: &str, pages: Option<u64>, author: u64) -> Book {
+    Book {
+        id: 0,
+        title: title.to_string(),
+        pages,
+        author: Some(Join::with_key(AuthorKey::from(author))),
+        chapters: vec![
+            Chapter {
+                id: 1,
+                book_id: 0,
+                title: format!("{} 1", title),
+            },
+            Chapter {
+                id: 2,
+                book_id: 0,
+                title: format!("{} 2", title),
+            },
+        ],
+    }
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn insert_and_load() {
+    let cache = Cache::new();
+    let mut toql = MockDb::in_memory(&cache);
+
+    let mut author = Author {
+        id: 7,
+        name: "Ann".to_string(),
+    };
+    toql.insert_one(&mut author, paths!(top)).await.unwrap();
+
+    let mut books = vec![book("Rust", Some(300), 7), book("Go", None, 7)];
+    toql.insert_many::<Book, _>(&mut books, paths!(Book, "chapters"))
+        .await
+        .unwrap();
+    assert_eq!(books[0].id, 100);
+    assert_eq!(books[1].id, 101);
+    assert_eq!(
+        toql.table_columns("Book"),
+        ["title", "pages", "author_id", "id"]
+    );
+    assert_eq!(toql.table_rows("Book").len(), 2);
+    toql.take_unsafe_sqls();
+
+    let q = query!(Book, "*, author_*, chapters_*, +title");
+    let loaded = toql.load_many(q).await.unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].title, "Go");
+    assert_eq!(loaded[0].pages, None);
+    assert_eq!(loaded[1].title, "Rust");
+    assert_eq!(loaded[1].pages, Some(300));
+    assert_eq!(
+        loaded[1].author.as_ref().unwrap().entity().unwrap().name,
+        "Ann"
+    );
+    let chapters = loaded[1]
+        .chapters
+        .iter()
+        .map(|c| c.title.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(chapters, ["Rust 1", "Rust 2"]);
+
+    // Filters are executed
+    let q = query!(Book, "*, pages gt 100");
+    let loaded = toql.load_many(q).await.unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].id, 100);
+
+    let q = query!(Book, "*, title lk 'G%'; pages eqn");
+    assert_eq!(toql.load_many(q).await.unwrap().len(), 1);
+    assert_eq!(
+        toql.count(query!(Book, "author_name eq 'Ann'"))
+            .await
+            .unwrap(),
+        2
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn update_and_delete() {
+    let cache = Cache::new();
+    let mut toql = MockDb::in_memory(&cache);
+
+    let mut books = vec![book("Rust", Some(300), 7), book("Go", None, 7)];
+    toql.insert_many::<Book, _>(&mut books, paths!(top))
+        .await
+        .unwrap();
+
+    books[1].pages = Some(150);
+    toql.update_one(&mut books[1], fields!(Book, "pages"))
+        .await
+        .unwrap();
+    let loaded = toql.load_one(query!(Book, "*, id eq 101")).await.unwrap();
+    assert_eq!(loaded.pages, Some(150));
+
+    toql.delete_one(BookKey::from(100)).await.unwrap();
+    let loaded = toql.load_many(query!(Book, "*")).await.unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].title, "Go");
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn page() {
+    let cache = Cache::new();
+    let mut toql = MockDb::in_memory(&cache);
+
+    let mut books = (0..5)
+        .map(|i| book(&format!("Book {}", i), Some(i * 100), 7))
+        .collect::<Vec<_>>();
+    toql.insert_many::<Book, _>(&mut books, paths!(top))
+        .await
+        .unwrap();
+    toql.take_unsafe_sqls();
+
+    let q = query!(Book, "id, -1pages, pages ge 100");
+    let (loaded, counts) = toql.load_page(q, Page::Counted(1, 2)).await.unwrap();
+    assert_eq!(
+        toql.take_unsafe_sqls()[0],
+        "SELECT book.id, book.title, book.pages FROM Book book WHERE book.pages >= 100 \
+        ORDER BY book.pages DESC LIMIT 1, 2"
+    );
+    let pages = loaded.iter().map(|b| b.pages).collect::<Vec<_>>();
+    assert_eq!(pages, [Some(300), Some(200)]);
+    let counts = counts.unwrap();
+    assert_eq!(counts.filtered, 4);
+    assert_eq!(counts.total, 5);
+
+    // Distinct rows are counted from a derived table
+    let q = query!(Book, "distinct pages ge 100");
+    assert_eq!(toql.count(&q).await.unwrap(), 4);
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn unsupported_sql() {
+    let cache = Cache::new();
+    let mut toql = MockDb::in_memory(&cache);
+
+    // Mocked rows are ignored
+    toql.mock_rows(
+        "SELECT author.id, author.name FROM Author author",
+        vec![row!(1u64, "Bob")],
+    );
+    assert!(toql
+        .load_many(query!(Author, "*"))
+        .await
+        .unwrap()
+        .is_empty());
+
+    let mut author = Author {
+        id: 1,
+        name: "Ann".to_string(),
+    };
+    toql.insert_one(&mut author, paths!(top)).await.unwrap();
+    let err = toql
+        .load_many(query!(Author, "name re '('"))
+        .await
+        .err()
+        .unwrap();
+    assert!(err
+        .to_string()
+        .starts_with("mock database failed: invalid regular expression"));
+}
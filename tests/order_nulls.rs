@@ -0,0 +1,90 @@
+use pretty_assertions::assert_eq;
+use toql::mock_db::MockDb;
+use toql::prelude::{query, Cache, ContextBuilder, Field, Join, Page, Query, Toql, ToqlApi};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+pub struct Level1 {
+    #[toql(key)]
+    id: u64,
+    #[toql(key, join)]
+    level2: Join<Level2>,
+    text: Option<String>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level2 {
+    #[toql(key)]
+    id: u64,
+    text: Option<String>,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn nulls() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "id, +1text nulls last, -2level2_text NULLS FIRST");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1_level2.id, level1_level2.text, level1.text \
+        FROM Level1 level1 \
+        JOIN (Level2 level1_level2) ON (level1.level2_id = level1_level2.id) \
+        ORDER BY CASE WHEN level1.text IS NULL THEN 1 ELSE 0 END, level1.text ASC, \
+        CASE WHEN level1_level2.text IS NULL THEN 0 ELSE 1 END, level1_level2.text DESC"
+    );
+
+    let q = Query::<Level1>::from(Field::from("id").desc(1).nulls_first());
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1_level2.id \
+        FROM Level1 level1 \
+        JOIN (Level2 level1_level2) ON (level1.level2_id = level1_level2.id) \
+        ORDER BY CASE WHEN level1.id IS NULL THEN 0 ELSE 1 END, level1.id DESC"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn stable_order() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new().with_stable_order(true).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    // Keys order after the query order
+    let q = query!(Level1, "id, -1text");
+    assert!(toql.load_page(q, Page::Uncounted(0, 10)).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1_level2.id, level1.text \
+        FROM Level1 level1 \
+        JOIN (Level2 level1_level2) ON (level1.level2_id = level1_level2.id) \
+        ORDER BY level1.text DESC, level1.id ASC, level1_level2.id ASC"
+    );
+
+    // Keys that are ordered by the query are not repeated
+    let q = query!(Level1, "-1id");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1_level2.id \
+        FROM Level1 level1 \
+        JOIN (Level2 level1_level2) ON (level1.level2_id = level1_level2.id) \
+        ORDER BY level1.id DESC, level1_level2.id ASC"
+    );
+
+    // Without stable order
+    let mut toql = MockDb::from(&cache);
+    let q = query!(Level1, "id");
+    let r = toql.load_many(q).await.unwrap();
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1_level2.id \
+        FROM Level1 level1 \
+        JOIN (Level2 level1_level2) ON (level1.level2_id = level1_level2.id)"
+    );
+    assert!(r.is_empty());
+}
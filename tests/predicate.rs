@@ -76,6 +76,14 @@ async fn load() {
         toql.take_unsafe_sql(),
         "SELECT level1.id, level1.text FROM Level1 level1 WHERE level1.predicate1 = 5"
     );
+    // Negated predicate
+    let q = query!(Level1, "id eq 1; !@pred 5");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.text FROM Level1 level1 \
+            WHERE level1.id = 1 OR NOT (level1.predicate1 = 5)"
+    );
     // Predicate on  level1
     let q = query!(Level1, "@level2_pred 2");
     assert!(toql.load_many(q).await.is_ok());
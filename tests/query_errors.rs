@@ -0,0 +1,188 @@
+use pretty_assertions::assert_eq;
+use std::collections::HashSet;
+use toql::mock_db::MockDb;
+use toql::prelude::{
+    Cache, ContextBuilder, FieldOperation, FieldPolicy, Join, QueryParser, Toql, ToqlApi, ToqlError,
+};
+use toql::query_parser::query_error::{QueryError, QueryErrorKind};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+#[toql(
+    predicate(name = "search", sql = "..title LIKE ?"),
+    selection(name = "short", fields = "*, author_name")
+)]
+pub struct Book {
+    #[toql(key)]
+    id: u64,
+    title: Option<String>,
+    #[toql(join)]
+    author: Option<Join<Author>>,
+    #[toql(roles(load = "admin"))]
+    price: Option<u64>,
+    #[toql(join, roles(load = "admin"))]
+    editor: Option<Join<Author>>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Author {
+    #[toql(key)]
+    id: u64,
+    name: Option<String>,
+}
+
+fn query_error(result: Result<toql::prelude::Query<Book>, ToqlError>) -> QueryError {
+    match result {
+        Err(ToqlError::QueryError(e)) => e,
+        r => panic!("expected query error, got {:?}", r.map(|q| q.to_string())),
+    }
+}
+
+#[test]
+fn unknown_names() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new().build();
+
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "id, titel eq 'a'",
+        &cache,
+        &context,
+    ));
+    assert_eq!(e.kind(), &QueryErrorKind::UnknownField);
+    assert_eq!(e.span(), 4..9);
+    assert_eq!(e.token(), "titel");
+    assert_eq!(e.suggestions(), ["title"]);
+    assert_eq!(
+        e.to_string(),
+        "unknown field `titel` at position 4, did you mean `title`?"
+    );
+
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "id, autor_name",
+        &cache,
+        &context,
+    ));
+    assert_eq!(e.kind(), &QueryErrorKind::UnknownPath);
+    assert_eq!(e.span(), 4..9);
+    assert_eq!(e.suggestions(), ["author"]);
+
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "author_nme",
+        &cache,
+        &context,
+    ));
+    assert_eq!(e.kind(), &QueryErrorKind::UnknownField);
+    assert_eq!(e.span(), 7..10);
+    assert_eq!(e.suggestions(), ["name"]);
+
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "id gt $idd",
+        &cache,
+        &context,
+    ));
+    assert_eq!(e.span(), 7..10);
+    assert_eq!(e.suggestions(), ["id"]);
+
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "@serch 'a'",
+        &cache,
+        &context,
+    ));
+    assert_eq!(e.kind(), &QueryErrorKind::UnknownPredicate);
+    assert_eq!(e.suggestions(), ["search"]);
+
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "$shrt", &cache, &context,
+    ));
+    assert_eq!(e.kind(), &QueryErrorKind::UnknownSelection);
+    assert_eq!(e.suggestions(), ["short"]);
+
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "writer_*", &cache, &context,
+    ));
+    assert_eq!(e.kind(), &QueryErrorKind::UnknownPath);
+    assert!(e.suggestions().is_empty());
+    assert_eq!(e.to_string(), "unknown path `writer` at position 0");
+
+    let q = QueryParser::parse_checked::<Book>(
+        "id, title eq $author_name, author_*, @search 'a', $short, $cnt",
+        &cache,
+        &context,
+    )
+    .unwrap();
+    assert_eq!(
+        q.to_string(),
+        "id,title EQ $author_name,author_*,@search 'a',$short,$cnt"
+    );
+}
+
+#[test]
+fn suggestions_respect_access() {
+    let cache = Cache::new();
+
+    // Names that need roles are not suggested
+    let context = ContextBuilder::new().build();
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "prise", &cache, &context,
+    ));
+    assert!(e.suggestions().is_empty());
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "editr_name",
+        &cache,
+        &context,
+    ));
+    assert!(e.suggestions().is_empty());
+
+    let mut roles = HashSet::new();
+    roles.insert("admin".to_string());
+    let context = ContextBuilder::new().with_roles(roles).build();
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "prise", &cache, &context,
+    ));
+    assert_eq!(e.suggestions(), ["price"]);
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "editr_name",
+        &cache,
+        &context,
+    ));
+    assert_eq!(e.suggestions(), ["editor"]);
+
+    // Fields that the field policy denies are not suggested
+    let policy = FieldPolicy::new().deny("title", &FieldOperation::ALL).deny(
+        "author_name",
+        &[FieldOperation::Select, FieldOperation::Order],
+    );
+    let context = ContextBuilder::new().with_field_policy(policy).build();
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "titel", &cache, &context,
+    ));
+    assert!(e.suggestions().is_empty());
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "author_nme",
+        &cache,
+        &context,
+    ));
+    assert_eq!(e.suggestions(), ["name"]);
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn syntax_error() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new().build();
+    let mut toql = MockDb::from(&cache);
+
+    let e = query_error(QueryParser::parse_checked::<Book>(
+        "id; title ne",
+        &cache,
+        &context,
+    ));
+    assert_eq!(e.kind(), &QueryErrorKind::Syntax);
+    assert_eq!(e.span(), 12..12);
+    assert_eq!(e.expected(), ["value"]);
+
+    // Unchecked queries fail later without position
+    let q = QueryParser::parse::<Book>("id, titel eq 'a'").unwrap();
+    let err = toql.load_many(q).await.err().unwrap();
+    assert_eq!(err.to_string(), "field `titel` is missing");
+}
@@ -0,0 +1,159 @@
+use toql::mock_db::MockDb;
+use toql::prelude::{
+    query, Cache, ContextBuilder, Join, Page, QueryLimits, SqlBuilderError, Toql, ToqlApi,
+    ToqlError,
+};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+pub struct Level1 {
+    #[toql(key)]
+    id: u64,
+    text: Option<String>,
+
+    #[toql(join)]
+    level2: Option<Option<Join<Level2>>>,
+
+    #[toql(merge)]
+    level4: Option<Vec<Level4>>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level2 {
+    #[toql(key)]
+    id: u64,
+    text: Option<String>,
+
+    #[toql(join)]
+    level3: Option<Option<Join<Level3>>>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level3 {
+    #[toql(key)]
+    id: u64,
+    text: Option<String>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level4 {
+    #[toql(key)]
+    id: u64,
+    #[toql(foreign_key)]
+    level1_id: u64,
+    text: Option<String>,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn unlimited() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "*, level2_*, level2_level3_*, level4_*");
+    assert!(toql.load_many(q).await.is_ok());
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn path_depth() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new()
+        .with_query_limits(QueryLimits::new().max_path_depth(1))
+        .build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Level1, "level2_text");
+    assert!(toql.load_many(q).await.is_ok());
+    toql.take_unsafe_sqls();
+
+    let q = query!(Level1, "level2_level3_text");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryLimitExceeded(name, _, _)) if name == "path depth"
+    ));
+    let q = query!(Level1, "level2_level3_*");
+    let err = toql.count(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryLimitExceeded(name, _, _)) if name == "path depth"
+    ));
+    assert!(toql.sqls_empty());
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn joins_and_merges() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new()
+        .with_query_limits(QueryLimits::new().max_joins(1).max_merges(0))
+        .build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Level1, "*, level2_*");
+    assert!(toql.load_many(q).await.is_ok());
+    toql.take_unsafe_sqls();
+
+    let q = query!(Level1, "*, level2_level3_*");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryLimitExceeded(name, _, _)) if name == "joins"
+    ));
+    let q = query!(Level1, "*, level4_text");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryLimitExceeded(name, _, _)) if name == "merges"
+    ));
+    let q = query!(Level1, "level2_level3_id eq 5");
+    let err = toql.delete_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryLimitExceeded(name, _, _)) if name == "joins"
+    ));
+    assert!(toql.sqls_empty());
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn wildcards_filter_args_and_page() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new()
+        .with_query_limits(
+            QueryLimits::new()
+                .max_wildcards(1)
+                .max_filter_args(2)
+                .max_page_size(10),
+        )
+        .build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Level1, "*, level2_*");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryLimitExceeded(name, _, _)) if name == "wildcards"
+    ));
+    let q = query!(Level1, "*, id in 1 2 3");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryLimitExceeded(name, _, _)) if name == "filter arguments"
+    ));
+    let q = query!(Level1, "*");
+    let err = toql
+        .load_page(q, Page::Uncounted(0, 100))
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryLimitExceeded(name, _, _)) if name == "page size"
+    ));
+    assert!(toql.sqls_empty());
+
+    let q = query!(Level1, "*, id in 1 2");
+    assert!(toql.load_page(q, Page::Counted(0, 10)).await.is_ok());
+}
@@ -0,0 +1,127 @@
+use pretty_assertions::assert_eq;
+use std::collections::HashMap;
+use toql::mock_db::MockDb;
+use toql::prelude::{query, Cache, ContextBuilder, Page, QueryParser, SqlArg, Toql, ToqlApi};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+#[toql(predicate(name = "older", sql = "..id > ?"))]
+pub struct Level1 {
+    #[toql(key)]
+    id: u64,
+    status: Option<String>,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn named_placeholders() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = QueryParser::parse::<Level1>("id, status eq :status, @older :id")
+        .unwrap()
+        .bind("status", "open")
+        .bind("id", 5);
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.status FROM Level1 level1 \
+        WHERE level1.status = 'open' AND level1.id > 5"
+    );
+
+    // Placeholder in list filter
+    let q = QueryParser::parse::<Level1>("id, status in 'closed' :status")
+        .unwrap()
+        .bind("status", "open");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.status FROM Level1 level1 \
+        WHERE level1.status IN ('closed', 'open')"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn missing_bind() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = QueryParser::parse::<Level1>("id, status eq :status").unwrap();
+    let err = toql.load_many(q).await.err().unwrap();
+    assert_eq!(err.to_string(), "query parameter `status` is missing");
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn aux_params_do_not_bind() {
+    let cache = Cache::new();
+    let mut aux_params = HashMap::new();
+    aux_params.insert("status".to_string(), SqlArg::from("open"));
+    let context = ContextBuilder::new().with_aux_params(aux_params).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    // Context aux params are not visible to placeholders
+    let q = QueryParser::parse::<Level1>("id, status eq :status").unwrap();
+    let err = toql.load_many(q).await.err().unwrap();
+    assert_eq!(err.to_string(), "query parameter `status` is missing");
+
+    // Neither are query aux params
+    let q = QueryParser::parse::<Level1>("id, status eq :status")
+        .unwrap()
+        .aux_param("status", "open");
+    let err = toql.load_many(q).await.err().unwrap();
+    assert_eq!(err.to_string(), "query parameter `status` is missing");
+
+    let q = QueryParser::parse::<Level1>("id, status eq :status")
+        .unwrap()
+        .bind("status", "closed");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1.status FROM Level1 level1 \
+        WHERE level1.status = 'closed'"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn distinct() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let q = query!(Level1, "distinct id");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT DISTINCT level1.id FROM Level1 level1"
+    );
+
+    let q = QueryParser::parse::<Level1>("DISTINCT status").unwrap();
+    assert_eq!(q.to_string(), "DISTINCT status");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT DISTINCT level1.id, level1.status FROM Level1 level1"
+    );
+
+    // Counts take distinct rows
+    let q = query!(Level1, "distinct status eq 'open'");
+    assert!(toql.count(&q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT COUNT(*) FROM (SELECT DISTINCT level1.id AS toql_c0, level1.status AS toql_c1 \
+        FROM Level1 level1 WHERE level1.status = 'open') toql_distinct"
+    );
+    assert!(toql.load_page(q, Page::Counted(0, 10)).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sqls(),
+        [
+            "SELECT DISTINCT level1.id, level1.status FROM Level1 level1 \
+            WHERE level1.status = 'open'",
+            "",
+            "SELECT COUNT(*) FROM (SELECT DISTINCT level1.id AS toql_c0, level1.status AS toql_c1 \
+            FROM Level1 level1) toql_distinct"
+        ]
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "serde")]
+use toql::prelude::{Field, Query, SqlArg};
+use toql::query::{predicate::Predicate, selection::Selection, wildcard::Wildcard};
+
+struct User;
+
+#[test]
+fn json_schema() {
+    let q = Query::<User>::from(Field::from("id").eq(5u64).asc(1));
+    assert_eq!(
+        serde_json::to_string(&q).unwrap(),
+        "{\"tokens\":[{\"field\":{\"concatenation\":\"and\",\"name\":\"id\",\"hidden\":false,\
+         \"order\":{\"asc\":1},\"filter\":{\"eq\":{\"u64\":5}}}}],\"distinct\":false,\
+         \"aux_params\":{},\"where_predicates\":[],\"where_predicate_params\":[],\
+         \"select_columns\":[],\"join_stmts\":[],\"join_stmt_params\":[],\"merge_limits\":{}}"
+    );
+
+    let q: Query<User> = serde_json::from_str(
+        "{\"tokens\":[{\"wildcard\":{\"concatenation\":\"and\",\"path\":\"\"}}]}",
+    )
+    .unwrap();
+    assert_eq!(q.to_string(), "*");
+}
+
+#[test]
+fn round_trip() {
+    let mut q = Query::<User>::from(Wildcard::from("level1"))
+        .and(Field::from("id").hide().bw(1, 10).desc(2))
+        .or_parentized(
+            Query::from(Predicate::from("search").are(vec!["A", "B"]))
+                .and(Field::from("text").ins(vec![SqlArg::Null, SqlArg::F64(0.5)]))
+                .or(Selection::from("std")),
+        )
+        .and(Field::from("flag").eq(true))
+        .aux_param("tenant", 7u64)
+        .limit_merge("level1", 3);
+    q.distinct = true;
+    q.where_predicates.push("level1.id > ?".to_string());
+    q.where_predicate_params.push(SqlArg::I64(-1));
+    q.select_columns.push("COUNT(*)".to_string());
+    q.join_stmts
+        .push("JOIN Extra e ON (e.id = level1.id AND e.x = ?)".to_string());
+    q.join_stmt_params.push(SqlArg::Str("x".to_string()));
+
+    let json = serde_json::to_string(&q).unwrap();
+    let d: Query<User> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(d.to_string(), q.to_string());
+    assert_eq!(d.distinct, true);
+    assert_eq!(d.aux_params.get("tenant"), Some(&SqlArg::U64(7)));
+    assert_eq!(d.where_predicates, q.where_predicates);
+    assert_eq!(d.where_predicate_params, q.where_predicate_params);
+    assert_eq!(d.select_columns, q.select_columns);
+    assert_eq!(d.join_stmts, q.join_stmts);
+    assert_eq!(d.join_stmt_params, q.join_stmt_params);
+    assert_eq!(d.merge_limits.get("level1"), Some(&3));
+    assert_eq!(serde_json::to_string(&d).unwrap(), json);
+}
+
+#[test]
+fn query_error() {
+    use toql::prelude::{QueryParser, ToqlError};
+    let e = match QueryParser::parse::<User>("id eq") {
+        Err(ToqlError::QueryError(e)) => e,
+        _ => panic!("expected query error"),
+    };
+    assert_eq!(
+        serde_json::to_string(&e).unwrap(),
+        "{\"kind\":\"syntax\",\"start\":5,\"end\":5,\"token\":\"\",\
+         \"expected\":[\"value\"],\"suggestions\":[]}"
+    );
+}
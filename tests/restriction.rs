@@ -0,0 +1,129 @@
+use pretty_assertions::assert_eq;
+use toql::mock_db::MockDb;
+use toql::prelude::{query, Cache, ContextBuilder, Field, Join, Query, Toql, ToqlApi};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+#[toql(predicate(name = "mine", sql = "..owner = <user>"))]
+pub struct Level1 {
+    #[toql(key)]
+    id: u64,
+    text: Option<String>,
+    #[toql(roles(load = "admin"))]
+    owner: Option<u64>,
+
+    #[toql(join)]
+    level2: Option<Option<Join<Level2>>>,
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Level2 {
+    #[toql(key)]
+    id: u64,
+    tenant: Option<u64>,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn select() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new()
+        .with_restriction(query!(Level1, "owner eq 5"))
+        .build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    // Restriction without user filter
+    let q = query!(Level1, "id");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id FROM Level1 level1 WHERE level1.owner = 5"
+    );
+
+    // OR in user query cannot bypass restriction
+    let q = query!(Level1, "id eq 1; id eq 2");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id FROM Level1 level1 \
+            WHERE (level1.id = 1 OR level1.id = 2) AND (level1.owner = 5)"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn count_and_delete() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new()
+        .with_restriction(query!(Level1, "owner eq 5"))
+        .build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Level1, "text eq 'ABC'");
+    assert!(toql.count(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT COUNT(*) FROM Level1 level1 \
+            WHERE (level1.text = 'ABC') AND (level1.owner = 5)"
+    );
+
+    let q = query!(Level1, "id eq 1");
+    assert!(toql.delete_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "DELETE level1 FROM Level1 level1 \
+            WHERE (level1.id = 1) AND (level1.owner = 5)"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn joined_restriction() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new()
+        .with_restriction(query!(Level1, "owner eq 5"))
+        .with_restriction(Query::<Level1>::from(Field::from("level2_tenant").eq(7)))
+        .build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Level1, "id");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id, level1_level2.id FROM Level1 level1 \
+            LEFT JOIN (Level2 level1_level2) \
+            ON (level1.level2_id = level1_level2.id) \
+            WHERE (level1.owner = 5) AND (level1_level2.tenant = 7)"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn restriction_aux_params() {
+    let cache = Cache::new();
+    let context = ContextBuilder::new()
+        .with_restriction(query!(Level1, "@mine").aux_param("user", 5))
+        .build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Level1, "id");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT level1.id FROM Level1 level1 WHERE level1.owner = 5"
+    );
+    let q = query!(Level1, "text eq 'ABC'");
+    assert!(toql.count(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT COUNT(*) FROM Level1 level1 \
+            WHERE (level1.text = 'ABC') AND (level1.owner = 5)"
+    );
+    let q = query!(Level1, "id eq 1");
+    assert!(toql.delete_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "DELETE level1 FROM Level1 level1 \
+            WHERE (level1.id = 1) AND (level1.owner = 5)"
+    );
+}
@@ -0,0 +1,108 @@
+use pretty_assertions::assert_eq;
+use toql::backend::routing::RoutingBackend;
+use toql::backend::Backend;
+use toql::mock_db::MockDb;
+use toql::prelude::{fields, paths, query, Cache, Page, Sql, Toql, ToqlApi};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+#[toql(auto_key)]
+pub struct User {
+    #[toql(key)]
+    id: u64,
+    name: String,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn route_selects_to_replica() {
+    let cache = Cache::new();
+    let mut toql = RoutingBackend::new(MockDb::from(&cache), MockDb::from(&cache));
+
+    let q = query!(User, "*");
+    let _ = toql.load_page(&q, Page::Counted(0, 10)).await.unwrap();
+    let _ = toql.count(query!(User, "id eq 5")).await.unwrap();
+    assert!(toql.primary_mut().sqls_empty());
+    assert_eq!(
+        toql.replica_mut().take_unsafe_sqls(),
+        [
+            "SELECT user.id, user.name FROM User user",
+            "",
+            "SELECT COUNT(*) FROM User user",
+            "SELECT COUNT(*) FROM User user WHERE user.id = 5"
+        ]
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn route_to_primary_after_write() {
+    let cache = Cache::new();
+    let mut toql = RoutingBackend::new(MockDb::from(&cache), MockDb::from(&cache));
+
+    let mut u = User {
+        id: 0,
+        name: "Alice".to_string(),
+    };
+    toql.insert_one(&mut u, paths!(top)).await.unwrap();
+    assert!(toql.reads_primary());
+    let _ = toql.load_many(query!(User, "*")).await.unwrap();
+    assert!(toql.replica_mut().sqls_empty());
+    assert_eq!(
+        toql.primary_mut().take_unsafe_sqls(),
+        [
+            "INSERT INTO User (name) VALUES ('Alice')",
+            "SELECT user.id, user.name FROM User user"
+        ]
+    );
+
+    // Back to replica after unit of work
+    toql.end_unit();
+    let _ = toql.load_many(query!(User, "*")).await.unwrap();
+    assert!(toql.primary_mut().sqls_empty());
+    assert_eq!(
+        toql.replica_mut().take_unsafe_sql(),
+        "SELECT user.id, user.name FROM User user"
+    );
+
+    // Updates are writes too
+    toql.update_one(&mut u, fields!(top)).await.unwrap();
+    let _ = toql.load_many(query!(User, "*")).await.unwrap();
+    assert!(toql.replica_mut().sqls_empty());
+    assert_eq!(toql.primary_mut().take_unsafe_sqls().len(), 2);
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn route_transactions_to_primary() {
+    let cache = Cache::new();
+    let mut toql = RoutingBackend::new(MockDb::from(&cache), MockDb::from(&cache));
+
+    toql.begin_transaction();
+    let _ = toql.load_many(query!(User, "*")).await.unwrap();
+    toql.end_transaction();
+    assert!(!toql.reads_primary());
+    assert!(toql.replica_mut().sqls_empty());
+    assert_eq!(
+        toql.primary_mut().take_unsafe_sql(),
+        "SELECT user.id, user.name FROM User user"
+    );
+
+    // Transactions controlled by statements
+    toql.execute_sql(Sql("START TRANSACTION".to_string(), vec![]))
+        .await
+        .unwrap();
+    assert!(toql.reads_primary());
+    toql.execute_sql(Sql("COMMIT".to_string(), vec![]))
+        .await
+        .unwrap();
+    assert!(!toql.reads_primary());
+}
+
+#[test]
+#[should_panic(expected = "primary and replica backend must share the same cache")]
+fn require_shared_cache() {
+    let primary_cache = Cache::new();
+    let replica_cache = Cache::new();
+    RoutingBackend::new(MockDb::from(&primary_cache), MockDb::from(&replica_cache));
+}
@@ -0,0 +1,79 @@
+use toql::mock_db::snapshot::Snapshot;
+use toql::prelude::{query, Cache, Join, Toql};
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Clone, Toql)]
+#[toql(auto_key)]
+pub struct Book {
+    #[toql(key)]
+    id: u64,
+    title: String,
+    pages: Option<u64>,
+    #[toql(join)]
+    author: Option<Join<Author>>,
+    #[toql(merge)]
+    chapters: Vec<Chapter>,
+}
+
+#[derive(Debug, Default, Clone, Toql)]
+pub struct Author {
+    #[toql(key)]
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, Default, Clone, Toql)]
+pub struct Chapter {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    book_id: u64,
+    title: String,
+}
+
+fn book() -> Book {
+    Book {
+        id: 1,
+        title: "Rust".to_string(),
+        pages: Some(300),
+        author: Some(Join::with_key(AuthorKey::from(7))),
+        chapters: Vec::new(),
+    }
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn snapshots_match() {
+    let cache = Cache::new();
+    let snapshot = Snapshot::new(&cache, "tests/snapshots");
+    let book = book();
+    snapshot
+        .assert(
+            &book,
+            &[
+                query!(Book, "*, author_name, id eq 1"),
+                query!(Book, "title lk 'R%', author_id eq 7"),
+            ],
+        )
+        .await;
+
+    // Insert sets the auto key only on a clone
+    assert_eq!(book.id, 1);
+}
+
+#[tokio::test]
+#[traced_test("info")]
+#[should_panic(
+    expected = "does not match, set TOQL_UPDATE_SNAPSHOTS=1 to update it:\n- -- Outdated\n+ -- Canonical"
+)]
+async fn snapshots_differ() {
+    let dir = std::env::temp_dir().join("toql_snapshot_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("Book.sql"), "-- Outdated\n").unwrap();
+
+    let cache = Cache::new();
+    let snapshot = Snapshot::new(&cache, dir).update(false);
+    snapshot
+        .assert(&book(), &[query!(Book, "*, id eq 1")])
+        .await;
+}
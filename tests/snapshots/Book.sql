@@ -0,0 +1,72 @@
+-- Canonical
+-- load `*,author_name,id EQ 1`
+SELECT book.id, book.title, book.pages, book_author.id, book_author.name FROM Book book JOIN (Author book_author) ON (book.author_id = book_author.id) WHERE book.id = 1
+-- count `*,author_name,id EQ 1`
+SELECT COUNT(*) FROM Book book WHERE book.id = 1
+-- load `title LK 'R%',author_id EQ 7`
+SELECT book.id, book.title, book_author.id, book_author.name FROM Book book JOIN (Author book_author) ON (book.author_id = book_author.id) WHERE book.title LIKE 'R%' AND book_author.id = 7
+-- count `title LK 'R%',author_id EQ 7`
+SELECT COUNT(*) FROM Book book JOIN (Author book_author) ON (book.author_id = book_author.id) WHERE book.title LIKE 'R%' AND book_author.id = 7
+-- insert
+INSERT INTO Book (title, pages, author_id) VALUES ('Rust', 300, 7)
+-- update
+UPDATE Book SET title = 'Rust', pages = 300, author_id = 7 WHERE id = 100
+-- delete `*,author_name,id EQ 1`
+DELETE book FROM Book book WHERE book.id = 1
+-- delete `title LK 'R%',author_id EQ 7`
+DELETE book FROM Book book JOIN (Author book_author) ON (book.author_id = book_author.id) WHERE book.title LIKE 'R%' AND book_author.id = 7
+
+-- MediumIndex
+-- load `*,author_name,id EQ 1`
+SELECT book1.id, book1.title, book1.pages, author2.id, author2.name FROM Book book1 JOIN (Author author2) ON (book1.author_id = author2.id) WHERE book1.id = 1
+-- count `*,author_name,id EQ 1`
+SELECT COUNT(*) FROM Book book1 WHERE book1.id = 1
+-- load `title LK 'R%',author_id EQ 7`
+SELECT book1.id, book1.title, author2.id, author2.name FROM Book book1 JOIN (Author author2) ON (book1.author_id = author2.id) WHERE book1.title LIKE 'R%' AND author2.id = 7
+-- count `title LK 'R%',author_id EQ 7`
+SELECT COUNT(*) FROM Book book1 JOIN (Author author2) ON (book1.author_id = author2.id) WHERE book1.title LIKE 'R%' AND author2.id = 7
+-- insert
+INSERT INTO Book (title, pages, author_id) VALUES ('Rust', 300, 7)
+-- update
+UPDATE Book SET title = 'Rust', pages = 300, author_id = 7 WHERE id = 100
+-- delete `*,author_name,id EQ 1`
+DELETE book1 FROM Book book1 WHERE book1.id = 1
+-- delete `title LK 'R%',author_id EQ 7`
+DELETE book1 FROM Book book1 JOIN (Author author2) ON (book1.author_id = author2.id) WHERE book1.title LIKE 'R%' AND author2.id = 7
+
+-- ShortIndex
+-- load `*,author_name,id EQ 1`
+SELECT bo1.id, bo1.title, bo1.pages, au2.id, au2.name FROM Book bo1 JOIN (Author au2) ON (bo1.author_id = au2.id) WHERE bo1.id = 1
+-- count `*,author_name,id EQ 1`
+SELECT COUNT(*) FROM Book bo1 WHERE bo1.id = 1
+-- load `title LK 'R%',author_id EQ 7`
+SELECT bo1.id, bo1.title, au2.id, au2.name FROM Book bo1 JOIN (Author au2) ON (bo1.author_id = au2.id) WHERE bo1.title LIKE 'R%' AND au2.id = 7
+-- count `title LK 'R%',author_id EQ 7`
+SELECT COUNT(*) FROM Book bo1 JOIN (Author au2) ON (bo1.author_id = au2.id) WHERE bo1.title LIKE 'R%' AND au2.id = 7
+-- insert
+INSERT INTO Book (title, pages, author_id) VALUES ('Rust', 300, 7)
+-- update
+UPDATE Book SET title = 'Rust', pages = 300, author_id = 7 WHERE id = 100
+-- delete `*,author_name,id EQ 1`
+DELETE bo1 FROM Book bo1 WHERE bo1.id = 1
+-- delete `title LK 'R%',author_id EQ 7`
+DELETE bo1 FROM Book bo1 JOIN (Author au2) ON (bo1.author_id = au2.id) WHERE bo1.title LIKE 'R%' AND au2.id = 7
+
+-- TinyIndex
+-- load `*,author_name,id EQ 1`
+SELECT t1.id, t1.title, t1.pages, t2.id, t2.name FROM Book t1 JOIN (Author t2) ON (t1.author_id = t2.id) WHERE t1.id = 1
+-- count `*,author_name,id EQ 1`
+SELECT COUNT(*) FROM Book t1 WHERE t1.id = 1
+-- load `title LK 'R%',author_id EQ 7`
+SELECT t1.id, t1.title, t2.id, t2.name FROM Book t1 JOIN (Author t2) ON (t1.author_id = t2.id) WHERE t1.title LIKE 'R%' AND t2.id = 7
+-- count `title LK 'R%',author_id EQ 7`
+SELECT COUNT(*) FROM Book t1 JOIN (Author t2) ON (t1.author_id = t2.id) WHERE t1.title LIKE 'R%' AND t2.id = 7
+-- insert
+INSERT INTO Book (title, pages, author_id) VALUES ('Rust', 300, 7)
+-- update
+UPDATE Book SET title = 'Rust', pages = 300, author_id = 7 WHERE id = 100
+-- delete `*,author_name,id EQ 1`
+DELETE t1 FROM Book t1 WHERE t1.id = 1
+-- delete `title LK 'R%',author_id EQ 7`
+DELETE t1 FROM Book t1 JOIN (Author t2) ON (t1.author_id = t2.id) WHERE t1.title LIKE 'R%' AND t2.id = 7
+
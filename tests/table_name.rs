@@ -0,0 +1,129 @@
+use pretty_assertions::assert_eq;
+use std::collections::HashMap;
+use toql::mock_db::MockDb;
+use toql::prelude::{
+    fields, paths, query, Cache, ContextBuilder, Join, SqlArg, Toql, ToqlApi, ToqlError,
+};
+use toql::sql_expr::resolver_error::ResolverError;
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+#[toql(table = "<schema>.Order_<year>")]
+pub struct Order {
+    #[toql(key)]
+    id: u64,
+    title: String,
+    #[toql(join)]
+    customer: Option<Join<Customer>>,
+    #[toql(merge)]
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Default, Toql)]
+#[toql(table = "<schema>.Customer")]
+pub struct Customer {
+    #[toql(key)]
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, Default, Toql)]
+#[toql(table = "<schema>.Item")]
+pub struct Item {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    order_id: u64,
+    title: String,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn load() {
+    let cache = Cache::new();
+    let mut aux_params = HashMap::new();
+    aux_params.insert("schema".to_string(), SqlArg::from("acme"));
+    aux_params.insert("year".to_string(), SqlArg::from(2024u64));
+    let context = ContextBuilder::new().with_aux_params(aux_params).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let select = "SELECT schemaOrderYear.id, schemaOrderYear.title, \
+        schemaOrderYear_customer.id, schemaOrderYear_customer.name \
+        FROM acme.Order_2024 schemaOrderYear \
+        JOIN (acme.Customer schemaOrderYear_customer) \
+        ON (schemaOrderYear.customer_id = schemaOrderYear_customer.id)";
+    toql.mock_rows(select, vec![toql::row!(1u64, "ABC", 3u64, "Alice")]);
+    let merge_select =
+        "SELECT schemaOrderYear.id, schemaItem.id, schemaItem.order_id, schemaItem.title \
+        FROM acme.Item schemaItem \
+        JOIN acme.Order_2024 schemaOrderYear \
+        ON (schemaOrderYear.id = schemaItem.order_id AND schemaOrderYear.id = 1)";
+    toql.mock_rows(merge_select, vec![]);
+
+    let q = query!(Order, "id, customer_name, items_title");
+    toql.load_many(q).await.unwrap();
+    assert_eq!(toql.take_unsafe_sqls(), [select, merge_select]);
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn insert_update_delete() {
+    let cache = Cache::new();
+    let mut aux_params = HashMap::new();
+    aux_params.insert("schema".to_string(), SqlArg::from("acme"));
+    aux_params.insert("year".to_string(), SqlArg::from(2024u64));
+    let context = ContextBuilder::new().with_aux_params(aux_params).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let mut o = Order {
+        id: 1,
+        title: "ABC".to_string(),
+        customer: None,
+        items: vec![Item {
+            id: 2,
+            order_id: 1,
+            title: "Book".to_string(),
+        }],
+    };
+    toql.insert_one(&mut o, paths!(Order, "items"))
+        .await
+        .unwrap();
+    toql.update_one(&mut o, fields!(Order, "*, items"))
+        .await
+        .unwrap();
+    toql.delete_one(OrderKey::from(1)).await.unwrap();
+    assert_eq!(
+        toql.take_unsafe_sqls(),
+        [
+            "INSERT INTO acme.Order_2024 (id, title, customer_id) VALUES (1, 'ABC', DEFAULT)",
+            "INSERT INTO acme.Item (id, order_id, title) VALUES (2, 1, 'Book')",
+            "UPDATE acme.Order_2024 SET title = 'ABC' WHERE id = 1",
+            "DELETE schemaOrderYear_items FROM acme.Item schemaOrderYear_items \
+                JOIN acme.Order_2024 schemaOrderYear ON schemaOrderYear.id = schemaOrderYear_items.order_id \
+                WHERE schemaOrderYear.id = 1 \
+                AND NOT (schemaOrderYear_items.id = 2 AND schemaOrderYear_items.order_id = 1)",
+            "DELETE schemaOrderYear FROM acme.Order_2024 schemaOrderYear WHERE schemaOrderYear.id = 1"
+        ]
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn missing_aux_param() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let err = toql.load_many(query!(Order, "*")).await.err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        ToqlError::SqlExprResolverError(ResolverError::TableAuxParamMissing(
+            "schema".to_string(),
+            "<schema>.Order_<year>".to_string()
+        ))
+        .to_string()
+    );
+
+    let mut o = Order::default();
+    assert!(toql.insert_one(&mut o, paths!(top)).await.is_err());
+    assert!(toql.sqls_empty());
+}
@@ -0,0 +1,213 @@
+use pretty_assertions::assert_eq;
+use std::collections::HashMap;
+use toql::mock_db::MockDb;
+use toql::prelude::{
+    fields, paths, query, Cache, ContextBuilder, Join, Page, ResolverError, SqlArg, Toql, ToqlApi,
+    ToqlError,
+};
+use toql::sql_builder::sql_builder_error::SqlBuilderError;
+use tracing_test::traced_test;
+
+#[derive(Debug, Default, Toql)]
+#[toql(auto_key, tenant = "tenant_id", aux_param = "tenant")]
+pub struct Book {
+    #[toql(key)]
+    id: u64,
+    tenant_id: u64,
+    title: String,
+    #[toql(join)]
+    author: Option<Join<Author>>,
+    #[toql(merge)]
+    chapters: Vec<Chapter>,
+    #[toql(count_of = "chapters", skip_wildcard)]
+    chapter_count: Option<u64>,
+}
+
+#[derive(Debug, Default, Toql)]
+#[toql(tenant = "tenant_id")]
+pub struct Author {
+    #[toql(key)]
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, Default, Toql)]
+#[toql(tenant = "tenant_id")]
+pub struct Chapter {
+    #[toql(key)]
+    id: u64,
+    #[toql(key)]
+    book_id: u64,
+    title: String,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn filter_selects() {
+    let cache = Cache::new();
+    let mut aux_params = HashMap::new();
+    aux_params.insert("tenant".to_string(), SqlArg::U64(7));
+    let context = ContextBuilder::new().with_aux_params(aux_params).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Book, "id, title eq 'ABC'; title eq 'DEF', author_name");
+    assert!(toql.load_many(q).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "SELECT book.id, book.tenant_id, book.title, book_author.id, book_author.name \
+            FROM Book book \
+            JOIN (Author book_author) ON (book.author_id = book_author.id AND book_author.tenant_id = 7) \
+            WHERE (book.title = 'ABC' OR book.title = 'DEF') AND book.tenant_id = 7"
+    );
+
+    // Merged entities
+    let merge_sql = "SELECT book.id, chapter.id, chapter.book_id, chapter.title \
+        FROM Chapter chapter \
+        JOIN Book book ON (book.id = chapter.book_id AND book.id = 1) \
+        WHERE chapter.tenant_id = 7";
+    toql.mock_rows(
+        "SELECT book.id, book.tenant_id, book.title FROM Book book WHERE book.tenant_id = 7",
+        vec![toql::row!(1u64, 7u64, "ABC")],
+    );
+    toql.mock_rows(merge_sql, vec![]);
+    let q = query!(Book, "*, chapters_title");
+    let books = toql.load_many(q).await.unwrap();
+    assert_eq!(books[0].tenant_id, 7);
+    assert_eq!(toql.take_unsafe_sqls()[1], merge_sql);
+
+    // Subqueries of merged entities
+    let select = "SELECT book.id, book.tenant_id, book.title, \
+        (SELECT COUNT(*) FROM Chapter book_chapters \
+        WHERE book.id = book_chapters.book_id AND book_chapters.tenant_id = 7) \
+        FROM Book book \
+        WHERE (EXISTS (SELECT 1 FROM Chapter book_chapters \
+        WHERE book.id = book_chapters.book_id AND book_chapters.tenant_id = 7 \
+        AND book_chapters.title = 'Intro')) AND book.tenant_id = 7";
+    toql.mock_rows(select, vec![toql::row!(1u64, 7u64, "ABC", 2u64)]);
+    toql.mock_rows(merge_sql, vec![]);
+    let q = query!(Book, "id, chapterCount, any .chapters_title eq 'Intro'");
+    let books = toql.load_many(q).await.unwrap();
+    assert_eq!(toql.take_unsafe_sqls()[0], select);
+    assert_eq!(books[0].chapter_count, Some(2));
+
+    // Entities without tenant count merged entities of the tenant
+    let select = "SELECT series.id, \
+        (SELECT COUNT(*) FROM Chapter series_chapters \
+        WHERE series.id = series_chapters.series_id AND series_chapters.tenant_id = 7) \
+        FROM Series series";
+    toql.mock_rows(select, vec![toql::row!(3u64, 4u64)]);
+    let q = query!(Series, "id, chapterCount").aux_param("tenant", 9u64);
+    let series = toql.load_many(q).await.unwrap();
+    assert_eq!(toql.take_unsafe_sql(), select);
+    assert_eq!((series[0].id, series[0].chapter_count), (3, Some(4)));
+    assert!(series[0].chapters.is_none());
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn filter_counts_and_deletes() {
+    let cache = Cache::new();
+    let mut aux_params = HashMap::new();
+    aux_params.insert("tenant".to_string(), SqlArg::U64(7));
+    let context = ContextBuilder::new().with_aux_params(aux_params).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let q = query!(Book, "id, title eq 'ABC'");
+    assert!(toql.load_page(q, Page::Counted(0, 10)).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sqls(),
+        [
+            "SELECT book.id, book.tenant_id, book.title FROM Book book WHERE (book.title = 'ABC') AND book.tenant_id = 7",
+            "",
+            "SELECT COUNT(*) FROM Book book WHERE book.tenant_id = 7"
+        ]
+    );
+
+    assert!(toql.delete_one(BookKey::from(5)).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "DELETE book FROM Book book WHERE (book.id = 5) AND book.tenant_id = 7"
+    );
+}
+
+#[derive(Debug, Default, Toql)]
+pub struct Series {
+    #[toql(key)]
+    id: u64,
+    #[toql(merge)]
+    chapters: Option<Vec<Chapter>>,
+    #[toql(count_of = "chapters")]
+    chapter_count: Option<u64>,
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn fill_inserts_and_filter_updates() {
+    let cache = Cache::new();
+    let mut aux_params = HashMap::new();
+    aux_params.insert("tenant".to_string(), SqlArg::U64(7));
+    let context = ContextBuilder::new().with_aux_params(aux_params).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let mut b = Book {
+        id: 0,
+        tenant_id: 99,
+        title: "ABC".to_string(),
+        author: None,
+        chapters: vec![Chapter {
+            id: 1,
+            book_id: 0,
+            title: "Intro".to_string(),
+        }],
+        chapter_count: None,
+    };
+    assert!(toql
+        .insert_one(&mut b, paths!(Book, "chapters"))
+        .await
+        .is_ok());
+    assert_eq!(
+        toql.take_unsafe_sqls(),
+        [
+            "INSERT INTO Book (title, author_id, tenant_id) VALUES ('ABC', DEFAULT, 7)",
+            "INSERT INTO Chapter (id, book_id, title, tenant_id) VALUES (1, 100, 'Intro', 7)"
+        ]
+    );
+    assert_eq!(b.tenant_id, 7);
+
+    b.tenant_id = 99;
+    assert!(toql.update_one(&mut b, fields!(Book, "*")).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "UPDATE Book SET title = 'ABC' WHERE id = 100 AND tenant_id = 7"
+    );
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn require_tenant() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    let err = toql.load_many(query!(Book, "*")).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryParamMissing(p)) if p == "tenant"
+    ));
+
+    let mut b = Book::default();
+    let err = toql.insert_one(&mut b, paths!(top)).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlExprResolverError(ResolverError::AuxParamMissing(p)) if p == "tenant"
+    ));
+    assert!(toql.sqls_empty());
+
+    // Queries cannot choose the tenant of a count subquery
+    let q = query!(Series, "id, chapterCount").aux_param("tenant", 7u64);
+    let err = toql.load_many(q).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ToqlError::SqlBuilderError(SqlBuilderError::QueryParamMissing(p)) if p == "tenant"
+    ));
+    assert!(toql.sqls_empty());
+}
@@ -0,0 +1,193 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use pretty_assertions::assert_eq;
+use std::sync::Arc;
+use toql::clock::Timestamps;
+use toql::mock_db::MockDb;
+use toql::prelude::{fields, paths, Cache, ContextBuilder, SqlArg, Toql, ToqlApi};
+use tracing_test::traced_test;
+
+#[derive(Debug, Toql)]
+#[toql(auto_key)]
+pub struct Post {
+    #[toql(key)]
+    id: u64,
+    title: String,
+    #[toql(created_at)]
+    created_at: Option<NaiveDateTime>,
+    #[toql(updated_at)]
+    updated_at: Option<NaiveDateTime>,
+    #[toql(merge)]
+    comments: Vec<Comment>,
+}
+
+#[derive(Debug, Toql)]
+#[toql(auto_key)]
+pub struct Comment {
+    #[toql(key)]
+    id: u64,
+    #[toql(foreign_key)]
+    post_id: u64,
+    text: String,
+    #[toql(created_at)]
+    created_at: NaiveDateTime,
+}
+
+fn time(hour: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 5, 1)
+        .unwrap()
+        .and_hms_opt(hour, 0, 0)
+        .unwrap()
+}
+
+fn comment(id: u64, text: &str) -> Comment {
+    Comment {
+        id,
+        post_id: 0,
+        text: text.to_string(),
+        created_at: time(8),
+    }
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn fill_with_system_clock() {
+    let cache = Cache::new();
+    let mut toql = MockDb::from(&cache);
+
+    // Row separators in values must not generate more ids
+    let mut p = Post {
+        id: 0,
+        title: "ABC), (DEF".to_string(),
+        created_at: Some(time(8)),
+        updated_at: None,
+        comments: vec![comment(0, "Hello")],
+    };
+    assert!(toql
+        .insert_one(&mut p, paths!(Post, "comments"))
+        .await
+        .is_ok());
+    let now = p.created_at.unwrap();
+    assert_ne!(now, time(8));
+    assert_eq!(p.updated_at, Some(now));
+    assert_eq!(p.comments[0].created_at, now);
+    assert_eq!(
+        toql.take_unsafe_sqls(),
+        [
+            format!(
+                "INSERT INTO Post (title, created_at, updated_at) VALUES ('ABC), (DEF', '{0}', '{0}')",
+                now
+            ),
+            format!(
+                "INSERT INTO Comment (post_id, text, created_at) VALUES (100, 'Hello', '{}')",
+                now
+            )
+        ]
+    );
+
+    p.updated_at = None;
+    assert!(toql.update_one(&mut p, fields!(Post, "*")).await.is_ok());
+    let now = p.updated_at.unwrap();
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        format!(
+            "UPDATE Post SET title = 'ABC), (DEF', updated_at = '{}' WHERE id = 100",
+            now
+        )
+    );
+
+    // Timestamps can't be updated from the entity
+    assert!(toql
+        .update_one(&mut p, fields!(Post, "createdAt, updatedAt"))
+        .await
+        .is_ok());
+    assert!(toql.sqls_empty());
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn fill_with_clock() {
+    let cache = Cache::new();
+    let clock = Arc::new(|| SqlArg::from(time(12)));
+    let context = ContextBuilder::new().with_clock(clock).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let mut p = Post {
+        id: 0,
+        title: "ABC".to_string(),
+        created_at: None,
+        updated_at: None,
+        comments: Vec::new(),
+    };
+    assert!(toql.insert_one(&mut p, paths!(top)).await.is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "INSERT INTO Post (title, created_at, updated_at) \
+            VALUES ('ABC', '2024-05-01 12:00:00', '2024-05-01 12:00:00')"
+    );
+    assert_eq!(p.created_at, Some(time(12)));
+    assert_eq!(p.updated_at, Some(time(12)));
+
+    // Only new merges get a creation time
+    p.created_at = Some(time(9));
+    p.comments = vec![comment(5, "Old"), comment(0, "New")];
+    assert!(toql
+        .update_one(&mut p, fields!(Post, "title, comments"))
+        .await
+        .is_ok());
+    let sqls = toql.take_unsafe_sqls();
+    assert_eq!(
+        sqls[0],
+        "UPDATE Post SET title = 'ABC', updated_at = '2024-05-01 12:00:00' WHERE id = 100"
+    );
+    assert_eq!(
+        sqls[2],
+        "INSERT INTO Comment (post_id, text, created_at) VALUES (100, 'New', '2024-05-01 12:00:00')"
+    );
+    assert_eq!(p.created_at, Some(time(9)));
+    assert_eq!(p.updated_at, Some(time(12)));
+    assert_eq!(p.comments[0].created_at, time(8));
+    assert_eq!(p.comments[1].created_at, time(12));
+}
+
+#[tokio::test]
+#[traced_test("info")]
+async fn fill_with_database() {
+    let cache = Cache::new();
+    let timestamps = Timestamps::Database("NOW()".to_string());
+    let context = ContextBuilder::new().with_timestamps(timestamps).build();
+    let mut toql = MockDb::with_context(&cache, context);
+
+    let mut p = Post {
+        id: 0,
+        title: "ABC".to_string(),
+        created_at: None,
+        updated_at: None,
+        comments: vec![comment(0, "New")],
+    };
+    assert!(toql
+        .insert_one(&mut p, paths!(Post, "comments"))
+        .await
+        .is_ok());
+    assert_eq!(
+        toql.take_unsafe_sqls(),
+        [
+            "INSERT INTO Post (title, created_at, updated_at) VALUES ('ABC', NOW(), NOW())",
+            "INSERT INTO Comment (post_id, text, created_at) VALUES (100, 'New', NOW())"
+        ]
+    );
+
+    // The database time is not written back
+    assert_eq!(p.created_at, None);
+    assert_eq!(p.updated_at, None);
+    assert_eq!(p.comments[0].created_at, time(8));
+
+    assert!(toql
+        .update_one(&mut p, fields!(Post, "title"))
+        .await
+        .is_ok());
+    assert_eq!(
+        toql.take_unsafe_sql(),
+        "UPDATE Post SET title = 'ABC', updated_at = NOW() WHERE id = 100"
+    );
+    assert_eq!(p.updated_at, None);
+}